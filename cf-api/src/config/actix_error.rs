@@ -70,3 +70,18 @@ pub fn json_error_handler(
 
   error::InternalError::from_response(err, HttpResponse::BadRequest().json(response_json)).into()
 }
+
+pub fn multipart_error_handler(
+  err: actix_multipart::MultipartError,
+  _req: &actix_web::HttpRequest,
+) -> error::Error {
+  // Keep the status picked by actix-multipart, 413 for oversized uploads
+  let status = error::ResponseError::status_code(&err);
+  let response_json: ResponseApi<()> = ResponseApi {
+    status: false,
+    message: format!("Invalid multipart form: {}", err),
+    data: None,
+  };
+
+  error::InternalError::from_response(err, HttpResponse::build(status).json(response_json)).into()
+}
@@ -0,0 +1,58 @@
+use std::env;
+use std::net::IpAddr;
+use std::time::Duration;
+
+use cf_entity::entity::sea_orm_active_enums::TrendingPeriod;
+
+use super::{env_or, trending};
+
+/// Tuning for view recording and the daily rollup.
+#[derive(Clone, Debug)]
+pub struct AnalyticsConfig {
+  /// Repeat views by the same visitor within this window count once
+  pub dedup_window: Duration,
+  /// Pending views kept in memory before new ones are dropped
+  pub queue_capacity: usize,
+  pub flush_interval: Duration,
+  pub flush_batch_size: usize,
+  pub rollup_interval: Duration,
+  /// Days recomputed on every rollup, today included
+  pub rollup_lookback_days: i64,
+  /// Raw views older than this are deleted after the rollup
+  pub raw_retention_days: i64,
+  /// Peers whose `X-Forwarded-For` entries are believed
+  pub trusted_proxies: Vec<IpAddr>,
+}
+
+impl AnalyticsConfig {
+  pub fn from_env() -> Self {
+    let rollup_lookback_days = env_or("VIEW_ROLLUP_LOOKBACK_DAYS", 2i64).max(1);
+
+    Self {
+      dedup_window: Duration::from_secs(env_or("VIEW_DEDUP_WINDOW_MINUTES", 30u64).max(1) * 60),
+      queue_capacity: env_or("VIEW_QUEUE_CAPACITY", 10_000usize).max(1),
+      flush_interval: Duration::from_secs(env_or("VIEW_FLUSH_INTERVAL_SECS", 5u64).max(1)),
+      flush_batch_size: env_or("VIEW_FLUSH_BATCH_SIZE", 500usize).max(1),
+      rollup_interval: Duration::from_secs(env_or("VIEW_ROLLUP_INTERVAL_SECS", 300u64).max(1)),
+      rollup_lookback_days,
+      // Raw rows must outlive the lookback or the rollup would undercount,
+      // and the longest trending window or rankings would miss views
+      raw_retention_days: env_or("VIEW_RAW_RETENTION_DAYS", 30i64)
+        .max(rollup_lookback_days)
+        .max(trending::window(TrendingPeriod::Week).num_days()),
+      trusted_proxies: env::var("VIEW_TRUSTED_PROXIES")
+        .unwrap_or_default()
+        .split(',')
+        .map(str::trim)
+        .filter(|ip| !ip.is_empty())
+        .filter_map(|ip| match ip.parse() {
+          Ok(ip) => Some(ip),
+          Err(_) => {
+            tracing::warn!("Ignoring invalid address in VIEW_TRUSTED_PROXIES: {}", ip);
+            None
+          }
+        })
+        .collect(),
+    }
+  }
+}
@@ -0,0 +1,220 @@
+use std::collections::HashSet;
+use std::env;
+use std::future::{Ready, ready};
+use std::sync::Arc;
+
+use actix_web::{FromRequest, HttpRequest, dev::Payload, http::header, web};
+use base64::Engine;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use hmac::{Hmac, Mac};
+use serde::Deserialize;
+use sha2::Sha256;
+use uuid::Uuid;
+
+use crate::config::custom_error::AppError;
+use crate::config::{env_or, is_development};
+
+/// Verifies the access tokens issued by the auth service: HS256 JWTs signed
+/// with `AUTH_JWT_SECRET` whose `sub` is the user id, `iss` is
+/// `AUTH_JWT_ISSUER` and `aud` includes `AUTH_JWT_AUDIENCE`.
+#[derive(Clone)]
+pub struct AuthConfig {
+  secret: Arc<[u8]>,
+  /// Seconds of clock skew allowed on `exp` and `nbf`
+  leeway: i64,
+  /// Required `iss`, unchecked when `None`
+  issuer: Option<String>,
+  /// Value `aud` must be or contain, unchecked when `None`
+  audience: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct TokenHeader {
+  alg: String,
+}
+
+/// `aud` is either a single value or a list of them.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum TokenAudience {
+  One(String),
+  Many(Vec<String>),
+}
+
+impl TokenAudience {
+  fn contains(&self, audience: &str) -> bool {
+    match self {
+      TokenAudience::One(value) => value == audience,
+      TokenAudience::Many(values) => values.iter().any(|value| value == audience),
+    }
+  }
+}
+
+#[derive(Deserialize)]
+struct TokenClaims {
+  sub: String,
+  exp: i64,
+  nbf: Option<i64>,
+  iss: Option<String>,
+  aud: Option<TokenAudience>,
+}
+
+impl AuthConfig {
+  /// The issuer and audience may only be left unset when `APP_ENV` is
+  /// `development`, which then accepts tokens meant for any service.
+  pub fn from_env() -> Result<Self, String> {
+    let secret = env::var("AUTH_JWT_SECRET")
+      .ok()
+      .filter(|secret| !secret.is_empty())
+      .ok_or_else(|| "AUTH_JWT_SECRET not set".to_string())?;
+    let issuer = env::var("AUTH_JWT_ISSUER").ok().filter(|v| !v.is_empty());
+    let audience = env::var("AUTH_JWT_AUDIENCE").ok().filter(|v| !v.is_empty());
+    if !is_development() {
+      if issuer.is_none() {
+        return Err("AUTH_JWT_ISSUER not set".to_string());
+      }
+      if audience.is_none() {
+        return Err("AUTH_JWT_AUDIENCE not set".to_string());
+      }
+    }
+
+    Ok(Self {
+      secret: secret.into_bytes().into(),
+      leeway: env_or("AUTH_JWT_LEEWAY_SECS", 30i64).clamp(0, 300),
+      issuer,
+      audience,
+    })
+  }
+
+  /// The user a token was issued to, `None` for a forged, malformed or
+  /// expired token or one issued by or for another service.
+  pub fn verify(&self, token: &str) -> Option<Uuid> {
+    let mut parts = token.split('.');
+    let (header, claims, signature) = (parts.next()?, parts.next()?, parts.next()?);
+    if parts.next().is_some() {
+      return None;
+    }
+
+    let header: TokenHeader = serde_json::from_slice(&URL_SAFE_NO_PAD.decode(header).ok()?).ok()?;
+    if header.alg != "HS256" {
+      return None;
+    }
+    let mut mac =
+      Hmac::<Sha256>::new_from_slice(&self.secret).expect("HMAC accepts any key length");
+    // The signature covers `header.claims` as sent
+    mac.update(&token.as_bytes()[..token.len() - signature.len() - 1]);
+    mac
+      .verify_slice(&URL_SAFE_NO_PAD.decode(signature).ok()?)
+      .ok()?;
+
+    let claims: TokenClaims = serde_json::from_slice(&URL_SAFE_NO_PAD.decode(claims).ok()?).ok()?;
+    let now = chrono::Utc::now().timestamp();
+    if claims.exp + self.leeway <= now || claims.nbf.is_some_and(|nbf| nbf - self.leeway > now) {
+      return None;
+    }
+    if self
+      .issuer
+      .as_ref()
+      .is_some_and(|issuer| claims.iss.as_ref() != Some(issuer))
+    {
+      return None;
+    }
+    if self.audience.as_ref().is_some_and(|audience| {
+      !claims
+        .aud
+        .as_ref()
+        .is_some_and(|aud| aud.contains(audience))
+    }) {
+      return None;
+    }
+    Uuid::parse_str(&claims.sub).ok()
+  }
+}
+
+/// The calling user, from a verified `Authorization: Bearer` token. Use
+/// `Option<AuthUser>` on endpoints that also serve anonymous callers.
+#[derive(Debug, Clone, Copy)]
+pub struct AuthUser {
+  pub id: Uuid,
+}
+
+impl FromRequest for AuthUser {
+  type Error = AppError;
+  type Future = Ready<Result<Self, Self::Error>>;
+
+  fn from_request(req: &HttpRequest, _payload: &mut Payload) -> Self::Future {
+    ready(Self::from_token(req))
+  }
+}
+
+impl AuthUser {
+  fn from_token(req: &HttpRequest) -> Result<Self, AppError> {
+    let config = req
+      .app_data::<web::Data<AuthConfig>>()
+      .ok_or_else(|| AppError::InternalError("Authentication is not configured".to_string()))?;
+
+    req
+      .headers()
+      .get(header::AUTHORIZATION)
+      .and_then(|value| value.to_str().ok())
+      .and_then(|value| value.strip_prefix("Bearer "))
+      .and_then(|token| config.verify(token.trim()))
+      .map(|id| AuthUser { id })
+      .ok_or_else(|| AppError::Unauthorized("Missing or invalid access token".to_string()))
+  }
+}
+
+/// Users allowed on editor endpoints, from the comma separated
+/// `ADMIN_USER_IDS`.
+#[derive(Clone, Debug, Default)]
+pub struct AdminConfig {
+  pub user_ids: Arc<HashSet<Uuid>>,
+}
+
+impl AdminConfig {
+  pub fn from_env() -> Self {
+    let user_ids = env::var("ADMIN_USER_IDS")
+      .unwrap_or_default()
+      .split(',')
+      .map(str::trim)
+      .filter(|id| !id.is_empty())
+      .filter_map(|id| match Uuid::parse_str(id) {
+        Ok(id) => Some(id),
+        Err(_) => {
+          tracing::warn!("Ignoring invalid id in ADMIN_USER_IDS: {}", id);
+          None
+        }
+      })
+      .collect();
+
+    Self {
+      user_ids: Arc::new(user_ids),
+    }
+  }
+}
+
+/// A caller listed in [`AdminConfig`].
+#[derive(Debug, Clone, Copy)]
+pub struct AdminUser {
+  pub id: Uuid,
+}
+
+impl FromRequest for AdminUser {
+  type Error = AppError;
+  type Future = Ready<Result<Self, Self::Error>>;
+
+  fn from_request(req: &HttpRequest, _payload: &mut Payload) -> Self::Future {
+    let admin = AuthUser::from_token(req).and_then(|user| {
+      let allowed = req
+        .app_data::<web::Data<AdminConfig>>()
+        .is_some_and(|config| config.user_ids.contains(&user.id));
+      if allowed {
+        Ok(AdminUser { id: user.id })
+      } else {
+        Err(AppError::Unauthorized("Admin access required".to_string()))
+      }
+    });
+
+    ready(admin)
+  }
+}
@@ -1,5 +1,6 @@
 use crate::structs::response_api::ResponseApi;
 use actix_web::{HttpResponse, ResponseError, http::StatusCode};
+use cf_util::storage::StorageError;
 use sea_orm::DbErr;
 use std::fmt;
 use validator::ValidationErrors;
@@ -9,6 +10,7 @@ pub enum AppError {
   ValidationError(String),
   Validator(ValidationErrors),
   NotFound(String),
+  Unauthorized(String),
   DatabaseError(String),
   InternalError(String),
   S3Error(String),
@@ -20,6 +22,7 @@ impl fmt::Display for AppError {
       AppError::ValidationError(msg) => write!(f, "Validation error: {}", msg),
       AppError::Validator(msg) => write!(f, "{}", msg),
       AppError::NotFound(msg) => write!(f, "Not found: {}", msg),
+      AppError::Unauthorized(msg) => write!(f, "Unauthorized: {}", msg),
       AppError::DatabaseError(msg) => write!(f, "Database error: {}", msg),
       AppError::InternalError(msg) => write!(f, "Internal error: {}", msg),
       AppError::S3Error(msg) => write!(f, "S3 error: {}", msg), // Add this
@@ -33,6 +36,7 @@ impl ResponseError for AppError {
       AppError::ValidationError(_) => StatusCode::BAD_REQUEST,
       AppError::Validator(_) => StatusCode::BAD_REQUEST,
       AppError::NotFound(_) => StatusCode::NOT_FOUND,
+      AppError::Unauthorized(_) => StatusCode::UNAUTHORIZED,
       AppError::DatabaseError(_) => StatusCode::INTERNAL_SERVER_ERROR,
       AppError::InternalError(_) => StatusCode::INTERNAL_SERVER_ERROR,
       AppError::S3Error(_) => StatusCode::INTERNAL_SERVER_ERROR,
@@ -44,6 +48,7 @@ impl ResponseError for AppError {
       AppError::ValidationError(msg) => msg.clone(),
       AppError::Validator(err) => err.to_string(),
       AppError::NotFound(msg) => msg.clone(),
+      AppError::Unauthorized(msg) => msg.clone(),
       AppError::DatabaseError(msg) => msg.clone(),
       AppError::InternalError(msg) => msg.clone(),
       AppError::S3Error(msg) => msg.clone(),
@@ -80,6 +85,16 @@ impl From<aws_sdk_s3::Error> for AppError {
   }
 }
 
+impl From<StorageError> for AppError {
+  fn from(err: StorageError) -> Self {
+    match err {
+      StorageError::NotFound(key) => AppError::NotFound(format!("Object {} not found", key)),
+      StorageError::InvalidKey(_) => AppError::ValidationError(err.to_string()),
+      _ => AppError::S3Error(err.to_string()),
+    }
+  }
+}
+
 impl From<std::io::Error> for AppError {
   fn from(err: std::io::Error) -> Self {
     AppError::InternalError(err.to_string())
@@ -0,0 +1,109 @@
+use std::env;
+use std::time::Duration;
+
+use cf_util::imaging::ImageFormat;
+
+use super::env_or;
+
+/// A derived image generated for every upload.
+#[derive(Clone, Debug)]
+pub struct RenditionSpec {
+  pub name: String,
+  pub max_width: u32,
+  /// `None` keeps the source format, except GIF which becomes PNG
+  pub format: Option<ImageFormat>,
+}
+
+/// Upload limits for the media endpoints.
+#[derive(Clone, Debug)]
+pub struct MediaConfig {
+  /// Largest file accepted through `POST /api/media`
+  pub max_upload_bytes: usize,
+  /// Largest file accepted as a presigned direct upload
+  pub max_direct_upload_bytes: u64,
+  /// How long a presigned upload URL stays valid
+  pub presign_expiry: Duration,
+  /// How often expired, never completed uploads are swept
+  pub upload_sweep_interval: Duration,
+  pub renditions: Vec<RenditionSpec>,
+  /// Widths and heights `/img/{media_id}` may be asked for
+  pub variant_sizes: Vec<u32>,
+  /// Edge lengths of the square avatar renditions
+  pub avatar_sizes: Vec<u32>,
+}
+
+impl MediaConfig {
+  pub fn from_env() -> Self {
+    Self {
+      max_upload_bytes: env_or("MEDIA_MAX_UPLOAD_BYTES", 10 * 1024 * 1024usize).max(1),
+      max_direct_upload_bytes: env_or("MEDIA_MAX_DIRECT_UPLOAD_BYTES", 100 * 1024 * 1024u64).max(1),
+      presign_expiry: Duration::from_secs(env_or("MEDIA_PRESIGN_EXPIRY_SECS", 900u64).max(1)),
+      upload_sweep_interval: Duration::from_secs(
+        env_or("MEDIA_UPLOAD_SWEEP_INTERVAL_SECS", 600u64).max(1),
+      ),
+      renditions: parse_renditions(
+        &env::var("MEDIA_RENDITIONS").unwrap_or(String::from(DEFAULT_RENDITIONS)),
+      ),
+      variant_sizes: parse_sizes(
+        &env::var("MEDIA_VARIANT_SIZES").unwrap_or(String::from(DEFAULT_VARIANT_SIZES)),
+      ),
+      avatar_sizes: parse_sizes(
+        &env::var("MEDIA_AVATAR_SIZES").unwrap_or(String::from(DEFAULT_AVATAR_SIZES)),
+      ),
+    }
+  }
+}
+
+const DEFAULT_VARIANT_SIZES: &str = "64,128,256,320,480,640,800,1024,1280,1600,1920";
+
+const DEFAULT_AVATAR_SIZES: &str = "64,128,256,512";
+
+/// Parse positive sizes separated by commas, skipping anything else.
+fn parse_sizes(value: &str) -> Vec<u32> {
+  let mut sizes: Vec<u32> = value
+    .split(',')
+    .filter_map(|size| size.trim().parse().ok())
+    .filter(|size| *size > 0)
+    .collect();
+  sizes.sort_unstable();
+  sizes.dedup();
+  sizes
+}
+
+const DEFAULT_RENDITIONS: &str = "thumbnail:320,medium:800,large:1600,webp:1600:webp";
+
+/// Parse `name:max_width[:format]` entries separated by commas, where format
+/// is `jpeg`, `png` or `webp`. Invalid entries are skipped with a warning.
+fn parse_renditions(value: &str) -> Vec<RenditionSpec> {
+  value
+    .split(',')
+    .map(str::trim)
+    .filter(|entry| !entry.is_empty())
+    .filter_map(|entry| {
+      let mut parts = entry.split(':');
+      let name = parts.next()?.trim();
+      let max_width = parts.next().and_then(|w| w.trim().parse().ok());
+      let format = match parts.next().map(|f| f.trim().to_lowercase()) {
+        None => Some(None),
+        Some(f) if f == "jpeg" || f == "jpg" => Some(Some(ImageFormat::Jpeg)),
+        Some(f) if f == "png" => Some(Some(ImageFormat::Png)),
+        Some(f) if f == "webp" => Some(Some(ImageFormat::WebP)),
+        Some(_) => None,
+      };
+
+      match (max_width, format) {
+        (Some(max_width), Some(format)) if !name.is_empty() && max_width > 0 => {
+          Some(RenditionSpec {
+            name: name.to_string(),
+            max_width,
+            format,
+          })
+        }
+        _ => {
+          tracing::warn!("Ignoring invalid media rendition '{}'", entry);
+          None
+        }
+      }
+    })
+    .collect()
+}
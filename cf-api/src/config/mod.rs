@@ -1,3 +1,28 @@
+use std::env;
+
 pub mod database;
 pub mod actix_error;
+pub mod analytics;
+pub mod auth;
 pub mod custom_error;
+pub mod media;
+pub mod pagination;
+pub mod preview;
+pub mod sanitize;
+pub mod site;
+pub mod storage;
+pub mod trending;
+
+/// `key` parsed as `T`, or `default` when unset or unparsable.
+pub(crate) fn env_or<T: std::str::FromStr>(key: &str, default: T) -> T {
+  env::var(key)
+    .ok()
+    .and_then(|v| v.parse().ok())
+    .unwrap_or(default)
+}
+
+/// Whether `APP_ENV` is `development`, which relaxes settings that must be
+/// set in production.
+pub(crate) fn is_development() -> bool {
+  env::var("APP_ENV").is_ok_and(|v| v == "development")
+}
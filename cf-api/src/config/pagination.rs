@@ -0,0 +1,20 @@
+use super::env_or;
+
+/// Page size limits applied to every list endpoint.
+#[derive(Clone, Debug)]
+pub struct PaginationConfig {
+  pub default_per_page: u64,
+  pub max_per_page: u64,
+}
+
+impl PaginationConfig {
+  pub fn from_env() -> Self {
+    let default_per_page = env_or("PAGINATION_DEFAULT_PER_PAGE", 10u64);
+    let max_per_page = env_or("PAGINATION_MAX_PER_PAGE", 100u64);
+
+    Self {
+      default_per_page: default_per_page.clamp(1, max_per_page.max(1)),
+      max_per_page: max_per_page.max(1),
+    }
+  }
+}
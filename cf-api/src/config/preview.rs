@@ -0,0 +1,41 @@
+use std::env;
+use std::sync::Arc;
+use std::time::Duration;
+
+use super::{env_or, is_development};
+
+/// Signing and lifetime of draft preview links.
+#[derive(Clone)]
+pub struct PreviewConfig {
+  /// HMAC key preview tokens are signed with
+  pub secret: Arc<[u8]>,
+  /// Lifetime of a link when none is requested
+  pub default_ttl: Duration,
+  /// Longest lifetime a link may be given
+  pub max_ttl: Duration,
+}
+
+impl PreviewConfig {
+  /// `PREVIEW_SIGNING_SECRET` is required unless `APP_ENV` is
+  /// `development`, where a random key is used instead.
+  pub fn from_env() -> Result<Self, String> {
+    let secret: Arc<[u8]> = match env::var("PREVIEW_SIGNING_SECRET") {
+      Ok(secret) if !secret.is_empty() => secret.into_bytes().into(),
+      _ if is_development() => {
+        // Links still work, but only until the next restart
+        tracing::warn!("PREVIEW_SIGNING_SECRET not set, using a random key");
+        rand::random::<[u8; 32]>().to_vec().into()
+      }
+      _ => return Err("PREVIEW_SIGNING_SECRET not set".to_string()),
+    };
+    let max_ttl = env_or("PREVIEW_LINK_MAX_TTL_SECS", 30 * 24 * 3600u64).max(60);
+
+    Ok(Self {
+      secret,
+      default_ttl: Duration::from_secs(
+        env_or("PREVIEW_LINK_TTL_SECS", 7 * 24 * 3600u64).clamp(60, max_ttl),
+      ),
+      max_ttl: Duration::from_secs(max_ttl),
+    })
+  }
+}
@@ -0,0 +1,20 @@
+use std::{env, fs};
+
+use cf_util::sanitize::SanitizePolicies;
+
+/// Policy comments are cleaned with.
+pub const COMMENT_POLICY: &str = "comments";
+
+/// Policy rendered post content is cleaned with.
+pub const ARTICLE_POLICY: &str = "articles";
+
+/// Built-in sanitisation policies, overridden or extended by the JSON file at
+/// `SANITIZE_POLICIES_FILE` when set.
+pub fn sanitize_policies_from_env() -> Result<SanitizePolicies, String> {
+  let Ok(path) = env::var("SANITIZE_POLICIES_FILE") else {
+    return Ok(SanitizePolicies::default());
+  };
+
+  let json = fs::read_to_string(&path).map_err(|err| format!("Cannot read {}: {}", path, err))?;
+  SanitizePolicies::from_json(&json).map_err(|err| format!("Invalid {}: {}", path, err))
+}
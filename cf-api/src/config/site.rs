@@ -0,0 +1,66 @@
+use std::env;
+use std::time::Duration;
+
+use super::env_or;
+
+/// Public facing site details, used wherever absolute links are generated.
+#[derive(Clone, Debug)]
+pub struct SiteConfig {
+  /// Frontend origin post and profile links point at, without trailing slash
+  pub base_url: String,
+  pub title: String,
+  pub description: String,
+  /// Most recent posts included in a feed
+  pub feed_item_limit: u64,
+  /// Words in a feed item excerpt
+  pub feed_excerpt_words: usize,
+  /// Origin `/feeds/...` is served from, used for feed ids and self links
+  pub feed_base_url: String,
+  /// Origin `/sitemap.xml` and its parts are served from
+  pub sitemap_base_url: String,
+  /// How often stored sitemaps are brought up to date
+  pub sitemap_refresh_interval: Duration,
+}
+
+impl SiteConfig {
+  pub fn from_env() -> Self {
+    let base_url = env::var("SITE_BASE_URL")
+      .unwrap_or(String::from("http://localhost:3000"))
+      .trim_end_matches('/')
+      .to_string();
+
+    Self {
+      feed_base_url: env::var("FEED_BASE_URL")
+        .map(|url| url.trim_end_matches('/').to_string())
+        .unwrap_or(base_url.clone()),
+      sitemap_base_url: env::var("SITEMAP_BASE_URL")
+        .map(|url| url.trim_end_matches('/').to_string())
+        .unwrap_or(base_url.clone()),
+      sitemap_refresh_interval: Duration::from_secs(
+        env_or("SITEMAP_REFRESH_INTERVAL_SECS", 3600u64).max(60),
+      ),
+      base_url,
+      title: env::var("SITE_TITLE").unwrap_or(String::from("CF Framework")),
+      description: env::var("SITE_DESCRIPTION").unwrap_or(String::from("Latest posts")),
+      feed_item_limit: env_or("FEED_ITEM_LIMIT", 20u64).clamp(1, 100),
+      feed_excerpt_words: env_or("FEED_EXCERPT_WORDS", 50usize).max(1),
+    }
+  }
+
+  pub fn post_url(&self, id: uuid::Uuid) -> String {
+    format!("{}/posts/{}", self.base_url, id)
+  }
+
+  pub fn preview_url(&self, token: &str) -> String {
+    format!("{}/preview/{}", self.base_url, token)
+  }
+
+  pub fn user_url(&self, id: uuid::Uuid) -> String {
+    format!("{}/users/{}", self.base_url, id)
+  }
+
+  /// Absolute URL of a feed, `path` being relative to `/feeds/`.
+  pub fn feed_url(&self, path: &str) -> String {
+    format!("{}/feeds/{}", self.feed_base_url, path)
+  }
+}
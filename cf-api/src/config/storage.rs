@@ -0,0 +1,71 @@
+use std::env;
+use std::sync::Arc;
+use std::time::Duration;
+
+use cf_util::s3::S3Client;
+use cf_util::storage::{LocalStorage, MemoryStorage, Storage};
+use cf_util::urls::{UrlResolver, Visibility};
+
+use super::env_or;
+
+/// Build the object storage picked by `STORAGE_BACKEND`: `s3`, `local`
+/// (files under `STORAGE_LOCAL_ROOT`) or `memory`. When it is unset the
+/// backend is `s3` if any `S3_*` variable is present, so deployments that
+/// predate the setting keep writing to their bucket, and `local` otherwise.
+pub fn storage_from_env() -> Result<Arc<dyn Storage>, String> {
+  let backend = env::var("STORAGE_BACKEND").unwrap_or_else(|_| {
+    if env::vars().any(|(key, _)| key.starts_with("S3_")) {
+      String::from("s3")
+    } else {
+      String::from("local")
+    }
+  });
+
+  match backend.to_lowercase().as_str() {
+    "s3" => {
+      let var = |key: &str| env::var(key).map_err(|_| format!("{} not set", key));
+      let region = env::var("S3_REGION").unwrap_or(String::from("us-east-1"));
+
+      Ok(Arc::new(S3Client::new(
+        &var("S3_HOST")?,
+        &var("S3_ACCESS_KEY_ID")?,
+        &var("S3_SECRET_ACCESS_KEY")?,
+        &region,
+        &var("S3_BUCKET_NAME")?,
+      )))
+    }
+    "local" => {
+      let root = env::var("STORAGE_LOCAL_ROOT").unwrap_or(String::from("./storage"));
+      Ok(Arc::new(LocalStorage::new(root)))
+    }
+    "memory" => Ok(Arc::new(MemoryStorage::new())),
+    other => Err(format!("Unknown STORAGE_BACKEND '{}'", other)),
+  }
+}
+
+/// Build the resolver for media URLs. `STORAGE_VISIBILITY` is the bucket
+/// default (`public` unless set), `STORAGE_VISIBILITY_RULES` overrides it per
+/// prefix as `prefix=public|private` pairs separated by commas.
+pub fn url_resolver_from_env(storage: Arc<dyn Storage>) -> Result<UrlResolver, String> {
+  let default_visibility = env::var("STORAGE_VISIBILITY")
+    .unwrap_or(String::from("public"))
+    .parse()?;
+  let signed_expiry = env_or("STORAGE_SIGNED_URL_EXPIRY_SECS", 3600u64).max(1);
+
+  let mut resolver = UrlResolver::new(
+    storage,
+    &env::var("MEDIA_PUBLIC_BASE_URL").unwrap_or_default(),
+    default_visibility,
+    Duration::from_secs(signed_expiry),
+  );
+
+  let rules = env::var("STORAGE_VISIBILITY_RULES").unwrap_or_default();
+  for rule in rules.split(',').filter(|r| !r.trim().is_empty()) {
+    let (prefix, visibility) = rule
+      .split_once('=')
+      .ok_or_else(|| format!("Invalid visibility rule '{}'", rule))?;
+    resolver = resolver.with_rule(prefix.trim(), visibility.parse::<Visibility>()?);
+  }
+
+  Ok(resolver)
+}
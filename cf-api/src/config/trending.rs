@@ -0,0 +1,39 @@
+use std::time::Duration;
+
+use cf_entity::entity::sea_orm_active_enums::TrendingPeriod;
+use cf_repository::repositories::TrendingWeights;
+
+use super::env_or;
+
+/// Span of activity a ranking of `period` is computed from.
+pub fn window(period: TrendingPeriod) -> chrono::Duration {
+  match period {
+    TrendingPeriod::Day => chrono::Duration::hours(24),
+    TrendingPeriod::Week => chrono::Duration::days(7),
+  }
+}
+
+/// Weights and refresh schedule of the trending rankings.
+#[derive(Clone, Debug)]
+pub struct TrendingConfig {
+  pub refresh_interval: Duration,
+  pub weights: TrendingWeights,
+  /// Posts kept per ranking
+  pub max_posts: u64,
+}
+
+impl TrendingConfig {
+  pub fn from_env() -> Self {
+    Self {
+      refresh_interval: Duration::from_secs(
+        env_or("TRENDING_REFRESH_INTERVAL_SECS", 600u64).max(60),
+      ),
+      weights: TrendingWeights {
+        view: env_or("TRENDING_VIEW_WEIGHT", 1.0f64).max(0.0),
+        reaction: env_or("TRENDING_REACTION_WEIGHT", 3.0f64).max(0.0),
+        comment: env_or("TRENDING_COMMENT_WEIGHT", 5.0f64).max(0.0),
+      },
+      max_posts: env_or("TRENDING_MAX_POSTS", 1000u64).clamp(1, 10_000),
+    }
+  }
+}
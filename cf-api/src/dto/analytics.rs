@@ -0,0 +1,15 @@
+use chrono::NaiveDate;
+use serde::Deserialize;
+use utoipa::{IntoParams, ToSchema};
+
+#[derive(Deserialize, Debug, IntoParams, ToSchema)]
+pub struct PostStatsParam {
+  /// First day, inclusive. Defaults to 29 days before `to`
+  #[param(value_type = Option<String>, format = Date)]
+  #[schema(value_type = Option<String>, format = Date)]
+  pub from: Option<NaiveDate>,
+  /// Last day, inclusive. Defaults to today (UTC)
+  #[param(value_type = Option<String>, format = Date)]
+  #[schema(value_type = Option<String>, format = Date)]
+  pub to: Option<NaiveDate>,
+}
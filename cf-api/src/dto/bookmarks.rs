@@ -0,0 +1,41 @@
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+use validator::Validate;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default, Validate, ToSchema)]
+pub struct AddBookmarkRequest {
+  /// Collection to file the bookmark in, omitted to leave it unfiled
+  pub collection_id: Option<Uuid>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Validate, ToSchema)]
+pub struct BookmarkCollectionRequest {
+  #[validate(length(min = 1, max = 100, message = "Name must be 1 to 100 characters"))]
+  pub name: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Validate, ToSchema)]
+pub struct BookmarkCheckRequest {
+  #[validate(length(
+    min = 1,
+    max = 100,
+    message = "Between 1 and 100 post ids are required"
+  ))]
+  pub post_ids: Vec<Uuid>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum BookmarkSort {
+  #[default]
+  Newest,
+  Oldest,
+}
+
+#[derive(Deserialize, Debug, IntoParams, ToSchema)]
+pub struct BookmarkListParam {
+  /// Only list bookmarks filed in this collection
+  pub collection_id: Option<Uuid>,
+  pub sort: Option<BookmarkSort>,
+}
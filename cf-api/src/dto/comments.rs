@@ -0,0 +1,44 @@
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+use validator::Validate;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Validate, ToSchema)]
+pub struct CreateCommentRequest {
+  /// Comment being replied to, omitted for a top level comment
+  pub parent_id: Option<Uuid>,
+  #[validate(length(
+    min = 1,
+    max = 10000,
+    message = "Content must be 1 to 10000 characters"
+  ))]
+  pub content: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Validate, ToSchema)]
+pub struct UpdateCommentRequest {
+  #[validate(length(
+    min = 1,
+    max = 10000,
+    message = "Content must be 1 to 10000 characters"
+  ))]
+  pub content: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum CommentFormat {
+  /// Replies nested under their parent
+  #[default]
+  Tree,
+  /// Thread order as a flat list, indent by `depth`
+  Flat,
+}
+
+#[derive(Deserialize, Debug, IntoParams, ToSchema)]
+pub struct CommentListParam {
+  pub format: Option<CommentFormat>,
+  /// Deepest reply level returned, 0 for top level comments only
+  #[param(minimum = 0)]
+  pub max_depth: Option<i32>,
+}
@@ -0,0 +1,16 @@
+use serde::Deserialize;
+use utoipa::{IntoParams, ToSchema};
+
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum FeedFormat {
+  Rss,
+  Atom,
+}
+
+#[derive(Deserialize, Debug, IntoParams)]
+pub struct FeedParam {
+  /// Include the full rendered post instead of an excerpt
+  #[serde(default)]
+  pub full: bool,
+}
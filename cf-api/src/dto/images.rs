@@ -0,0 +1,34 @@
+use serde::Deserialize;
+use utoipa::{IntoParams, ToSchema};
+
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ImageFit {
+  /// Fill `w`×`h` exactly, cropping the overflow
+  #[default]
+  Cover,
+  /// Fit inside `w`×`h`, keeping the aspect ratio
+  Contain,
+  /// Stretch to `w`×`h`
+  Fill,
+}
+
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ImageOutputFormat {
+  Jpeg,
+  Png,
+  Webp,
+}
+
+#[derive(Deserialize, Debug, IntoParams, ToSchema)]
+pub struct ImageParam {
+  /// Width, must be one of the configured variant sizes
+  pub w: Option<u32>,
+  /// Height, must be one of the configured variant sizes
+  pub h: Option<u32>,
+  /// Only used when both `w` and `h` are given
+  pub fit: Option<ImageFit>,
+  /// Defaults to the original format, PNG for GIF originals
+  pub format: Option<ImageOutputFormat>,
+}
@@ -0,0 +1,58 @@
+use actix_multipart::form::MultipartForm;
+use actix_multipart::form::bytes::Bytes;
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+use validator::Validate;
+
+use super::posts::deserialize_some;
+
+#[derive(MultipartForm, ToSchema)]
+pub struct UploadMediaForm {
+  /// Image file, JPEG, PNG, GIF or WebP. The type is detected from the content
+  #[schema(value_type = String, format = Binary)]
+  pub file: Bytes,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Validate, ToSchema)]
+pub struct CreateUploadRequest {
+  /// Type the file will be uploaded with, JPEG, PNG, GIF or WebP
+  pub content_type: String,
+  /// Exact size of the file; the upload URL is only valid for this size
+  #[validate(range(min = 1, message = "Size must be positive"))]
+  pub size_bytes: u64,
+}
+
+#[derive(Deserialize, Debug, IntoParams, ToSchema)]
+pub struct MediaListParam {
+  /// Only media uploaded by this user
+  pub user_id: Option<Uuid>,
+  /// Exact type, e.g. `image/png`
+  pub mime_type: Option<String>,
+  /// Uploaded on or after this day (UTC)
+  #[param(value_type = Option<String>, format = Date)]
+  #[schema(value_type = Option<String>, format = Date)]
+  pub from: Option<NaiveDate>,
+  /// Uploaded on or before this day (UTC)
+  #[param(value_type = Option<String>, format = Date)]
+  #[schema(value_type = Option<String>, format = Date)]
+  pub to: Option<NaiveDate>,
+}
+
+/// Omit a field to keep it, `null` to clear it.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, Validate, ToSchema)]
+pub struct UpdateMediaRequest {
+  #[serde(default, deserialize_with = "deserialize_some")]
+  #[schema(value_type = Option<String>, nullable)]
+  #[validate(length(max = 1000, message = "Alt text cannot exceed 1000 characters"))]
+  pub alt_text: Option<Option<String>>,
+  #[serde(default, deserialize_with = "deserialize_some")]
+  #[schema(value_type = Option<String>, nullable)]
+  #[validate(length(max = 2000, message = "Caption cannot exceed 2000 characters"))]
+  pub caption: Option<Option<String>>,
+  #[serde(default, deserialize_with = "deserialize_some")]
+  #[schema(value_type = Option<String>, nullable)]
+  #[validate(length(max = 255, message = "Credit cannot exceed 255 characters"))]
+  pub credit: Option<Option<String>>,
+}
@@ -0,0 +1,28 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+use validator::Validate;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default, Validate, ToSchema)]
+pub struct PlacePostRequest {
+  /// Order among posts of the same kind, lowest first. Defaults to the
+  /// current position, or after the last one for a new placement
+  #[validate(range(min = 0, message = "Position must not be negative"))]
+  pub position: Option<i32>,
+  /// When the placement lapses, never when omitted
+  #[schema(value_type = Option<String>, format = DateTime)]
+  pub expires_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Validate, ToSchema)]
+pub struct ReorderPlacementsRequest {
+  /// Placed posts in their new order. Placements left out keep their
+  /// relative order after these
+  #[validate(length(
+    min = 1,
+    max = 100,
+    message = "Between 1 and 100 post ids are required"
+  ))]
+  pub post_ids: Vec<Uuid>,
+}
@@ -1,16 +1,40 @@
-use cf_entity::entity::sea_orm_active_enums::PostStatus;
-use serde::{Deserialize, Serialize};
-use utoipa::ToSchema;
+use cf_entity::entity::sea_orm_active_enums::{PostStatus, PostVisibility};
+use serde::{Deserialize, Deserializer, Serialize};
+use utoipa::{IntoParams, ToSchema};
 use uuid::Uuid;
-use validator::Validate;
+use validator::{Validate, ValidationError};
 
 #[derive(Serialize, Deserialize, Debug, Clone, Validate, ToSchema)]
 pub struct CreatePostRequest {
-  pub user_id: Uuid,
   #[validate(length(min = 1, message = "Title is required"))]
   pub title: String,
   #[validate(length(min = 1, message = "Content is required"))]
   pub content: String,
+  /// Uploaded media of the author shown above the post
+  pub header_image_id: Option<Uuid>,
+  /// Who can read the post once published, `PUBLIC` when omitted
+  pub visibility: Option<PostVisibility>,
+  /// Tag names; tags are created on first use and matched case-insensitively
+  #[serde(default)]
+  #[validate(length(max = 10, message = "A post can have at most 10 tags"))]
+  pub tags: Vec<String>,
+  /// Title for search results and link previews, `title` when unset
+  #[validate(length(max = 120, message = "Meta title cannot exceed 120 characters"))]
+  pub meta_title: Option<String>,
+  /// Description for search results and link previews, an excerpt when unset
+  #[validate(length(max = 320, message = "Meta description cannot exceed 320 characters"))]
+  pub meta_description: Option<String>,
+  /// Original location when the post was first published elsewhere
+  #[validate(
+    url(message = "Canonical URL must be a valid URL"),
+    custom(
+      function = "http_url",
+      message = "Canonical URL must use http or https"
+    )
+  )]
+  pub canonical_url: Option<String>,
+  /// Uploaded media of the author for link previews, the header image when unset
+  pub social_image_id: Option<Uuid>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, Validate, ToSchema)]
@@ -18,4 +42,63 @@ pub struct UpdatePostRequest {
   pub title: Option<String>,
   pub content: Option<String>,
   pub status: Option<PostStatus>,
+  pub visibility: Option<PostVisibility>,
+  /// Omit to keep the current header image, `null` to remove it
+  #[serde(default, deserialize_with = "deserialize_some")]
+  #[schema(value_type = Option<Uuid>, nullable)]
+  pub header_image_id: Option<Option<Uuid>>,
+  /// Omit to keep the current tags, `[]` to remove them all
+  #[validate(length(max = 10, message = "A post can have at most 10 tags"))]
+  pub tags: Option<Vec<String>>,
+  /// The SEO fields below: omit to keep, `null` to fall back to the default
+  #[serde(default, deserialize_with = "deserialize_some")]
+  #[schema(value_type = Option<String>, nullable)]
+  #[validate(length(max = 120, message = "Meta title cannot exceed 120 characters"))]
+  pub meta_title: Option<Option<String>>,
+  #[serde(default, deserialize_with = "deserialize_some")]
+  #[schema(value_type = Option<String>, nullable)]
+  #[validate(length(max = 320, message = "Meta description cannot exceed 320 characters"))]
+  pub meta_description: Option<Option<String>>,
+  #[serde(default, deserialize_with = "deserialize_some")]
+  #[schema(value_type = Option<String>, nullable)]
+  #[validate(
+    url(message = "Canonical URL must be a valid URL"),
+    custom(
+      function = "http_url",
+      message = "Canonical URL must use http or https"
+    )
+  )]
+  pub canonical_url: Option<Option<String>>,
+  #[serde(default, deserialize_with = "deserialize_some")]
+  #[schema(value_type = Option<Uuid>, nullable)]
+  pub social_image_id: Option<Option<Uuid>>,
+}
+
+#[derive(Deserialize, Debug, IntoParams, ToSchema, Validate)]
+pub struct RelatedPostsParam {
+  /// Defaults to 5
+  #[param(minimum = 1, maximum = 20)]
+  #[validate(range(min = 1, max = 20, message = "limit must be between 1 and 20"))]
+  pub limit: Option<u64>,
+}
+
+/// Tell an explicit `null` apart from a missing field.
+pub(crate) fn deserialize_some<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
+where
+  D: Deserializer<'de>,
+  T: Deserialize<'de>,
+{
+  T::deserialize(deserializer).map(Some)
+}
+
+/// Only `http` and `https` links, so a stored URL can't smuggle `javascript:`
+/// or `data:` into pages that render it.
+fn http_url(url: &str) -> Result<(), ValidationError> {
+  let scheme = url
+    .split_once(':')
+    .map(|(scheme, _)| scheme.to_ascii_lowercase());
+  match scheme.as_deref() {
+    Some("http" | "https") => Ok(()),
+    _ => Err(ValidationError::new("url_scheme")),
+  }
 }
@@ -0,0 +1,9 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default, ToSchema)]
+pub struct CreatePreviewRequest {
+  /// Seconds until the link expires. Defaults to `PREVIEW_LINK_TTL_SECS`,
+  /// at most `PREVIEW_LINK_MAX_TTL_SECS`
+  pub expires_in_secs: Option<u64>,
+}
@@ -0,0 +1,10 @@
+use cf_entity::entity::sea_orm_active_enums::ReactionKind;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// Sending the caller's current reaction again removes it, sending another
+/// kind replaces it.
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+pub struct ToggleReactionRequest {
+  pub kind: ReactionKind,
+}
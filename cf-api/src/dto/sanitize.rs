@@ -0,0 +1,9 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use validator::Validate;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Validate, ToSchema)]
+pub struct SanitizeRequest {
+  #[validate(length(max = 1_000_000, message = "HTML cannot exceed 1000000 characters"))]
+  pub html: String,
+}
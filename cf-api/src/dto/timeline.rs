@@ -0,0 +1,9 @@
+use serde::Deserialize;
+use utoipa::{IntoParams, ToSchema};
+
+#[derive(Deserialize, Debug, IntoParams, ToSchema)]
+pub struct TimelineParam {
+  /// Mix the caller's own published posts into the timeline
+  #[serde(default)]
+  pub include_own: bool,
+}
@@ -0,0 +1,10 @@
+use cf_entity::entity::sea_orm_active_enums::TrendingPeriod;
+use serde::Deserialize;
+use utoipa::{IntoParams, ToSchema};
+
+#[derive(Deserialize, Debug, IntoParams, ToSchema)]
+pub struct TrendingParam {
+  /// `24h` or `7d`, defaults to `24h`
+  #[param(value_type = Option<TrendingPeriod>)]
+  pub window: Option<TrendingPeriod>,
+}
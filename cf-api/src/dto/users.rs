@@ -1,7 +1,12 @@
+use actix_multipart::form::MultipartForm;
+use actix_multipart::form::bytes::Bytes;
+use actix_multipart::form::text::Text;
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 use validator::Validate;
 
+use crate::config::custom_error::AppError;
+
 #[derive(Serialize, Deserialize, Debug, Clone, Validate, ToSchema)]
 pub struct CreateUserRequest {
   #[validate(length(min = 1, message = "Name is required"))]
@@ -16,3 +21,52 @@ pub struct UpdateUserRequest {
   #[validate(email(message = "Invalid email format"))]
   pub email: Option<String>,
 }
+
+/// Part of the upright image to use for the avatar, in pixels. Non-square
+/// rectangles are center cropped to a square.
+#[derive(Debug, Clone, Copy)]
+pub struct CropRect {
+  pub x: u32,
+  pub y: u32,
+  pub width: u32,
+  pub height: u32,
+}
+
+#[derive(MultipartForm, ToSchema)]
+pub struct UpdateAvatarForm {
+  /// Image file, JPEG, PNG, GIF or WebP. The type is detected from the content
+  #[schema(value_type = String, format = Binary)]
+  pub file: Bytes,
+  /// Crop rectangle; either all four fields or none, which takes the
+  /// largest centered square
+  #[schema(value_type = Option<u32>)]
+  pub crop_x: Option<Text<u32>>,
+  #[schema(value_type = Option<u32>)]
+  pub crop_y: Option<Text<u32>>,
+  #[schema(value_type = Option<u32>)]
+  pub crop_width: Option<Text<u32>>,
+  #[schema(value_type = Option<u32>)]
+  pub crop_height: Option<Text<u32>>,
+}
+
+impl UpdateAvatarForm {
+  pub fn crop_rect(&self) -> Result<Option<CropRect>, AppError> {
+    match (
+      &self.crop_x,
+      &self.crop_y,
+      &self.crop_width,
+      &self.crop_height,
+    ) {
+      (Some(x), Some(y), Some(width), Some(height)) => Ok(Some(CropRect {
+        x: **x,
+        y: **y,
+        width: **width,
+        height: **height,
+      })),
+      (None, None, None, None) => Ok(None),
+      _ => Err(AppError::ValidationError(
+        "crop_x, crop_y, crop_width and crop_height must be given together".to_string(),
+      )),
+    }
+  }
+}
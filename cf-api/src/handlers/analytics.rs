@@ -0,0 +1,39 @@
+use crate::config::auth::AuthUser;
+use crate::config::custom_error::AppError;
+use crate::dto::analytics::PostStatsParam;
+use crate::services::analytics::AnalyticsService;
+use crate::structs::analytics::PostStats;
+use crate::structs::response_api::ResponseApi;
+use actix_web::{HttpResponse, Responder, get, web};
+
+#[utoipa::path(
+    get,
+    path = "/api/posts/{id}/stats",
+    params(
+        PostStatsParam,
+        ("Authorization" = String, Header, description = "Bearer token of the caller, must be the author"),
+    ),
+    tag = "analytics",
+    responses(
+        (status = 200, description = "Daily views of a post, zero-filled", body = ResponseApi<PostStats>),
+        (status = 400, description = "Invalid or too long date range"),
+        (status = 401, description = "Missing caller"),
+        (status = 404, description = "Post not found or caller is not the author")
+    )
+)]
+#[get("/{id}/stats")]
+pub async fn get_post_stats(
+  service: web::Data<AnalyticsService>,
+  id: web::Path<uuid::Uuid>,
+  params: web::Query<PostStatsParam>,
+  user: AuthUser,
+) -> Result<impl Responder, AppError> {
+  let stats = service
+    .get_post_stats(user.id, *id, params.from, params.to)
+    .await?;
+  Ok(HttpResponse::Ok().json(ResponseApi {
+    status: true,
+    message: "Post stats retrieved successfully".to_string(),
+    data: Some(stats),
+  }))
+}
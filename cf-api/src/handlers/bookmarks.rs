@@ -0,0 +1,254 @@
+use std::collections::HashMap;
+
+use crate::config::auth::AuthUser;
+use crate::config::custom_error::AppError;
+use crate::config::pagination::PaginationConfig;
+use crate::dto::bookmarks::{
+  AddBookmarkRequest, BookmarkCheckRequest, BookmarkCollectionRequest, BookmarkListParam,
+};
+use crate::services::bookmarks::BookmarkService;
+use crate::structs::bookmark::BookmarkResponse;
+use crate::structs::generals::{Empty, Paginated, PaginationParam};
+use crate::structs::response_api::ResponseApi;
+use actix_web::{HttpRequest, HttpResponse, Responder, delete, get, post, put, web};
+use validator::Validate;
+
+#[utoipa::path(
+    get,
+    path = "/api/bookmarks",
+    params(
+        PaginationParam,
+        BookmarkListParam,
+        ("Authorization" = String, Header, description = "Bearer token of the caller"),
+    ),
+    tag = "bookmark",
+    responses(
+        (status = 200, description = "Caller's bookmarked posts", body = ResponseApi<Paginated<BookmarkResponse>>),
+        (status = 401, description = "Missing caller"),
+        (status = 404, description = "Collection not found")
+    )
+)]
+#[get("")]
+pub async fn get_bookmarks(
+  req: HttpRequest,
+  service: web::Data<BookmarkService>,
+  pagination: web::Data<PaginationConfig>,
+  params: web::Query<PaginationParam>,
+  filter: web::Query<BookmarkListParam>,
+  user: AuthUser,
+) -> Result<impl Responder, AppError> {
+  let (page, per_page) = params.page_bounds(&pagination)?;
+  let result = service
+    .get_bookmarks(
+      user.id,
+      filter.collection_id,
+      filter.sort.unwrap_or_default(),
+      page,
+      per_page,
+    )
+    .await?;
+  Ok(HttpResponse::Ok().json(ResponseApi {
+    status: true,
+    message: "Bookmarks retrieved successfully".to_string(),
+    data: Some(Paginated::from_page(result, &req)),
+  }))
+}
+
+#[utoipa::path(
+    put,
+    path = "/api/bookmarks/{post_id}",
+    params(
+        ("Authorization" = String, Header, description = "Bearer token of the caller"),
+    ),
+    request_body = AddBookmarkRequest,
+    tag = "bookmark",
+    responses(
+        (status = 200, description = "Post bookmarked or moved to another collection", body = ResponseApi<cf_entity::entity::bookmarks::Model>),
+        (status = 401, description = "Missing caller"),
+        (status = 404, description = "Post or collection not found")
+    )
+)]
+#[put("/{post_id}")]
+pub async fn add_bookmark(
+  service: web::Data<BookmarkService>,
+  post_id: web::Path<uuid::Uuid>,
+  user: AuthUser,
+  req: Option<web::Json<AddBookmarkRequest>>,
+) -> Result<impl Responder, AppError> {
+  let req = req.map(|r| r.into_inner()).unwrap_or_default();
+  let bookmark = service.add_bookmark(user.id, *post_id, req).await?;
+  Ok(HttpResponse::Ok().json(ResponseApi {
+    status: true,
+    message: "Bookmark saved successfully".to_string(),
+    data: Some(bookmark),
+  }))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/bookmarks/{post_id}",
+    params(
+        ("Authorization" = String, Header, description = "Bearer token of the caller"),
+    ),
+    tag = "bookmark",
+    responses(
+        (status = 200, description = "Bookmark removed", body = ResponseApi<Empty>),
+        (status = 401, description = "Missing caller"),
+        (status = 404, description = "Bookmark not found")
+    )
+)]
+#[delete("/{post_id}")]
+pub async fn remove_bookmark(
+  service: web::Data<BookmarkService>,
+  post_id: web::Path<uuid::Uuid>,
+  user: AuthUser,
+) -> Result<impl Responder, AppError> {
+  service.remove_bookmark(user.id, *post_id).await?;
+  Ok(HttpResponse::Ok().json(ResponseApi {
+    status: true,
+    message: "Bookmark removed successfully".to_string(),
+    data: Some(Empty {}),
+  }))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/bookmarks/check",
+    params(
+        ("Authorization" = String, Header, description = "Bearer token of the caller"),
+    ),
+    request_body = BookmarkCheckRequest,
+    tag = "bookmark",
+    responses(
+        (status = 200, description = "Bookmark state keyed by post id", body = ResponseApi<HashMap<uuid::Uuid, bool>>),
+        (status = 401, description = "Missing caller")
+    )
+)]
+#[post("/check")]
+pub async fn check_bookmarks(
+  service: web::Data<BookmarkService>,
+  user: AuthUser,
+  req: web::Json<BookmarkCheckRequest>,
+) -> Result<impl Responder, AppError> {
+  req.validate()?;
+  let result = service.check_bookmarks(user.id, &req.post_ids).await?;
+  Ok(HttpResponse::Ok().json(ResponseApi {
+    status: true,
+    message: "Bookmarks checked successfully".to_string(),
+    data: Some(result),
+  }))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/bookmarks/collections",
+    params(
+        ("Authorization" = String, Header, description = "Bearer token of the caller"),
+    ),
+    tag = "bookmark",
+    responses(
+        (status = 200, description = "Caller's bookmark collections", body = ResponseApi<Vec<cf_entity::entity::bookmark_collections::Model>>),
+        (status = 401, description = "Missing caller")
+    )
+)]
+#[get("/collections")]
+pub async fn get_collections(
+  service: web::Data<BookmarkService>,
+  user: AuthUser,
+) -> Result<impl Responder, AppError> {
+  let collections = service.get_collections(user.id).await?;
+  Ok(HttpResponse::Ok().json(ResponseApi {
+    status: true,
+    message: "Collections retrieved successfully".to_string(),
+    data: Some(collections),
+  }))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/bookmarks/collections",
+    params(
+        ("Authorization" = String, Header, description = "Bearer token of the caller"),
+    ),
+    request_body = BookmarkCollectionRequest,
+    tag = "bookmark",
+    responses(
+        (status = 201, description = "Collection created", body = ResponseApi<cf_entity::entity::bookmark_collections::Model>),
+        (status = 400, description = "Name missing or already used"),
+        (status = 401, description = "Missing caller")
+    )
+)]
+#[post("/collections")]
+pub async fn create_collection(
+  service: web::Data<BookmarkService>,
+  user: AuthUser,
+  req: web::Json<BookmarkCollectionRequest>,
+) -> Result<impl Responder, AppError> {
+  req.validate()?;
+  let collection = service.create_collection(user.id, req.into_inner()).await?;
+  Ok(HttpResponse::Created().json(ResponseApi {
+    status: true,
+    message: "Collection created successfully".to_string(),
+    data: Some(collection),
+  }))
+}
+
+#[utoipa::path(
+    put,
+    path = "/api/bookmarks/collections/{id}",
+    params(
+        ("Authorization" = String, Header, description = "Bearer token of the caller"),
+    ),
+    request_body = BookmarkCollectionRequest,
+    tag = "bookmark",
+    responses(
+        (status = 200, description = "Collection renamed", body = ResponseApi<cf_entity::entity::bookmark_collections::Model>),
+        (status = 400, description = "Name missing or already used"),
+        (status = 401, description = "Missing caller"),
+        (status = 404, description = "Collection not found")
+    )
+)]
+#[put("/collections/{id}")]
+pub async fn rename_collection(
+  service: web::Data<BookmarkService>,
+  id: web::Path<uuid::Uuid>,
+  user: AuthUser,
+  req: web::Json<BookmarkCollectionRequest>,
+) -> Result<impl Responder, AppError> {
+  req.validate()?;
+  let collection = service
+    .rename_collection(user.id, *id, req.into_inner())
+    .await?;
+  Ok(HttpResponse::Ok().json(ResponseApi {
+    status: true,
+    message: "Collection updated successfully".to_string(),
+    data: Some(collection),
+  }))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/bookmarks/collections/{id}",
+    params(
+        ("Authorization" = String, Header, description = "Bearer token of the caller"),
+    ),
+    tag = "bookmark",
+    responses(
+        (status = 200, description = "Collection deleted, its bookmarks become unfiled", body = ResponseApi<Empty>),
+        (status = 401, description = "Missing caller"),
+        (status = 404, description = "Collection not found")
+    )
+)]
+#[delete("/collections/{id}")]
+pub async fn delete_collection(
+  service: web::Data<BookmarkService>,
+  id: web::Path<uuid::Uuid>,
+  user: AuthUser,
+) -> Result<impl Responder, AppError> {
+  service.delete_collection(user.id, *id).await?;
+  Ok(HttpResponse::Ok().json(ResponseApi {
+    status: true,
+    message: "Collection deleted successfully".to_string(),
+    data: Some(Empty {}),
+  }))
+}
@@ -0,0 +1,150 @@
+use crate::config::auth::AuthUser;
+use crate::config::custom_error::AppError;
+use crate::dto::comments::{
+  CommentFormat, CommentListParam, CreateCommentRequest, UpdateCommentRequest,
+};
+use crate::services::comments::CommentService;
+use crate::structs::comment::CommentNode;
+use crate::structs::generals::Empty;
+use crate::structs::response_api::ResponseApi;
+use actix_web::{HttpResponse, Responder, delete, get, post, put, web};
+use validator::Validate;
+
+#[utoipa::path(
+    get,
+    path = "/api/posts/{post_id}/comments",
+    params(
+        CommentListParam,
+        ("Authorization" = Option<String>, Header, description = "Bearer token of the caller, needed for posts that are not public"),
+    ),
+    tag = "comment",
+    responses(
+        (status = 200, description = "Comment threads of a post. A flat list of comments when `format=flat`", body = ResponseApi<Vec<CommentNode>>),
+        (status = 404, description = "Post not found")
+    )
+)]
+#[get("/{post_id}/comments")]
+pub async fn get_post_comments(
+  service: web::Data<CommentService>,
+  post_id: web::Path<uuid::Uuid>,
+  params: web::Query<CommentListParam>,
+  user: Option<AuthUser>,
+) -> Result<impl Responder, AppError> {
+  let message = "Comments retrieved successfully".to_string();
+  let viewer = user.map(|u| u.id);
+
+  match params.format.unwrap_or_default() {
+    CommentFormat::Tree => {
+      let comments = service
+        .get_comment_tree(*post_id, params.max_depth, viewer)
+        .await?;
+      Ok(HttpResponse::Ok().json(ResponseApi {
+        status: true,
+        message,
+        data: Some(comments),
+      }))
+    }
+    CommentFormat::Flat => {
+      let comments = service
+        .get_comment_list(*post_id, params.max_depth, viewer)
+        .await?;
+      Ok(HttpResponse::Ok().json(ResponseApi {
+        status: true,
+        message,
+        data: Some(comments),
+      }))
+    }
+  }
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/posts/{post_id}/comments",
+    params(
+        ("Authorization" = String, Header, description = "Bearer token of the caller, the comment author"),
+    ),
+    request_body = CreateCommentRequest,
+    tag = "comment",
+    responses(
+        (status = 201, description = "Comment created", body = ResponseApi<cf_entity::entity::comments::Model>),
+        (status = 400, description = "Post not published or reply too deep"),
+        (status = 401, description = "Missing caller"),
+        (status = 404, description = "Post or parent comment not found")
+    )
+)]
+#[post("/{post_id}/comments")]
+pub async fn create_comment(
+  service: web::Data<CommentService>,
+  post_id: web::Path<uuid::Uuid>,
+  user: AuthUser,
+  req: web::Json<CreateCommentRequest>,
+) -> Result<impl Responder, AppError> {
+  req.validate()?;
+  let comment = service
+    .create_comment(user.id, *post_id, req.into_inner())
+    .await?;
+  Ok(HttpResponse::Created().json(ResponseApi {
+    status: true,
+    message: "Comment created successfully".to_string(),
+    data: Some(comment),
+  }))
+}
+
+#[utoipa::path(
+    put,
+    path = "/api/comments/{id}",
+    params(
+        ("Authorization" = String, Header, description = "Bearer token of the caller, must be the comment author"),
+    ),
+    request_body = UpdateCommentRequest,
+    tag = "comment",
+    responses(
+        (status = 200, description = "Comment updated", body = ResponseApi<cf_entity::entity::comments::Model>),
+        (status = 401, description = "Missing caller"),
+        (status = 404, description = "Comment not found or caller is not the author")
+    )
+)]
+#[put("/{id}")]
+pub async fn update_comment(
+  service: web::Data<CommentService>,
+  id: web::Path<uuid::Uuid>,
+  user: AuthUser,
+  req: web::Json<UpdateCommentRequest>,
+) -> Result<impl Responder, AppError> {
+  req.validate()?;
+  let comment = service
+    .update_comment(user.id, *id, req.into_inner())
+    .await?;
+  Ok(HttpResponse::Ok().json(ResponseApi {
+    status: true,
+    message: "Comment updated successfully".to_string(),
+    data: Some(comment),
+  }))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/comments/{id}",
+    params(
+        ("Authorization" = String, Header, description = "Bearer token of the caller, must be the comment author"),
+    ),
+    tag = "comment",
+    responses(
+        (status = 200, description = "Comment and its replies deleted", body = ResponseApi<Empty>),
+        (status = 401, description = "Missing caller"),
+        (status = 404, description = "Comment not found or caller is not the author")
+    )
+)]
+#[delete("/{id}")]
+pub async fn delete_comment(
+  service: web::Data<CommentService>,
+  id: web::Path<uuid::Uuid>,
+  user: AuthUser,
+) -> Result<impl Responder, AppError> {
+  service.delete_comment(user.id, *id).await?;
+  Ok(HttpResponse::Ok().json(ResponseApi {
+    status: true,
+    message: "Comment deleted successfully".to_string(),
+    data: Some(Empty {}),
+  }))
+}
@@ -0,0 +1,147 @@
+use std::time::SystemTime;
+
+use crate::config::custom_error::AppError;
+use crate::dto::feeds::{FeedFormat, FeedParam};
+use crate::services::feeds::{FeedScope, FeedService, RenderedFeed};
+use actix_web::http::header::{
+  CacheControl, CacheDirective, ETag, EntityTag, HttpDate, IfModifiedSince, IfNoneMatch,
+  LastModified,
+};
+use actix_web::{HttpResponse, Responder, get, web};
+
+/// Readers poll often; let them and any proxy reuse a copy for a few minutes.
+fn cache_control() -> CacheControl {
+  CacheControl(vec![CacheDirective::Public, CacheDirective::MaxAge(300)])
+}
+
+/// Answer with the feed, or 304 when the client's copy is current. An
+/// `If-None-Match` takes precedence over `If-Modified-Since`.
+fn feed_response(
+  feed: RenderedFeed,
+  if_none_match: Option<web::Header<IfNoneMatch>>,
+  if_modified_since: Option<web::Header<IfModifiedSince>>,
+) -> HttpResponse {
+  let etag = EntityTag::new_strong(feed.etag);
+  let last_modified = feed
+    .last_modified
+    .map(|updated| HttpDate::from(SystemTime::from(updated)));
+
+  let fresh = match (if_none_match.map(|h| h.into_inner()), if_modified_since) {
+    (Some(IfNoneMatch::Any), _) => true,
+    (Some(IfNoneMatch::Items(tags)), _) => tags.iter().any(|tag| tag.weak_eq(&etag)),
+    (None, Some(since)) => last_modified
+      .is_some_and(|modified| SystemTime::from(modified) <= SystemTime::from(since.into_inner().0)),
+    (None, None) => false,
+  };
+
+  let mut response = if fresh {
+    HttpResponse::NotModified()
+  } else {
+    HttpResponse::Ok()
+  };
+  response
+    .insert_header(ETag(etag))
+    .insert_header(cache_control());
+  if let Some(modified) = last_modified {
+    response.insert_header(LastModified(modified));
+  }
+
+  if fresh {
+    response.finish()
+  } else {
+    response.content_type(feed.content_type).body(feed.body)
+  }
+}
+
+#[utoipa::path(
+    get,
+    path = "/feeds/posts.{format}",
+    params(
+        ("format" = FeedFormat, Path, description = "`rss` or `atom`"),
+        FeedParam,
+        ("If-None-Match" = Option<String>, Header, description = "ETag of a cached copy"),
+        ("If-Modified-Since" = Option<String>, Header, description = "Last-Modified of a cached copy"),
+    ),
+    tag = "feed",
+    responses(
+        (status = 200, description = "Latest published posts", content_type = "application/rss+xml"),
+        (status = 304, description = "Cached copy is still current")
+    )
+)]
+#[get("/posts.{format}")]
+pub async fn get_posts_feed(
+  service: web::Data<FeedService>,
+  format: web::Path<FeedFormat>,
+  query: web::Query<FeedParam>,
+  if_none_match: Option<web::Header<IfNoneMatch>>,
+  if_modified_since: Option<web::Header<IfModifiedSince>>,
+) -> Result<impl Responder, AppError> {
+  let feed = service
+    .render(FeedScope::All, format.into_inner(), query.full)
+    .await?;
+  Ok(feed_response(feed, if_none_match, if_modified_since))
+}
+
+#[utoipa::path(
+    get,
+    path = "/feeds/authors/{user_id}.{format}",
+    params(
+        ("user_id" = uuid::Uuid, Path, description = "Author whose posts are listed"),
+        ("format" = FeedFormat, Path, description = "`rss` or `atom`"),
+        FeedParam,
+        ("If-None-Match" = Option<String>, Header, description = "ETag of a cached copy"),
+        ("If-Modified-Since" = Option<String>, Header, description = "Last-Modified of a cached copy"),
+    ),
+    tag = "feed",
+    responses(
+        (status = 200, description = "Latest published posts of the author", content_type = "application/rss+xml"),
+        (status = 304, description = "Cached copy is still current"),
+        (status = 404, description = "User not found")
+    )
+)]
+#[get("/authors/{user_id}.{format}")]
+pub async fn get_author_feed(
+  service: web::Data<FeedService>,
+  path: web::Path<(uuid::Uuid, FeedFormat)>,
+  query: web::Query<FeedParam>,
+  if_none_match: Option<web::Header<IfNoneMatch>>,
+  if_modified_since: Option<web::Header<IfModifiedSince>>,
+) -> Result<impl Responder, AppError> {
+  let (user_id, format) = path.into_inner();
+  let feed = service
+    .render(FeedScope::Author(user_id), format, query.full)
+    .await?;
+  Ok(feed_response(feed, if_none_match, if_modified_since))
+}
+
+#[utoipa::path(
+    get,
+    path = "/feeds/tags/{slug}.{format}",
+    params(
+        ("slug" = String, Path, description = "Tag slug"),
+        ("format" = FeedFormat, Path, description = "`rss` or `atom`"),
+        FeedParam,
+        ("If-None-Match" = Option<String>, Header, description = "ETag of a cached copy"),
+        ("If-Modified-Since" = Option<String>, Header, description = "Last-Modified of a cached copy"),
+    ),
+    tag = "feed",
+    responses(
+        (status = 200, description = "Latest published posts with the tag", content_type = "application/rss+xml"),
+        (status = 304, description = "Cached copy is still current"),
+        (status = 404, description = "Tag not found")
+    )
+)]
+#[get("/tags/{slug}.{format}")]
+pub async fn get_tag_feed(
+  service: web::Data<FeedService>,
+  path: web::Path<(String, FeedFormat)>,
+  query: web::Query<FeedParam>,
+  if_none_match: Option<web::Header<IfNoneMatch>>,
+  if_modified_since: Option<web::Header<IfModifiedSince>>,
+) -> Result<impl Responder, AppError> {
+  let (slug, format) = path.into_inner();
+  let feed = service
+    .render(FeedScope::Tag(slug), format, query.full)
+    .await?;
+  Ok(feed_response(feed, if_none_match, if_modified_since))
+}
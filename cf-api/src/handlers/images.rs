@@ -0,0 +1,64 @@
+use crate::config::custom_error::AppError;
+use crate::dto::images::ImageParam;
+use crate::services::images::ImageService;
+use actix_web::http::header::{CacheControl, CacheDirective, ETag, EntityTag, IfNoneMatch};
+use actix_web::{HttpResponse, Responder, get, web};
+
+/// Variants never change for a given URL, so clients and CDNs may keep them.
+fn cache_control() -> CacheControl {
+  CacheControl(vec![
+    CacheDirective::Public,
+    CacheDirective::MaxAge(31_536_000),
+    CacheDirective::Extension("immutable".to_string(), None),
+  ])
+}
+
+#[utoipa::path(
+    get,
+    path = "/img/{media_id}",
+    params(
+        ("media_id" = uuid::Uuid, Path, description = "Media to render"),
+        ImageParam,
+        ("If-None-Match" = Option<String>, Header, description = "ETag of a cached copy"),
+    ),
+    tag = "image",
+    responses(
+        (status = 200, description = "Image bytes, resized and converted as requested", content_type = "image/*"),
+        (status = 304, description = "Cached copy is still current"),
+        (status = 400, description = "Size not in the allow-list or unknown fit or format"),
+        (status = 404, description = "Media not found or not processed yet")
+    )
+)]
+#[get("/{media_id}")]
+pub async fn get_image(
+  service: web::Data<ImageService>,
+  media_id: web::Path<uuid::Uuid>,
+  query: web::Query<ImageParam>,
+  if_none_match: Option<web::Header<IfNoneMatch>>,
+) -> Result<impl Responder, AppError> {
+  let variant = service.resolve(*media_id, query.into_inner()).await?;
+  let etag = EntityTag::new_strong(variant.etag.clone());
+
+  let fresh = match if_none_match.map(|header| header.into_inner()) {
+    Some(IfNoneMatch::Any) => true,
+    Some(IfNoneMatch::Items(tags)) => tags.iter().any(|tag| tag.weak_eq(&etag)),
+    None => false,
+  };
+  if fresh {
+    return Ok(
+      HttpResponse::NotModified()
+        .insert_header(ETag(etag))
+        .insert_header(cache_control())
+        .finish(),
+    );
+  }
+
+  let data = service.render(&variant).await?;
+  Ok(
+    HttpResponse::Ok()
+      .content_type(variant.content_type)
+      .insert_header(ETag(etag))
+      .insert_header(cache_control())
+      .body(data),
+  )
+}
@@ -0,0 +1,215 @@
+use crate::config::auth::AuthUser;
+use crate::config::custom_error::AppError;
+use crate::config::pagination::PaginationConfig;
+use crate::dto::media::{CreateUploadRequest, MediaListParam, UpdateMediaRequest, UploadMediaForm};
+use crate::services::media::MediaService;
+use crate::structs::generals::{Empty, Paginated, PaginationParam};
+use crate::structs::media::{MediaResponse, PresignedUpload};
+use crate::structs::response_api::ResponseApi;
+use actix_multipart::form::MultipartForm;
+use actix_web::{HttpRequest, HttpResponse, Responder, delete, get, post, put, web};
+use validator::Validate;
+
+#[utoipa::path(
+    get,
+    path = "/api/media",
+    params(
+        PaginationParam,
+        MediaListParam,
+        ("Authorization" = String, Header, description = "Bearer token of the caller"),
+    ),
+    tag = "media",
+    responses(
+        (status = 200, description = "Uploaded media, newest first", body = ResponseApi<Paginated<MediaResponse>>),
+        (status = 400, description = "Invalid filters or pagination bounds"),
+        (status = 401, description = "Missing caller")
+    )
+)]
+#[get("")]
+pub async fn get_all_media(
+  req: HttpRequest,
+  service: web::Data<MediaService>,
+  pagination: web::Data<PaginationConfig>,
+  params: web::Query<PaginationParam>,
+  filter: web::Query<MediaListParam>,
+  _user: AuthUser,
+) -> Result<impl Responder, AppError> {
+  let (page, per_page) = params.page_bounds(&pagination)?;
+  let result = service
+    .list_media(filter.into_inner(), page, per_page)
+    .await?;
+  Ok(HttpResponse::Ok().json(ResponseApi {
+    status: true,
+    message: "Media retrieved successfully".to_string(),
+    data: Some(Paginated::from_page(result, &req)),
+  }))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/media/{id}",
+    tag = "media",
+    responses(
+        (status = 200, description = "Media with its rendition URLs", body = ResponseApi<MediaResponse>),
+        (status = 404, description = "Media not found")
+    )
+)]
+#[get("/{id}")]
+pub async fn get_media(
+  service: web::Data<MediaService>,
+  id: web::Path<uuid::Uuid>,
+) -> Result<impl Responder, AppError> {
+  let media = service.get_media(*id).await?;
+  Ok(HttpResponse::Ok().json(ResponseApi {
+    status: true,
+    message: "Media retrieved successfully".to_string(),
+    data: Some(media),
+  }))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/media",
+    params(
+        ("Authorization" = String, Header, description = "Bearer token of the uploader"),
+    ),
+    request_body(content = UploadMediaForm, content_type = "multipart/form-data"),
+    tag = "media",
+    responses(
+        (status = 201, description = "Image uploaded, renditions follow in the background", body = ResponseApi<MediaResponse>),
+        (status = 400, description = "Empty file, content is not a JPEG, PNG, GIF or WebP image, or WebP is animated"),
+        (status = 401, description = "Missing caller"),
+        (status = 413, description = "Upload larger than the configured limit"),
+        (status = 500, description = "Storage failure")
+    )
+)]
+#[post("")]
+pub async fn upload_media(
+  service: web::Data<MediaService>,
+  user: AuthUser,
+  MultipartForm(form): MultipartForm<UploadMediaForm>,
+) -> Result<impl Responder, AppError> {
+  let media = service.upload_image(user.id, form.file.data).await?;
+  Ok(HttpResponse::Created().json(ResponseApi {
+    status: true,
+    message: "Media uploaded successfully".to_string(),
+    data: Some(media),
+  }))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/media/uploads",
+    params(
+        ("Authorization" = String, Header, description = "Bearer token of the uploader"),
+    ),
+    request_body = CreateUploadRequest,
+    tag = "media",
+    responses(
+        (status = 201, description = "Presigned URL to upload the file to directly", body = ResponseApi<PresignedUpload>),
+        (status = 400, description = "Unsupported type or size over the limit"),
+        (status = 401, description = "Missing caller"),
+        (status = 500, description = "Storage backend cannot presign URLs")
+    )
+)]
+#[post("/uploads")]
+pub async fn create_upload(
+  service: web::Data<MediaService>,
+  user: AuthUser,
+  req: web::Json<CreateUploadRequest>,
+) -> Result<impl Responder, AppError> {
+  req.validate()?;
+  let upload = service.create_upload(user.id, req.into_inner()).await?;
+  Ok(HttpResponse::Created().json(ResponseApi {
+    status: true,
+    message: "Upload URL created successfully".to_string(),
+    data: Some(upload),
+  }))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/media/uploads/{id}/complete",
+    params(
+        ("Authorization" = String, Header, description = "Bearer token of the uploader"),
+    ),
+    tag = "media",
+    responses(
+        (status = 201, description = "Upload verified and recorded", body = ResponseApi<MediaResponse>),
+        (status = 400, description = "Upload expired, or file missing or not matching the signed size and type"),
+        (status = 401, description = "Missing caller"),
+        (status = 404, description = "Upload not found or already completed")
+    )
+)]
+#[post("/uploads/{id}/complete")]
+pub async fn complete_upload(
+  service: web::Data<MediaService>,
+  user: AuthUser,
+  id: web::Path<uuid::Uuid>,
+) -> Result<impl Responder, AppError> {
+  let media = service.complete_upload(user.id, *id).await?;
+  Ok(HttpResponse::Created().json(ResponseApi {
+    status: true,
+    message: "Upload completed successfully".to_string(),
+    data: Some(media),
+  }))
+}
+
+#[utoipa::path(
+    put,
+    path = "/api/media/{id}",
+    params(
+        ("Authorization" = String, Header, description = "Bearer token of the uploader"),
+    ),
+    request_body = UpdateMediaRequest,
+    tag = "media",
+    responses(
+        (status = 200, description = "Metadata updated", body = ResponseApi<MediaResponse>),
+        (status = 400, description = "Field too long"),
+        (status = 401, description = "Missing caller"),
+        (status = 404, description = "Media not found or owned by another user")
+    )
+)]
+#[put("/{id}")]
+pub async fn update_media(
+  service: web::Data<MediaService>,
+  user: AuthUser,
+  id: web::Path<uuid::Uuid>,
+  req: web::Json<UpdateMediaRequest>,
+) -> Result<impl Responder, AppError> {
+  req.validate()?;
+  let media = service.update_media(user.id, *id, req.into_inner()).await?;
+  Ok(HttpResponse::Ok().json(ResponseApi {
+    status: true,
+    message: "Media updated successfully".to_string(),
+    data: Some(media),
+  }))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/media/{id}",
+    params(
+        ("Authorization" = String, Header, description = "Bearer token of the uploader"),
+    ),
+    tag = "media",
+    responses(
+        (status = 200, description = "Media and all its objects deleted", body = ResponseApi<Empty>),
+        (status = 400, description = "Media is still used by posts"),
+        (status = 401, description = "Missing caller"),
+        (status = 404, description = "Media not found or owned by another user")
+    )
+)]
+#[delete("/{id}")]
+pub async fn delete_media(
+  service: web::Data<MediaService>,
+  user: AuthUser,
+  id: web::Path<uuid::Uuid>,
+) -> Result<impl Responder, AppError> {
+  service.delete_media(user.id, *id).await?;
+  Ok(HttpResponse::Ok().json(ResponseApi {
+    status: true,
+    message: "Media deleted successfully".to_string(),
+    data: Some(Empty {}),
+  }))
+}
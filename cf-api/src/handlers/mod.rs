@@ -1,2 +1,15 @@
+pub mod analytics;
+pub mod bookmarks;
+pub mod comments;
+pub mod feeds;
+pub mod images;
+pub mod media;
+pub mod placements;
 pub mod posts;
+pub mod previews;
+pub mod public;
+pub mod reactions;
+pub mod sanitize;
+pub mod sitemaps;
+pub mod timeline;
 pub mod users;
@@ -0,0 +1,133 @@
+use crate::config::auth::AdminUser;
+use crate::config::custom_error::AppError;
+use crate::dto::placements::{PlacePostRequest, ReorderPlacementsRequest};
+use crate::services::placements::PlacementService;
+use crate::structs::generals::Empty;
+use crate::structs::response_api::ResponseApi;
+use actix_web::{HttpResponse, Responder, delete, get, put, web};
+use cf_entity::entity::post_placements::Model as PlacementModel;
+use cf_entity::entity::sea_orm_active_enums::PlacementKind;
+use validator::Validate;
+
+#[utoipa::path(
+    get,
+    path = "/api/admin/placements/{kind}",
+    params(
+        ("kind" = PlacementKind, Path, description = "`pinned` or `featured`"),
+        ("Authorization" = String, Header, description = "Bearer token of the caller, must be listed in `ADMIN_USER_IDS`"),
+    ),
+    tag = "admin",
+    responses(
+        (status = 200, description = "Placements that have not expired, in position order", body = ResponseApi<Vec<PlacementModel>>),
+        (status = 400, description = "Unknown kind"),
+        (status = 401, description = "Missing caller or caller is not an admin")
+    )
+)]
+#[get("/{kind}")]
+pub async fn get_placements(
+  service: web::Data<PlacementService>,
+  kind: web::Path<PlacementKind>,
+  _admin: AdminUser,
+) -> Result<impl Responder, AppError> {
+  let placements = service.list(*kind).await?;
+  Ok(HttpResponse::Ok().json(ResponseApi {
+    status: true,
+    message: "Placements retrieved successfully".to_string(),
+    data: Some(placements),
+  }))
+}
+
+#[utoipa::path(
+    put,
+    path = "/api/admin/placements/{kind}/order",
+    params(
+        ("kind" = PlacementKind, Path, description = "`pinned` or `featured`"),
+        ("Authorization" = String, Header, description = "Bearer token of the caller, must be listed in `ADMIN_USER_IDS`"),
+    ),
+    request_body = ReorderPlacementsRequest,
+    tag = "admin",
+    responses(
+        (status = 200, description = "Placements in their new order", body = ResponseApi<Vec<PlacementModel>>),
+        (status = 400, description = "Unknown kind, or a post listed twice or not placed"),
+        (status = 401, description = "Missing caller or caller is not an admin")
+    )
+)]
+#[put("/{kind}/order")]
+pub async fn reorder_placements(
+  service: web::Data<PlacementService>,
+  kind: web::Path<PlacementKind>,
+  _admin: AdminUser,
+  req: web::Json<ReorderPlacementsRequest>,
+) -> Result<impl Responder, AppError> {
+  req.validate()?;
+  let placements = service.reorder(*kind, req.into_inner()).await?;
+  Ok(HttpResponse::Ok().json(ResponseApi {
+    status: true,
+    message: "Placements reordered successfully".to_string(),
+    data: Some(placements),
+  }))
+}
+
+#[utoipa::path(
+    put,
+    path = "/api/admin/placements/{kind}/{post_id}",
+    params(
+        ("kind" = PlacementKind, Path, description = "`pinned` or `featured`"),
+        ("Authorization" = String, Header, description = "Bearer token of the caller, must be listed in `ADMIN_USER_IDS`"),
+    ),
+    request_body = Option<PlacePostRequest>,
+    tag = "admin",
+    responses(
+        (status = 200, description = "Post pinned or featured, or its placement updated", body = ResponseApi<PlacementModel>),
+        (status = 400, description = "Unknown kind, unpublished post, past expiry or too many placements"),
+        (status = 401, description = "Missing caller or caller is not an admin"),
+        (status = 404, description = "Post not found")
+    )
+)]
+#[put("/{kind}/{post_id}")]
+pub async fn place_post(
+  service: web::Data<PlacementService>,
+  path: web::Path<(PlacementKind, uuid::Uuid)>,
+  admin: AdminUser,
+  req: Option<web::Json<PlacePostRequest>>,
+) -> Result<impl Responder, AppError> {
+  let req = req.map(|r| r.into_inner()).unwrap_or_default();
+  req.validate()?;
+  let (kind, post_id) = path.into_inner();
+  let placement = service.place(admin.id, kind, post_id, req).await?;
+  Ok(HttpResponse::Ok().json(ResponseApi {
+    status: true,
+    message: "Post placed successfully".to_string(),
+    data: Some(placement),
+  }))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/admin/placements/{kind}/{post_id}",
+    params(
+        ("kind" = PlacementKind, Path, description = "`pinned` or `featured`"),
+        ("Authorization" = String, Header, description = "Bearer token of the caller, must be listed in `ADMIN_USER_IDS`"),
+    ),
+    tag = "admin",
+    responses(
+        (status = 200, description = "Post unpinned or no longer featured", body = ResponseApi<Empty>),
+        (status = 400, description = "Unknown kind"),
+        (status = 401, description = "Missing caller or caller is not an admin"),
+        (status = 404, description = "Post is not placed")
+    )
+)]
+#[delete("/{kind}/{post_id}")]
+pub async fn remove_placement(
+  service: web::Data<PlacementService>,
+  path: web::Path<(PlacementKind, uuid::Uuid)>,
+  _admin: AdminUser,
+) -> Result<impl Responder, AppError> {
+  let (kind, post_id) = path.into_inner();
+  service.remove(kind, post_id).await?;
+  Ok(HttpResponse::Ok().json(ResponseApi {
+    status: true,
+    message: "Placement removed successfully".to_string(),
+    data: Some(Empty {}),
+  }))
+}
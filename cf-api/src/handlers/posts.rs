@@ -1,31 +1,116 @@
+use crate::config::auth::AuthUser;
 use crate::config::custom_error::AppError;
-use crate::dto::posts::{CreatePostRequest, UpdatePostRequest};
+use crate::config::pagination::PaginationConfig;
+use crate::dto::posts::{CreatePostRequest, RelatedPostsParam, UpdatePostRequest};
+use crate::dto::trending::TrendingParam;
+use crate::services::post_meta::PostMetaService;
 use crate::services::posts::PostService;
-use crate::structs::generals::{Empty, PaginationParam};
+use crate::structs::generals::{CursorPaginated, Empty, Paginated, PaginationParam};
+use crate::structs::post::{PostMeta, PostResponse};
 use crate::structs::response_api::ResponseApi;
-use actix_web::{HttpResponse, Responder, delete, get, post, put, web};
+use actix_web::{HttpRequest, HttpResponse, Responder, delete, get, post, put, web};
+use cf_entity::entity::sea_orm_active_enums::TrendingPeriod;
+use validator::Validate;
 
 #[utoipa::path(
     get,
     path = "/api/posts",
-    params(PaginationParam),
+    params(
+        PaginationParam,
+        ("Authorization" = String, Header, description = "Bearer token of the caller"),
+    ),
     tag = "post",
     responses(
-        (status = 200, description = "List posts", body = ResponseApi<Vec<cf_entity::entity::posts::Model>>),
+        (status = 200, description = "List the caller's posts in any state, pinned posts first and the rest newest first. In cursor mode pins that do not fit the first page carry on over the next ones. Returns `CursorPaginated` instead when `cursor` or `limit` is given", body = ResponseApi<Paginated<PostResponse>>),
+        (status = 400, description = "Invalid cursor or pagination bounds"),
+        (status = 401, description = "Missing caller")
     )
 )]
 #[get("")]
 pub async fn get_all_posts(
+  req: HttpRequest,
   service: web::Data<PostService>,
+  pagination: web::Data<PaginationConfig>,
   params: web::Query<PaginationParam>,
+  user: AuthUser,
 ) -> Result<impl Responder, AppError> {
-  let page = params.page.unwrap_or(1);
-  let per_page = params.per_page.unwrap_or(10);
+  if params.is_cursor_mode() {
+    let limit = params.limit_bounds(&pagination)?;
+    let result = service
+      .get_posts_by_cursor(user.id, params.cursor.as_deref(), limit)
+      .await?;
+    return Ok(HttpResponse::Ok().json(ResponseApi {
+      status: true,
+      message: "Posts retrieved successfully".to_string(),
+      data: Some(CursorPaginated::from(result)),
+    }));
+  }
 
-  let (posts, _total_pages) = service.get_all_posts(page, per_page).await?;
+  let (page, per_page) = params.page_bounds(&pagination)?;
+  let result = service.get_all_posts(user.id, page, per_page).await?;
   Ok(HttpResponse::Ok().json(ResponseApi {
     status: true,
     message: "Posts retrieved successfully".to_string(),
+    data: Some(Paginated::from_page(result, &req)),
+  }))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/posts/trending",
+    params(
+        TrendingParam,
+        ("page" = Option<u64>, Query, minimum = 1),
+        ("per_page" = Option<u64>, Query, minimum = 1, description = "Capped by `PAGINATION_MAX_PER_PAGE`"),
+        ("Authorization" = Option<String>, Header, description = "Bearer token of the caller, fills `reactions.my_reaction`"),
+    ),
+    tag = "post",
+    responses(
+        (status = 200, description = "Public posts by time-decayed views, reactions and comments in the window, as of the last refresh", body = ResponseApi<Paginated<PostResponse>>),
+        (status = 400, description = "Unknown window or invalid pagination bounds")
+    )
+)]
+#[get("/trending")]
+pub async fn get_trending_posts(
+  req: HttpRequest,
+  service: web::Data<PostService>,
+  pagination: web::Data<PaginationConfig>,
+  params: web::Query<PaginationParam>,
+  trending: web::Query<TrendingParam>,
+  user: Option<AuthUser>,
+) -> Result<impl Responder, AppError> {
+  let (page, per_page) = params.page_bounds(&pagination)?;
+  let period = trending.window.unwrap_or(TrendingPeriod::Day);
+  let result = service
+    .get_trending_posts(period, page, per_page, user.map(|u| u.id))
+    .await?;
+  Ok(HttpResponse::Ok().json(ResponseApi {
+    status: true,
+    message: "Trending posts retrieved successfully".to_string(),
+    data: Some(Paginated::from_page(result, &req)),
+  }))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/posts/featured",
+    params(
+        ("Authorization" = Option<String>, Header, description = "Bearer token of the caller, fills `reactions.my_reaction`"),
+    ),
+    tag = "post",
+    responses(
+        (status = 200, description = "Featured carousel in editor order, without expired entries and posts the caller may not list", body = ResponseApi<Vec<PostResponse>>)
+    )
+)]
+#[get("/featured")]
+pub async fn get_featured_posts(
+  service: web::Data<PostService>,
+  user: Option<AuthUser>,
+) -> Result<impl Responder, AppError> {
+  let posts = service.get_featured_posts(user.map(|u| u.id)).await?;
+  Ok(HttpResponse::Ok().json(ResponseApi {
+    status: true,
+    message: "Featured posts retrieved successfully".to_string(),
     data: Some(posts),
   }))
 }
@@ -33,18 +118,23 @@ pub async fn get_all_posts(
 #[utoipa::path(
     get,
     path = "/api/posts/{id}",
+    params(
+        ("Authorization" = String, Header, description = "Bearer token of the caller"),
+    ),
     tag = "post",
     responses(
-        (status = 200, description = "Get post", body = ResponseApi<cf_entity::entity::posts::Model>),
-        (status = 404, description = "Post not found")
+        (status = 200, description = "Get one of the caller's posts in any state. Not counted as a view", body = ResponseApi<PostResponse>),
+        (status = 401, description = "Missing caller"),
+        (status = 404, description = "Post not found or owned by another user")
     )
 )]
 #[get("/{id}")]
 pub async fn get_post(
   service: web::Data<PostService>,
   id: web::Path<uuid::Uuid>,
+  user: AuthUser,
 ) -> Result<impl Responder, AppError> {
-  let post = service.get_post(*id).await?;
+  let post = service.get_post(user.id, *id).await?;
   Ok(HttpResponse::Ok().json(ResponseApi {
     status: true,
     message: "Post retrieved successfully".to_string(),
@@ -52,21 +142,86 @@ pub async fn get_post(
   }))
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/posts/{id}/related",
+    params(
+        RelatedPostsParam,
+        ("Authorization" = Option<String>, Header, description = "Bearer token of the caller, needed for posts that are not public"),
+    ),
+    tag = "post",
+    responses(
+        (status = 200, description = "Public posts sharing tags, author or title words with the post, best first", body = ResponseApi<Vec<PostResponse>>),
+        (status = 400, description = "Invalid limit"),
+        (status = 404, description = "Post not found")
+    )
+)]
+#[get("/{id}/related")]
+pub async fn get_related_posts(
+  service: web::Data<PostService>,
+  id: web::Path<uuid::Uuid>,
+  params: web::Query<RelatedPostsParam>,
+  user: Option<AuthUser>,
+) -> Result<impl Responder, AppError> {
+  params.validate()?;
+  let posts = service
+    .get_related_posts(*id, params.limit.unwrap_or(5), user.map(|u| u.id))
+    .await?;
+  Ok(HttpResponse::Ok().json(ResponseApi {
+    status: true,
+    message: "Related posts retrieved successfully".to_string(),
+    data: Some(posts),
+  }))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/posts/{id}/meta",
+    params(
+        ("Authorization" = String, Header, description = "Bearer token of the caller"),
+    ),
+    tag = "post",
+    responses(
+        (status = 200, description = "Open Graph, Twitter card and JSON-LD metadata of one of the caller's posts, for previewing drafts", body = ResponseApi<PostMeta>),
+        (status = 401, description = "Missing caller"),
+        (status = 404, description = "Post not found or owned by another user")
+    )
+)]
+#[get("/{id}/meta")]
+pub async fn get_post_meta(
+  service: web::Data<PostMetaService>,
+  id: web::Path<uuid::Uuid>,
+  user: AuthUser,
+) -> Result<impl Responder, AppError> {
+  let meta = service.get_post_meta(user.id, *id).await?;
+  Ok(HttpResponse::Ok().json(ResponseApi {
+    status: true,
+    message: "Post metadata retrieved successfully".to_string(),
+    data: Some(meta),
+  }))
+}
+
 #[utoipa::path(
     post,
     path = "/api/posts",
+    params(
+        ("Authorization" = String, Header, description = "Bearer token of the caller"),
+    ),
     request_body = CreatePostRequest,
     tag = "post",
     responses(
-        (status = 201, description = "Post created", body = ResponseApi<cf_entity::entity::posts::Model>),
+        (status = 201, description = "Post created with the caller as author", body = ResponseApi<cf_entity::entity::posts::Model>),
+        (status = 401, description = "Missing caller"),
     )
 )]
 #[post("")]
 pub async fn create_post(
   service: web::Data<PostService>,
   req: web::Json<CreatePostRequest>,
+  user: AuthUser,
 ) -> Result<impl Responder, AppError> {
-  let post = service.create_post(req.into_inner()).await?;
+  req.validate()?;
+  let post = service.create_post(user.id, req.into_inner()).await?;
   Ok(HttpResponse::Created().json(ResponseApi {
     status: true,
     message: "Post created successfully".to_string(),
@@ -77,10 +232,15 @@ pub async fn create_post(
 #[utoipa::path(
     put,
     path = "/api/posts/{id}",
+    params(
+        ("Authorization" = String, Header, description = "Bearer token of the caller"),
+    ),
     tag = "post",
     request_body = UpdatePostRequest,
     responses(
         (status = 200, description = "Post updated", body = ResponseApi<cf_entity::entity::posts::Model>),
+        (status = 401, description = "Missing caller"),
+        (status = 404, description = "Post not found or owned by another user"),
     )
 )]
 #[put("/{id}")]
@@ -88,8 +248,10 @@ pub async fn update_post(
   service: web::Data<PostService>,
   id: web::Path<uuid::Uuid>,
   req: web::Json<UpdatePostRequest>,
+  user: AuthUser,
 ) -> Result<impl Responder, AppError> {
-  let post = service.update_post(*id, req.into_inner()).await?;
+  req.validate()?;
+  let post = service.update_post(user.id, *id, req.into_inner()).await?;
   Ok(HttpResponse::Ok().json(ResponseApi {
     status: true,
     message: "Post updated successfully".to_string(),
@@ -100,17 +262,23 @@ pub async fn update_post(
 #[utoipa::path(
     delete,
     path = "/api/posts/{id}",
+    params(
+        ("Authorization" = String, Header, description = "Bearer token of the caller"),
+    ),
     tag = "post",
     responses(
         (status = 200, description = "Post deleted", body = ResponseApi<Empty>),
+        (status = 401, description = "Missing caller"),
+        (status = 404, description = "Post not found or owned by another user"),
     )
 )]
 #[delete("/{id}")]
 pub async fn delete_post(
   service: web::Data<PostService>,
   id: web::Path<uuid::Uuid>,
+  user: AuthUser,
 ) -> Result<impl Responder, AppError> {
-  service.delete_post(*id).await?;
+  service.delete_post(user.id, *id).await?;
   Ok(HttpResponse::Ok().json(ResponseApi {
     status: true,
     message: "Post deleted successfully".to_string(),
@@ -0,0 +1,116 @@
+use crate::config::auth::AuthUser;
+use crate::config::custom_error::AppError;
+use crate::dto::previews::CreatePreviewRequest;
+use crate::services::previews::PreviewService;
+use crate::structs::generals::Empty;
+use crate::structs::preview::{DraftPreview, PreviewLinkResponse};
+use crate::structs::response_api::ResponseApi;
+use actix_web::{HttpResponse, Responder, delete, get, post, web};
+
+#[utoipa::path(
+    post,
+    path = "/api/posts/{id}/previews",
+    params(
+        ("Authorization" = String, Header, description = "Bearer token of the caller, must be the author"),
+    ),
+    request_body = Option<CreatePreviewRequest>,
+    tag = "preview",
+    responses(
+        (status = 201, description = "Preview link to the current revision of the post", body = ResponseApi<PreviewLinkResponse>),
+        (status = 400, description = "Expiry out of range"),
+        (status = 401, description = "Missing caller"),
+        (status = 404, description = "Post not found or caller is not the author")
+    )
+)]
+#[post("/{id}/previews")]
+pub async fn create_preview_link(
+  service: web::Data<PreviewService>,
+  id: web::Path<uuid::Uuid>,
+  user: AuthUser,
+  req: Option<web::Json<CreatePreviewRequest>>,
+) -> Result<impl Responder, AppError> {
+  let req = req.map(|r| r.into_inner()).unwrap_or_default();
+  let link = service.create_link(user.id, *id, req).await?;
+  Ok(HttpResponse::Created().json(ResponseApi {
+    status: true,
+    message: "Preview link created successfully".to_string(),
+    data: Some(link),
+  }))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/posts/{id}/previews",
+    params(
+        ("Authorization" = String, Header, description = "Bearer token of the caller, must be the author"),
+    ),
+    tag = "preview",
+    responses(
+        (status = 200, description = "Preview links that are neither revoked nor expired, newest first", body = ResponseApi<Vec<PreviewLinkResponse>>),
+        (status = 401, description = "Missing caller"),
+        (status = 404, description = "Post not found or caller is not the author")
+    )
+)]
+#[get("/{id}/previews")]
+pub async fn get_preview_links(
+  service: web::Data<PreviewService>,
+  id: web::Path<uuid::Uuid>,
+  user: AuthUser,
+) -> Result<impl Responder, AppError> {
+  let links = service.list_links(user.id, *id).await?;
+  Ok(HttpResponse::Ok().json(ResponseApi {
+    status: true,
+    message: "Preview links retrieved successfully".to_string(),
+    data: Some(links),
+  }))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/posts/{id}/previews/{preview_id}",
+    params(
+        ("Authorization" = String, Header, description = "Bearer token of the caller, must be the author"),
+    ),
+    tag = "preview",
+    responses(
+        (status = 200, description = "Preview link revoked", body = ResponseApi<Empty>),
+        (status = 401, description = "Missing caller"),
+        (status = 404, description = "Post or active preview link not found, or caller is not the author")
+    )
+)]
+#[delete("/{id}/previews/{preview_id}")]
+pub async fn revoke_preview_link(
+  service: web::Data<PreviewService>,
+  path: web::Path<(uuid::Uuid, uuid::Uuid)>,
+  user: AuthUser,
+) -> Result<impl Responder, AppError> {
+  let (id, preview_id) = path.into_inner();
+  service.revoke_link(user.id, id, preview_id).await?;
+  Ok(HttpResponse::Ok().json(ResponseApi {
+    status: true,
+    message: "Preview link revoked successfully".to_string(),
+    data: Some(Empty {}),
+  }))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/preview/{token}",
+    tag = "preview",
+    responses(
+        (status = 200, description = "The post as it was when the link was created, rendered to HTML", body = ResponseApi<DraftPreview>),
+        (status = 404, description = "Invalid, revoked or expired link")
+    )
+)]
+#[get("/{token}")]
+pub async fn get_preview(
+  service: web::Data<PreviewService>,
+  token: web::Path<String>,
+) -> Result<impl Responder, AppError> {
+  let preview = service.get_preview(&token).await?;
+  Ok(HttpResponse::Ok().json(ResponseApi {
+    status: true,
+    message: "Preview retrieved successfully".to_string(),
+    data: Some(preview),
+  }))
+}
@@ -0,0 +1,114 @@
+use crate::config::auth::AuthUser;
+use crate::config::custom_error::AppError;
+use crate::config::pagination::PaginationConfig;
+use crate::services::analytics::ViewRecorder;
+use crate::services::post_meta::PostMetaService;
+use crate::services::posts::PostService;
+use crate::structs::generals::{CursorPaginated, Paginated, PaginationParam};
+use crate::structs::post::{PostMeta, PublicPostResponse};
+use crate::structs::response_api::ResponseApi;
+use actix_web::http::header;
+use actix_web::{HttpRequest, HttpResponse, Responder, get, web};
+
+#[utoipa::path(
+    get,
+    path = "/api/public/posts",
+    params(PaginationParam),
+    tag = "public",
+    responses(
+        (status = 200, description = "List published posts, pinned posts first and the rest newest first. In cursor mode pins that do not fit the first page carry on over the next ones. Returns `CursorPaginated` instead when `cursor` or `limit` is given", body = ResponseApi<Paginated<PublicPostResponse>>),
+        (status = 400, description = "Invalid cursor or pagination bounds")
+    )
+)]
+#[get("")]
+pub async fn get_public_posts(
+  req: HttpRequest,
+  service: web::Data<PostService>,
+  pagination: web::Data<PaginationConfig>,
+  params: web::Query<PaginationParam>,
+) -> Result<impl Responder, AppError> {
+  if params.is_cursor_mode() {
+    let limit = params.limit_bounds(&pagination)?;
+    let result = service
+      .get_public_posts_by_cursor(params.cursor.as_deref(), limit)
+      .await?;
+    return Ok(HttpResponse::Ok().json(ResponseApi {
+      status: true,
+      message: "Posts retrieved successfully".to_string(),
+      data: Some(CursorPaginated::from(result)),
+    }));
+  }
+
+  let (page, per_page) = params.page_bounds(&pagination)?;
+  let result = service.get_public_posts(page, per_page).await?;
+  Ok(HttpResponse::Ok().json(ResponseApi {
+    status: true,
+    message: "Posts retrieved successfully".to_string(),
+    data: Some(Paginated::from_page(result, &req)),
+  }))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/public/posts/{id}",
+    params(
+        ("Authorization" = Option<String>, Header, description = "Bearer token of the caller, needed for posts that are not public"),
+    ),
+    tag = "public",
+    responses(
+        (status = 200, description = "Get a published post the caller may read. Counts as a view, deduplicated per visitor", body = ResponseApi<PublicPostResponse>),
+        (status = 404, description = "Post not found or not readable by the caller")
+    )
+)]
+#[get("/{id}")]
+pub async fn get_public_post(
+  req: HttpRequest,
+  service: web::Data<PostService>,
+  views: web::Data<ViewRecorder>,
+  id: web::Path<uuid::Uuid>,
+  user: Option<AuthUser>,
+) -> Result<impl Responder, AppError> {
+  let viewer = user.map(|u| u.id);
+  let post = service.get_public_post(*id, viewer).await?;
+
+  let user_agent = req
+    .headers()
+    .get(header::USER_AGENT)
+    .and_then(|value| value.to_str().ok());
+  let fingerprint = ViewRecorder::fingerprint(views.client_ip(&req).as_deref(), user_agent, viewer);
+  views.record(post.id, fingerprint);
+
+  Ok(HttpResponse::Ok().json(ResponseApi {
+    status: true,
+    message: "Post retrieved successfully".to_string(),
+    data: Some(post),
+  }))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/public/posts/{id}/meta",
+    params(
+        ("Authorization" = Option<String>, Header, description = "Bearer token of the caller, needed for posts that are not public"),
+    ),
+    tag = "public",
+    responses(
+        (status = 200, description = "Open Graph, Twitter card and JSON-LD metadata of a published post the caller may read", body = ResponseApi<PostMeta>),
+        (status = 404, description = "Post not found or not readable by the caller")
+    )
+)]
+#[get("/{id}/meta")]
+pub async fn get_public_post_meta(
+  service: web::Data<PostMetaService>,
+  id: web::Path<uuid::Uuid>,
+  user: Option<AuthUser>,
+) -> Result<impl Responder, AppError> {
+  let meta = service
+    .get_public_post_meta(*id, user.map(|u| u.id))
+    .await?;
+  Ok(HttpResponse::Ok().json(ResponseApi {
+    status: true,
+    message: "Post metadata retrieved successfully".to_string(),
+    data: Some(meta),
+  }))
+}
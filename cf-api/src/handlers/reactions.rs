@@ -0,0 +1,125 @@
+use crate::config::auth::AuthUser;
+use crate::config::custom_error::AppError;
+use crate::dto::reactions::ToggleReactionRequest;
+use crate::services::reactions::ReactionService;
+use crate::structs::reaction::ReactionSummary;
+use crate::structs::response_api::ResponseApi;
+use actix_web::{HttpResponse, Responder, get, post, web};
+use cf_repository::repositories::ReactionTarget;
+
+#[utoipa::path(
+    get,
+    path = "/api/posts/{post_id}/reactions",
+    params(
+        ("Authorization" = Option<String>, Header, description = "Bearer token of the caller, fills `my_reaction`"),
+    ),
+    tag = "reaction",
+    responses(
+        (status = 200, description = "Reaction totals of a post", body = ResponseApi<ReactionSummary>),
+    )
+)]
+#[get("/{post_id}/reactions")]
+pub async fn get_post_reactions(
+  service: web::Data<ReactionService>,
+  post_id: web::Path<uuid::Uuid>,
+  user: Option<AuthUser>,
+) -> Result<impl Responder, AppError> {
+  let summary = service
+    .get_summary(ReactionTarget::post(*post_id), user.map(|u| u.id))
+    .await?;
+  Ok(HttpResponse::Ok().json(ResponseApi {
+    status: true,
+    message: "Reactions retrieved successfully".to_string(),
+    data: Some(summary),
+  }))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/posts/{post_id}/reactions",
+    params(
+        ("Authorization" = String, Header, description = "Bearer token of the caller"),
+    ),
+    request_body = ToggleReactionRequest,
+    tag = "reaction",
+    responses(
+        (status = 200, description = "Reaction toggled, returns the new totals", body = ResponseApi<ReactionSummary>),
+        (status = 400, description = "Post not published"),
+        (status = 401, description = "Missing caller"),
+        (status = 404, description = "Post not found")
+    )
+)]
+#[post("/{post_id}/reactions")]
+pub async fn toggle_post_reaction(
+  service: web::Data<ReactionService>,
+  post_id: web::Path<uuid::Uuid>,
+  user: AuthUser,
+  req: web::Json<ToggleReactionRequest>,
+) -> Result<impl Responder, AppError> {
+  let summary = service
+    .toggle_reaction(user.id, ReactionTarget::post(*post_id), req.kind)
+    .await?;
+  Ok(HttpResponse::Ok().json(ResponseApi {
+    status: true,
+    message: "Reaction updated successfully".to_string(),
+    data: Some(summary),
+  }))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/comments/{id}/reactions",
+    params(
+        ("Authorization" = Option<String>, Header, description = "Bearer token of the caller, fills `my_reaction`"),
+    ),
+    tag = "reaction",
+    responses(
+        (status = 200, description = "Reaction totals of a comment", body = ResponseApi<ReactionSummary>),
+    )
+)]
+#[get("/{id}/reactions")]
+pub async fn get_comment_reactions(
+  service: web::Data<ReactionService>,
+  id: web::Path<uuid::Uuid>,
+  user: Option<AuthUser>,
+) -> Result<impl Responder, AppError> {
+  let summary = service
+    .get_summary(ReactionTarget::comment(*id), user.map(|u| u.id))
+    .await?;
+  Ok(HttpResponse::Ok().json(ResponseApi {
+    status: true,
+    message: "Reactions retrieved successfully".to_string(),
+    data: Some(summary),
+  }))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/comments/{id}/reactions",
+    params(
+        ("Authorization" = String, Header, description = "Bearer token of the caller"),
+    ),
+    request_body = ToggleReactionRequest,
+    tag = "reaction",
+    responses(
+        (status = 200, description = "Reaction toggled, returns the new totals", body = ResponseApi<ReactionSummary>),
+        (status = 401, description = "Missing caller"),
+        (status = 404, description = "Comment not found")
+    )
+)]
+#[post("/{id}/reactions")]
+pub async fn toggle_comment_reaction(
+  service: web::Data<ReactionService>,
+  id: web::Path<uuid::Uuid>,
+  user: AuthUser,
+  req: web::Json<ToggleReactionRequest>,
+) -> Result<impl Responder, AppError> {
+  let summary = service
+    .toggle_reaction(user.id, ReactionTarget::comment(*id), req.kind)
+    .await?;
+  Ok(HttpResponse::Ok().json(ResponseApi {
+    status: true,
+    message: "Reaction updated successfully".to_string(),
+    data: Some(summary),
+  }))
+}
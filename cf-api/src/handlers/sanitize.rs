@@ -0,0 +1,36 @@
+use crate::config::custom_error::AppError;
+use crate::dto::sanitize::SanitizeRequest;
+use crate::services::sanitize::SanitizeService;
+use crate::structs::response_api::ResponseApi;
+use crate::structs::sanitize::SanitizePreview;
+use actix_web::{HttpResponse, Responder, post, web};
+use validator::Validate;
+
+#[utoipa::path(
+    post,
+    path = "/api/sanitize/{policy}",
+    params(
+        ("policy" = String, Path, description = "Policy name, e.g. comments, articles or embeds"),
+    ),
+    request_body = SanitizeRequest,
+    tag = "sanitize",
+    responses(
+        (status = 200, description = "Cleaned HTML and what was removed", body = ResponseApi<SanitizePreview>),
+        (status = 400, description = "HTML too large"),
+        (status = 404, description = "Unknown policy")
+    )
+)]
+#[post("/{policy}")]
+pub async fn preview_sanitize(
+  service: web::Data<SanitizeService>,
+  policy: web::Path<String>,
+  req: web::Json<SanitizeRequest>,
+) -> Result<impl Responder, AppError> {
+  req.validate()?;
+  let preview = service.preview(&policy, &req.html)?;
+  Ok(HttpResponse::Ok().json(ResponseApi {
+    status: true,
+    message: "HTML sanitized successfully".to_string(),
+    data: Some(preview),
+  }))
+}
@@ -0,0 +1,56 @@
+use crate::config::custom_error::AppError;
+use crate::services::sitemaps::SitemapService;
+use actix_web::http::header::{CacheControl, CacheDirective};
+use actix_web::{HttpResponse, Responder, get, web};
+
+/// Sitemaps are regenerated in the background, so an hour old copy is fine.
+fn cache_control() -> CacheControl {
+  CacheControl(vec![CacheDirective::Public, CacheDirective::MaxAge(3600)])
+}
+
+#[utoipa::path(
+    get,
+    path = "/sitemap.xml",
+    tag = "sitemap",
+    responses(
+        (status = 200, description = "Sitemap index listing the post and profile sitemaps", content_type = "application/xml")
+    )
+)]
+#[get("/sitemap.xml")]
+pub async fn get_sitemap_index(
+  service: web::Data<SitemapService>,
+) -> Result<impl Responder, AppError> {
+  let data = service.index().await?;
+  Ok(
+    HttpResponse::Ok()
+      .content_type("application/xml")
+      .insert_header(cache_control())
+      .body(data),
+  )
+}
+
+#[utoipa::path(
+    get,
+    path = "/sitemaps/{name}",
+    params(
+        ("name" = String, Path, description = "Part listed in the index, e.g. `posts-1.xml`"),
+    ),
+    tag = "sitemap",
+    responses(
+        (status = 200, description = "Up to 50,000 post or profile URLs", content_type = "application/xml"),
+        (status = 404, description = "No such sitemap")
+    )
+)]
+#[get("/sitemaps/{name}")]
+pub async fn get_sitemap(
+  service: web::Data<SitemapService>,
+  name: web::Path<String>,
+) -> Result<impl Responder, AppError> {
+  let data = service.part(&name).await?;
+  Ok(
+    HttpResponse::Ok()
+      .content_type("application/xml")
+      .insert_header(cache_control())
+      .body(data),
+  )
+}
@@ -0,0 +1,48 @@
+use crate::config::auth::AuthUser;
+use crate::config::custom_error::AppError;
+use crate::config::pagination::PaginationConfig;
+use crate::dto::timeline::TimelineParam;
+use crate::services::posts::PostService;
+use crate::structs::generals::{CursorPaginated, PaginationParam};
+use crate::structs::post::PostResponse;
+use crate::structs::response_api::ResponseApi;
+use actix_web::{HttpResponse, Responder, get, web};
+
+#[utoipa::path(
+    get,
+    path = "/api/feed",
+    params(
+        PaginationParam,
+        TimelineParam,
+        ("Authorization" = String, Header, description = "Bearer token of the caller"),
+    ),
+    tag = "timeline",
+    responses(
+        (status = 200, description = "Published posts of followed authors, newest first by publication. Always keyset paginated; `page` and `per_page` are ignored", body = ResponseApi<CursorPaginated<PostResponse>>),
+        (status = 400, description = "Invalid cursor or limit"),
+        (status = 401, description = "Missing caller")
+    )
+)]
+#[get("")]
+pub async fn get_timeline(
+  service: web::Data<PostService>,
+  pagination: web::Data<PaginationConfig>,
+  params: web::Query<PaginationParam>,
+  timeline: web::Query<TimelineParam>,
+  user: AuthUser,
+) -> Result<impl Responder, AppError> {
+  let limit = params.limit_bounds(&pagination)?;
+  let result = service
+    .get_timeline(
+      user.id,
+      timeline.include_own,
+      params.cursor.as_deref(),
+      limit,
+    )
+    .await?;
+  Ok(HttpResponse::Ok().json(ResponseApi {
+    status: true,
+    message: "Timeline retrieved successfully".to_string(),
+    data: Some(CursorPaginated::from(result)),
+  }))
+}
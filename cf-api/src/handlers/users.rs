@@ -1,9 +1,14 @@
+use crate::config::auth::AuthUser;
 use crate::config::custom_error::AppError;
-use crate::dto::users::{CreateUserRequest, UpdateUserRequest};
+use crate::config::pagination::PaginationConfig;
+use crate::dto::users::{CreateUserRequest, UpdateAvatarForm, UpdateUserRequest};
+use crate::services::avatars::AvatarService;
 use crate::services::users::UserService;
-use crate::structs::generals::{Empty, PaginationParam};
+use crate::structs::generals::{CursorPaginated, Empty, Paginated, PaginationParam};
+use crate::structs::profile::UserProfileResponse;
 use crate::structs::response_api::ResponseApi;
-use actix_web::{HttpResponse, Responder, delete, get, post, put, web};
+use actix_multipart::form::MultipartForm;
+use actix_web::{HttpRequest, HttpResponse, Responder, delete, get, post, put, web};
 
 #[utoipa::path(
     get,
@@ -11,22 +16,43 @@ use actix_web::{HttpResponse, Responder, delete, get, post, put, web};
     params(PaginationParam),
     tag = "user",
     responses(
-        (status = 200, description = "List users", body = ResponseApi<Vec<cf_entity::entity::users::Model>>),
+        (status = 200, description = "List users. Returns `CursorPaginated` instead when `cursor` or `limit` is given", body = ResponseApi<Paginated<UserProfileResponse>>),
+        (status = 400, description = "Invalid cursor or pagination bounds")
     )
 )]
 #[get("")]
 pub async fn get_all_users(
+  req: HttpRequest,
   service: web::Data<UserService>,
+  pagination: web::Data<PaginationConfig>,
   params: web::Query<PaginationParam>,
 ) -> Result<impl Responder, AppError> {
-  let page = params.page.unwrap_or(1);
-  let per_page = params.per_page.unwrap_or(10);
+  if params.is_cursor_mode() {
+    let limit = params.limit_bounds(&pagination)?;
+    let mut result = service
+      .get_users_by_cursor(params.cursor.as_deref(), limit)
+      .await?;
+    let items = service
+      .to_responses(std::mem::take(&mut result.items))
+      .await?;
+    let result = result.with_items(items);
+    return Ok(HttpResponse::Ok().json(ResponseApi {
+      status: true,
+      message: "Users retrieved successfully".to_string(),
+      data: Some(CursorPaginated::from(result)),
+    }));
+  }
 
-  let (users, _total_pages) = service.get_all_users(page, per_page).await?;
+  let (page, per_page) = params.page_bounds(&pagination)?;
+  let mut result = service.get_all_users(page, per_page).await?;
+  let items = service
+    .to_responses(std::mem::take(&mut result.items))
+    .await?;
+  let result = result.with_items(items);
   Ok(HttpResponse::Ok().json(ResponseApi {
     status: true,
     message: "Users retrieved successfully".to_string(),
-    data: Some(users),
+    data: Some(Paginated::from_page(result, &req)),
   }))
 }
 
@@ -35,7 +61,7 @@ pub async fn get_all_users(
     path = "/api/users/{id}",
     tag = "user",
     responses(
-        (status = 200, description = "Get user", body = ResponseApi<cf_entity::entity::users::Model>),
+        (status = 200, description = "Get user", body = ResponseApi<UserProfileResponse>),
         (status = 404, description = "User not found")
     )
 )]
@@ -45,6 +71,7 @@ pub async fn get_user(
   id: web::Path<uuid::Uuid>,
 ) -> Result<impl Responder, AppError> {
   let user = service.get_user(*id).await?;
+  let user = service.to_response(user).await?;
   Ok(HttpResponse::Ok().json(ResponseApi {
     status: true,
     message: "User retrieved successfully".to_string(),
@@ -58,7 +85,7 @@ pub async fn get_user(
     request_body = CreateUserRequest,
     tag = "user",
     responses(
-        (status = 201, description = "User created", body = ResponseApi<cf_entity::entity::users::Model>),
+        (status = 201, description = "User created", body = ResponseApi<UserProfileResponse>),
     )
 )]
 #[post("")]
@@ -67,6 +94,7 @@ pub async fn create_user(
   req: web::Json<CreateUserRequest>,
 ) -> Result<impl Responder, AppError> {
   let user = service.create_user(req.into_inner()).await?;
+  let user = service.to_response(user).await?;
   Ok(HttpResponse::Created().json(ResponseApi {
     status: true,
     message: "User created successfully".to_string(),
@@ -80,7 +108,7 @@ pub async fn create_user(
     tag = "user",
     request_body = UpdateUserRequest,
     responses(
-        (status = 200, description = "User updated", body = ResponseApi<cf_entity::entity::users::Model>),
+        (status = 200, description = "User updated", body = ResponseApi<UserProfileResponse>),
     )
 )]
 #[put("/{id}")]
@@ -90,6 +118,7 @@ pub async fn update_user(
   req: web::Json<UpdateUserRequest>,
 ) -> Result<impl Responder, AppError> {
   let user = service.update_user(*id, req.into_inner()).await?;
+  let user = service.to_response(user).await?;
   Ok(HttpResponse::Ok().json(ResponseApi {
     status: true,
     message: "User updated successfully".to_string(),
@@ -117,3 +146,98 @@ pub async fn delete_user(
     data: Some(Empty {}),
   }))
 }
+
+#[utoipa::path(
+    put,
+    path = "/api/users/{id}/avatar",
+    params(
+        ("Authorization" = String, Header, description = "Bearer token of the caller, must be the user"),
+    ),
+    request_body(content = UpdateAvatarForm, content_type = "multipart/form-data"),
+    tag = "user",
+    responses(
+        (status = 200, description = "Avatar replaced", body = ResponseApi<UserProfileResponse>),
+        (status = 400, description = "Not a supported image or crop rectangle outside the image"),
+        (status = 401, description = "Missing caller or caller is another user"),
+        (status = 404, description = "User not found"),
+        (status = 413, description = "Upload larger than the configured limit")
+    )
+)]
+#[put("/{id}/avatar")]
+pub async fn update_avatar(
+  service: web::Data<AvatarService>,
+  users: web::Data<UserService>,
+  user: AuthUser,
+  id: web::Path<uuid::Uuid>,
+  MultipartForm(form): MultipartForm<UpdateAvatarForm>,
+) -> Result<impl Responder, AppError> {
+  if user.id != *id {
+    return Err(AppError::Unauthorized(
+      "Cannot change another user's avatar".to_string(),
+    ));
+  }
+
+  let crop = form.crop_rect()?;
+  let user = service.update_avatar(*id, form.file.data, crop).await?;
+  let user = users.to_response(user).await?;
+  Ok(HttpResponse::Ok().json(ResponseApi {
+    status: true,
+    message: "Avatar updated successfully".to_string(),
+    data: Some(user),
+  }))
+}
+
+#[utoipa::path(
+    put,
+    path = "/api/users/{id}/follow",
+    params(
+        ("Authorization" = String, Header, description = "Bearer token of the caller"),
+    ),
+    tag = "user",
+    responses(
+        (status = 200, description = "Caller follows the user", body = ResponseApi<Empty>),
+        (status = 400, description = "Caller is the user"),
+        (status = 401, description = "Missing caller"),
+        (status = 404, description = "User not found")
+    )
+)]
+#[put("/{id}/follow")]
+pub async fn follow_user(
+  service: web::Data<UserService>,
+  user: AuthUser,
+  id: web::Path<uuid::Uuid>,
+) -> Result<impl Responder, AppError> {
+  service.follow(user.id, *id).await?;
+  Ok(HttpResponse::Ok().json(ResponseApi {
+    status: true,
+    message: "User followed successfully".to_string(),
+    data: Some(Empty {}),
+  }))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/users/{id}/follow",
+    params(
+        ("Authorization" = String, Header, description = "Bearer token of the caller"),
+    ),
+    tag = "user",
+    responses(
+        (status = 200, description = "Caller no longer follows the user", body = ResponseApi<Empty>),
+        (status = 401, description = "Missing caller"),
+        (status = 404, description = "Caller does not follow the user")
+    )
+)]
+#[delete("/{id}/follow")]
+pub async fn unfollow_user(
+  service: web::Data<UserService>,
+  user: AuthUser,
+  id: web::Path<uuid::Uuid>,
+) -> Result<impl Responder, AppError> {
+  service.unfollow(user.id, *id).await?;
+  Ok(HttpResponse::Ok().json(ResponseApi {
+    status: true,
+    message: "User unfollowed successfully".to_string(),
+    data: Some(Empty {}),
+  }))
+}
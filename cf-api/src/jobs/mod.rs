@@ -0,0 +1,4 @@
+pub mod sitemap_refresh;
+pub mod trending_refresh;
+pub mod upload_sweeper;
+pub mod view_rollup;
@@ -0,0 +1,19 @@
+use std::time::Duration;
+
+use crate::services::sitemaps::SitemapService;
+
+/// Periodically bring the stored sitemaps up to date with published posts
+/// and public profiles.
+pub fn spawn(service: SitemapService, interval: Duration) {
+  tokio::spawn(async move {
+    let mut ticker = tokio::time::interval(interval);
+    loop {
+      ticker.tick().await;
+      match service.regenerate().await {
+        Ok(0) => {}
+        Ok(written) => tracing::info!("Rewrote {} sitemaps", written),
+        Err(err) => tracing::error!("Sitemap refresh failed: {}", err),
+      }
+    }
+  });
+}
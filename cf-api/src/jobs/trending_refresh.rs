@@ -0,0 +1,44 @@
+use std::sync::Arc;
+
+use cf_entity::entity::sea_orm_active_enums::TrendingPeriod;
+use cf_repository::repositories::PostTrendingRepository;
+use chrono::Utc;
+use sea_orm::{DatabaseConnection, Iterable};
+
+use crate::config::trending::{self, TrendingConfig};
+
+/// Periodically rebuild the trending rankings so reading them is a plain
+/// indexed query.
+pub fn spawn(db: Arc<DatabaseConnection>, config: TrendingConfig) {
+  tokio::spawn(async move {
+    let mut ticker = tokio::time::interval(config.refresh_interval);
+    loop {
+      ticker.tick().await;
+      for period in TrendingPeriod::iter() {
+        run_once(&db, &config, period).await;
+      }
+    }
+  });
+}
+
+async fn run_once(db: &DatabaseConnection, config: &TrendingConfig, period: TrendingPeriod) {
+  // Events older than the window are ignored; within it a quarter of the
+  // window halves their weight, so fresh activity wins over old totals
+  let window = trending::window(period);
+  let now = Utc::now();
+  let half_life_secs = window.num_seconds() as f64 / 4.0;
+
+  if let Err(err) = PostTrendingRepository::recompute(
+    db,
+    period,
+    now.into(),
+    (now - window).into(),
+    half_life_secs,
+    config.weights,
+    config.max_posts,
+  )
+  .await
+  {
+    tracing::error!("Trending refresh for {:?} failed: {}", period, err);
+  }
+}
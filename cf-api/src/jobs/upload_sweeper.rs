@@ -0,0 +1,19 @@
+use std::time::Duration;
+
+use crate::services::media::MediaService;
+
+/// Periodically remove presigned uploads that expired without completion,
+/// both the stored object and the pending row.
+pub fn spawn(service: MediaService, interval: Duration) {
+  tokio::spawn(async move {
+    let mut ticker = tokio::time::interval(interval);
+    loop {
+      ticker.tick().await;
+      match service.sweep_expired_uploads().await {
+        Ok(0) => {}
+        Ok(removed) => tracing::info!("Removed {} expired uploads", removed),
+        Err(err) => tracing::error!("Upload sweep failed: {}", err),
+      }
+    }
+  });
+}
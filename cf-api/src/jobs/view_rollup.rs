@@ -0,0 +1,41 @@
+use std::sync::Arc;
+
+use cf_repository::repositories::PostViewsRepository;
+use chrono::{Duration, Utc};
+use sea_orm::DatabaseConnection;
+
+use crate::config::analytics::AnalyticsConfig;
+
+/// Periodically fold raw post views into `post_view_daily`, refresh
+/// `posts.view_count` and trim raw views past their retention.
+pub fn spawn(db: Arc<DatabaseConnection>, config: AnalyticsConfig) {
+  tokio::spawn(async move {
+    let mut ticker = tokio::time::interval(config.rollup_interval);
+    loop {
+      ticker.tick().await;
+      run_once(&db, &config).await;
+    }
+  });
+}
+
+async fn run_once(db: &DatabaseConnection, config: &AnalyticsConfig) {
+  let today = Utc::now().date_naive();
+  let since = (today - Duration::days(config.rollup_lookback_days - 1))
+    .and_hms_opt(0, 0, 0)
+    .map(|start| start.and_utc());
+  let Some(since) = since else {
+    return;
+  };
+
+  if let Err(err) = PostViewsRepository::rollup_since(db, since.into()).await {
+    tracing::error!("Post view rollup failed: {}", err);
+    return;
+  }
+
+  let cutoff = Utc::now() - Duration::days(config.raw_retention_days);
+  match PostViewsRepository::delete_before(db, cutoff.into()).await {
+    Ok(0) => {}
+    Ok(deleted) => tracing::info!("Deleted {} expired post views", deleted),
+    Err(err) => tracing::error!("Failed to delete expired post views: {}", err),
+  }
+}
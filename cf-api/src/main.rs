@@ -1,9 +1,36 @@
-use crate::config::actix_error::{json_error_handler, path_error_handler};
+use crate::config::actix_error::{json_error_handler, multipart_error_handler, path_error_handler};
+use crate::config::analytics::AnalyticsConfig;
+use crate::config::auth::{AdminConfig, AuthConfig};
 use crate::config::database::connect_db;
+use crate::config::media::MediaConfig;
+use crate::config::storage::{storage_from_env, url_resolver_from_env};
+use crate::config::pagination::PaginationConfig;
+use crate::config::preview::PreviewConfig;
+use crate::config::sanitize::sanitize_policies_from_env;
+use crate::config::site::SiteConfig;
+use crate::config::trending::TrendingConfig;
+use crate::routes::feeds::feed_routes;
+use crate::routes::images::image_routes;
 use crate::routes::routes::main_routes;
+use crate::routes::sitemaps::sitemap_routes;
+use crate::services::analytics::{AnalyticsService, ViewRecorder};
+use crate::services::avatars::AvatarService;
+use crate::services::bookmarks::BookmarkService;
+use crate::services::comments::CommentService;
+use crate::services::feeds::FeedService;
+use crate::services::images::ImageService;
+use crate::services::media::MediaService;
+use crate::services::media_processing::MediaProcessor;
+use crate::services::placements::PlacementService;
+use crate::services::post_meta::PostMetaService;
 use crate::services::posts::PostService;
+use crate::services::previews::PreviewService;
+use crate::services::reactions::ReactionService;
+use crate::services::sanitize::SanitizeService;
+use crate::services::sitemaps::SitemapService;
 use crate::services::users::UserService;
 use actix_cors::Cors;
+use actix_multipart::form::MultipartFormConfig;
 use actix_web::{web, App, HttpResponse, HttpServer};
 use cf_migration::{Migrator, MigratorTrait};
 use cf_util::intro;
@@ -16,13 +43,20 @@ use utoipa_swagger_ui::SwaggerUi;
 mod config;
 mod dto;
 mod handlers;
+mod jobs;
 mod routes;
 mod services;
 mod structs;
 
+use cf_entity::entity::comments::Model as CommentModel;
+use cf_entity::entity::media::Model as MediaModel;
+use cf_entity::entity::post_placements::Model as PlacementModel;
 use cf_entity::entity::posts::Model as PostModel;
-use cf_entity::entity::sea_orm_active_enums::PostStatus;
-use cf_entity::entity::users::Model as UserModel;
+use cf_entity::entity::tags::Model as TagModel;
+use cf_entity::entity::sea_orm_active_enums::{MediaStatus, PlacementKind, PostStatus, PostVisibility, ReactionKind, TrendingPeriod};
+use crate::structs::media::MediaResponse;
+use crate::structs::profile::UserProfileResponse;
+use crate::structs::post::{PostResponse, PublicPostResponse};
 
 // Define OpenAPI spec
 #[derive(OpenApi)]
@@ -36,28 +70,152 @@ use cf_entity::entity::users::Model as UserModel;
         crate::handlers::users::get_user,
         crate::handlers::users::create_user,
         crate::handlers::users::update_user,
+        crate::handlers::users::update_avatar,
         crate::handlers::users::delete_user,
+        crate::handlers::users::follow_user,
+        crate::handlers::users::unfollow_user,
         crate::handlers::posts::get_all_posts,
+        crate::handlers::posts::get_trending_posts,
+        crate::handlers::posts::get_featured_posts,
         crate::handlers::posts::get_post,
+        crate::handlers::posts::get_post_meta,
+        crate::handlers::posts::get_related_posts,
         crate::handlers::posts::create_post,
         crate::handlers::posts::update_post,
         crate::handlers::posts::delete_post,
+        crate::handlers::previews::create_preview_link,
+        crate::handlers::previews::get_preview_links,
+        crate::handlers::previews::revoke_preview_link,
+        crate::handlers::previews::get_preview,
+        crate::handlers::timeline::get_timeline,
+        crate::handlers::public::get_public_posts,
+        crate::handlers::public::get_public_post,
+        crate::handlers::public::get_public_post_meta,
+        crate::handlers::comments::get_post_comments,
+        crate::handlers::comments::create_comment,
+        crate::handlers::comments::update_comment,
+        crate::handlers::comments::delete_comment,
+        crate::handlers::reactions::get_post_reactions,
+        crate::handlers::reactions::toggle_post_reaction,
+        crate::handlers::reactions::get_comment_reactions,
+        crate::handlers::reactions::toggle_comment_reaction,
+        crate::handlers::bookmarks::get_bookmarks,
+        crate::handlers::bookmarks::add_bookmark,
+        crate::handlers::bookmarks::remove_bookmark,
+        crate::handlers::bookmarks::check_bookmarks,
+        crate::handlers::bookmarks::get_collections,
+        crate::handlers::bookmarks::create_collection,
+        crate::handlers::bookmarks::rename_collection,
+        crate::handlers::bookmarks::delete_collection,
+        crate::handlers::analytics::get_post_stats,
+        crate::handlers::media::get_all_media,
+        crate::handlers::media::get_media,
+        crate::handlers::media::update_media,
+        crate::handlers::media::delete_media,
+        crate::handlers::media::upload_media,
+        crate::handlers::media::create_upload,
+        crate::handlers::media::complete_upload,
+        crate::handlers::images::get_image,
+        crate::handlers::sanitize::preview_sanitize,
+        crate::handlers::feeds::get_posts_feed,
+        crate::handlers::feeds::get_author_feed,
+        crate::handlers::feeds::get_tag_feed,
+        crate::handlers::sitemaps::get_sitemap_index,
+        crate::handlers::sitemaps::get_sitemap,
+        crate::handlers::placements::get_placements,
+        crate::handlers::placements::reorder_placements,
+        crate::handlers::placements::place_post,
+        crate::handlers::placements::remove_placement,
     ),
     components(
         schemas(
             crate::dto::users::CreateUserRequest,
             crate::dto::users::UpdateUserRequest,
+            crate::dto::users::UpdateAvatarForm,
             crate::dto::posts::CreatePostRequest,
             crate::dto::posts::UpdatePostRequest,
+            crate::dto::posts::RelatedPostsParam,
+            crate::dto::previews::CreatePreviewRequest,
+            crate::structs::preview::PreviewLinkResponse,
+            crate::structs::preview::DraftPreview,
+            crate::dto::timeline::TimelineParam,
+            crate::dto::trending::TrendingParam,
+            crate::dto::comments::CreateCommentRequest,
+            crate::dto::comments::UpdateCommentRequest,
+            crate::dto::comments::CommentFormat,
+            crate::dto::comments::CommentListParam,
+            crate::structs::comment::CommentNode,
+            crate::dto::reactions::ToggleReactionRequest,
+            crate::structs::reaction::ReactionCount,
+            crate::structs::reaction::ReactionSummary,
+            crate::dto::bookmarks::AddBookmarkRequest,
+            crate::dto::bookmarks::BookmarkCollectionRequest,
+            crate::dto::bookmarks::BookmarkCheckRequest,
+            crate::dto::bookmarks::BookmarkSort,
+            crate::dto::bookmarks::BookmarkListParam,
+            crate::structs::bookmark::BookmarkResponse,
+            crate::dto::analytics::PostStatsParam,
+            crate::structs::analytics::DailyViews,
+            crate::structs::analytics::PostStats,
+            crate::dto::media::UploadMediaForm,
+            crate::dto::media::CreateUploadRequest,
+            crate::dto::media::MediaListParam,
+            crate::dto::media::UpdateMediaRequest,
+            crate::structs::media::PresignedUpload,
+            crate::structs::media::MediaResponse,
+            crate::structs::media::RenditionResponse,
+            crate::dto::images::ImageFit,
+            crate::dto::images::ImageOutputFormat,
+            crate::dto::images::ImageParam,
+            crate::dto::sanitize::SanitizeRequest,
+            crate::structs::sanitize::SanitizePreview,
+            crate::structs::sanitize::SanitizeReportResponse,
+            crate::dto::feeds::FeedFormat,
+            crate::dto::placements::PlacePostRequest,
+            crate::dto::placements::ReorderPlacementsRequest,
+            PlacementModel,
+            MediaModel,
+            crate::structs::post::PostResponse,
+            crate::structs::post::PostMeta,
+            crate::structs::post::MetaTag,
+            crate::structs::post::PublicPostResponse,
             crate::structs::generals::PaginationParam,
-            UserModel,
+            crate::structs::generals::PaginationLinks,
+            crate::structs::generals::Paginated<PostResponse>,
+            crate::structs::generals::Paginated<UserProfileResponse>,
+            crate::structs::generals::Paginated<MediaResponse>,
+            crate::structs::generals::Paginated<PublicPostResponse>,
+            crate::structs::generals::CursorPaginated<PostResponse>,
+            crate::structs::generals::CursorPaginated<UserProfileResponse>,
+            crate::structs::generals::CursorPaginated<PublicPostResponse>,
+            UserProfileResponse,
             PostModel,
+            CommentModel,
+            TagModel,
             PostStatus,
+            PostVisibility,
+            TrendingPeriod,
+            PlacementKind,
+            MediaStatus,
+            ReactionKind,
         )
     ),
     tags(
         (name = "user", description = "User management endpoints"),
         (name = "post", description = "Post management endpoints"),
+        (name = "timeline", description = "Home timeline of followed authors"),
+        (name = "preview", description = "Signed, expiring links to unpublished drafts"),
+        (name = "public", description = "Read-only published content for anonymous readers"),
+        (name = "comment", description = "Threaded post comment endpoints"),
+        (name = "reaction", description = "Post and comment reaction endpoints"),
+        (name = "bookmark", description = "Bookmark and reading list endpoints"),
+        (name = "analytics", description = "Post view statistics endpoints"),
+        (name = "media", description = "Media uploads, direct and presigned, and the media library"),
+        (name = "image", description = "Resized image delivery"),
+        (name = "sanitize", description = "HTML sanitisation previews"),
+        (name = "feed", description = "RSS and Atom feeds of published posts"),
+        (name = "sitemap", description = "XML sitemaps of published posts and public profiles"),
+        (name = "admin", description = "Editor curation of pinned and featured posts"),
     ),
 )]
 struct ApiDoc;
@@ -108,9 +266,89 @@ async fn main() {
 
   let db = Arc::new(db);
 
+  let auth_config = match AuthConfig::from_env() {
+    Ok(config) => config,
+    Err(err) => {
+      eprintln!("Failed to configure authentication: {}", err);
+      process::exit(1);
+    }
+  };
+
   // Initial services
-  let user_service = UserService::new(db.clone());
   let post_service = PostService::new(db.clone());
+  let placement_service = PlacementService::new(db.clone());
+  let sanitize_policies = match sanitize_policies_from_env() {
+    Ok(policies) => policies,
+    Err(err) => {
+      eprintln!("Failed to load sanitize policies: {}", err);
+      process::exit(1);
+    }
+  };
+  let comment_service = CommentService::new(db.clone(), &sanitize_policies);
+  let site_config = SiteConfig::from_env();
+  let feed_service = FeedService::new(db.clone(), site_config.clone(), &sanitize_policies);
+  let preview_config = match PreviewConfig::from_env() {
+    Ok(config) => config,
+    Err(err) => {
+      eprintln!("Failed to configure preview links: {}", err);
+      process::exit(1);
+    }
+  };
+  let preview_service = PreviewService::new(
+    db.clone(),
+    preview_config,
+    site_config.clone(),
+    &sanitize_policies,
+  );
+  let sanitize_service = SanitizeService::new(sanitize_policies);
+  let reaction_service = ReactionService::new(db.clone());
+  let bookmark_service = BookmarkService::new(db.clone());
+  let analytics_service = AnalyticsService::new(db.clone());
+
+  let pagination_config = PaginationConfig::from_env();
+  let admin_config = AdminConfig::from_env();
+  let analytics_config = AnalyticsConfig::from_env();
+  let media_config = MediaConfig::from_env();
+
+  let storage = match storage_from_env() {
+    Ok(storage) => storage,
+    Err(err) => {
+      eprintln!("Failed to configure storage: {}", err);
+      process::exit(1);
+    }
+  };
+  let url_resolver = match url_resolver_from_env(storage.clone()) {
+    Ok(resolver) => resolver,
+    Err(err) => {
+      eprintln!("Failed to configure media URLs: {}", err);
+      process::exit(1);
+    }
+  };
+  let user_service = UserService::new(db.clone(), url_resolver.clone());
+  let media_processor = MediaProcessor::start(
+    db.clone(),
+    storage.clone(),
+    media_config.renditions.clone(),
+  );
+  let avatar_service = AvatarService::new(db.clone(), storage.clone(), media_config.clone());
+  let image_service = ImageService::new(db.clone(), storage.clone(), media_config.clone());
+  let sitemap_service = SitemapService::new(db.clone(), storage.clone(), site_config.clone());
+  let post_meta_service =
+    PostMetaService::new(db.clone(), url_resolver.clone(), site_config.clone());
+  let media_service = MediaService::new(
+    db.clone(),
+    storage.clone(),
+    media_processor,
+    url_resolver.clone(),
+    media_config.clone(),
+  );
+
+  // Background jobs
+  let view_recorder = ViewRecorder::start(db.clone(), analytics_config.clone());
+  jobs::view_rollup::spawn(db.clone(), analytics_config);
+  jobs::upload_sweeper::spawn(media_service.clone(), media_config.upload_sweep_interval);
+  jobs::trending_refresh::spawn(db.clone(), TrendingConfig::from_env());
+  jobs::sitemap_refresh::spawn(sitemap_service.clone(), site_config.sitemap_refresh_interval);
 
   // Run web api
   println!("\nStarting server to {}:{}", api_host, api_port);
@@ -129,8 +367,32 @@ async fn main() {
       )
       .app_data(web::Data::new(user_service.clone()))
       .app_data(web::Data::new(post_service.clone()))
+      .app_data(web::Data::new(post_meta_service.clone()))
+      .app_data(web::Data::new(placement_service.clone()))
+      .app_data(web::Data::new(preview_service.clone()))
+      .app_data(web::Data::new(comment_service.clone()))
+      .app_data(web::Data::new(sanitize_service.clone()))
+      .app_data(web::Data::new(feed_service.clone()))
+      .app_data(web::Data::new(sitemap_service.clone()))
+      .app_data(web::Data::new(reaction_service.clone()))
+      .app_data(web::Data::new(bookmark_service.clone()))
+      .app_data(web::Data::new(analytics_service.clone()))
+      .app_data(web::Data::new(view_recorder.clone()))
+      .app_data(web::Data::new(media_service.clone()))
+      .app_data(web::Data::new(image_service.clone()))
+      .app_data(web::Data::new(avatar_service.clone()))
+      .app_data(web::Data::new(pagination_config.clone()))
+      .app_data(web::Data::new(auth_config.clone()))
+      .app_data(web::Data::new(admin_config.clone()))
       .app_data(web::PathConfig::default().error_handler(path_error_handler))
       .app_data(web::JsonConfig::default().error_handler(json_error_handler))
+      .app_data(
+        MultipartFormConfig::default()
+          // Leave room for the multipart framing around the file
+          .total_limit(media_config.max_upload_bytes + 64 * 1024)
+          .memory_limit(media_config.max_upload_bytes + 64 * 1024)
+          .error_handler(multipart_error_handler),
+      )
       .route(
         "/",
         web::get().to(|| async { HttpResponse::Ok().body("API framework template is running!") }),
@@ -146,6 +408,9 @@ async fn main() {
       )
       .configure(|cfg| {
         cfg.service(web::scope("/api").configure(main_routes));
+        cfg.service(web::scope("/img").configure(image_routes));
+        cfg.service(web::scope("/feeds").configure(feed_routes));
+        cfg.configure(sitemap_routes);
       })
       .default_service(
         web::route().to(|| async { HttpResponse::NotFound().body("404 page not found") }),
@@ -0,0 +1,7 @@
+use crate::handlers::analytics::*;
+use actix_web::web;
+
+/// Analytics routes nested under `/posts`
+pub fn post_analytics_routes(cfg: &mut web::ServiceConfig) {
+  cfg.service(get_post_stats);
+}
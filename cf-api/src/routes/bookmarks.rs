@@ -0,0 +1,15 @@
+use crate::handlers::bookmarks::*;
+use actix_web::web;
+
+pub fn bookmark_routes(cfg: &mut web::ServiceConfig) {
+  // Fixed segments go before `/{post_id}` so they are not parsed as ids
+  cfg
+    .service(get_bookmarks)
+    .service(check_bookmarks)
+    .service(get_collections)
+    .service(create_collection)
+    .service(rename_collection)
+    .service(delete_collection)
+    .service(add_bookmark)
+    .service(remove_bookmark);
+}
@@ -0,0 +1,11 @@
+use crate::handlers::comments::*;
+use actix_web::web;
+
+/// Comment routes nested under `/posts`
+pub fn post_comment_routes(cfg: &mut web::ServiceConfig) {
+  cfg.service(get_post_comments).service(create_comment);
+}
+
+pub fn comment_routes(cfg: &mut web::ServiceConfig) {
+  cfg.service(update_comment).service(delete_comment);
+}
@@ -0,0 +1,9 @@
+use crate::handlers::feeds::*;
+use actix_web::web;
+
+pub fn feed_routes(cfg: &mut web::ServiceConfig) {
+  cfg
+    .service(get_posts_feed)
+    .service(get_author_feed)
+    .service(get_tag_feed);
+}
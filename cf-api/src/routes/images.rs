@@ -0,0 +1,6 @@
+use crate::handlers::images::*;
+use actix_web::web;
+
+pub fn image_routes(cfg: &mut web::ServiceConfig) {
+  cfg.service(get_image);
+}
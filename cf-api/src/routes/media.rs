@@ -0,0 +1,13 @@
+use crate::handlers::media::*;
+use actix_web::web;
+
+pub fn media_routes(cfg: &mut web::ServiceConfig) {
+  cfg
+    .service(upload_media)
+    .service(create_upload)
+    .service(complete_upload)
+    .service(get_all_media)
+    .service(get_media)
+    .service(update_media)
+    .service(delete_media);
+}
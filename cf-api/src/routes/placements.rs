@@ -0,0 +1,12 @@
+use crate::handlers::placements::*;
+use actix_web::web;
+
+/// Editor curation under `/admin/placements`
+pub fn placement_routes(cfg: &mut web::ServiceConfig) {
+  cfg
+    .service(get_placements)
+    // Before `/{kind}/{post_id}`, which would also match
+    .service(reorder_placements)
+    .service(place_post)
+    .service(remove_placement);
+}
@@ -3,7 +3,11 @@ use actix_web::web;
 
 pub fn post_routes(cfg: &mut web::ServiceConfig) {
     cfg.service(get_all_posts)
+        .service(get_trending_posts)
+        .service(get_featured_posts)
         .service(get_post)
+        .service(get_post_meta)
+        .service(get_related_posts)
         .service(create_post)
         .service(update_post)
         .service(delete_post);
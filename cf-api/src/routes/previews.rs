@@ -0,0 +1,14 @@
+use crate::handlers::previews::*;
+use actix_web::web;
+
+/// Preview link management nested under `/posts`
+pub fn post_preview_routes(cfg: &mut web::ServiceConfig) {
+  cfg
+    .service(create_preview_link)
+    .service(get_preview_links)
+    .service(revoke_preview_link);
+}
+
+pub fn preview_routes(cfg: &mut web::ServiceConfig) {
+  cfg.service(get_preview);
+}
@@ -0,0 +1,9 @@
+use crate::handlers::public::*;
+use actix_web::web;
+
+pub fn public_post_routes(cfg: &mut web::ServiceConfig) {
+  cfg
+    .service(get_public_posts)
+    .service(get_public_post)
+    .service(get_public_post_meta);
+}
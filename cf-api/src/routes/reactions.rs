@@ -0,0 +1,16 @@
+use crate::handlers::reactions::*;
+use actix_web::web;
+
+/// Reaction routes nested under `/posts`
+pub fn post_reaction_routes(cfg: &mut web::ServiceConfig) {
+  cfg
+    .service(get_post_reactions)
+    .service(toggle_post_reaction);
+}
+
+/// Reaction routes nested under `/comments`
+pub fn comment_reaction_routes(cfg: &mut web::ServiceConfig) {
+  cfg
+    .service(get_comment_reactions)
+    .service(toggle_comment_reaction);
+}
@@ -1,8 +1,40 @@
-use super::{posts::post_routes, users::user_routes};
+use super::{
+  analytics::post_analytics_routes,
+  bookmarks::bookmark_routes,
+  comments::{comment_routes, post_comment_routes},
+  media::media_routes,
+  placements::placement_routes,
+  posts::post_routes,
+  previews::{post_preview_routes, preview_routes},
+  public::public_post_routes,
+  reactions::{comment_reaction_routes, post_reaction_routes},
+  sanitize::sanitize_routes,
+  timeline::timeline_routes,
+  users::user_routes,
+};
 use actix_web::web;
 
 pub fn main_routes(cfg: &mut web::ServiceConfig) {
   cfg
     .service(web::scope("/users").configure(user_routes))
-    .service(web::scope("/posts").configure(post_routes));
+    .service(web::scope("/feed").configure(timeline_routes))
+    .service(
+      web::scope("/posts")
+        .configure(post_analytics_routes)
+        .configure(post_comment_routes)
+        .configure(post_preview_routes)
+        .configure(post_reaction_routes)
+        .configure(post_routes),
+    )
+    .service(
+      web::scope("/comments")
+        .configure(comment_reaction_routes)
+        .configure(comment_routes),
+    )
+    .service(web::scope("/bookmarks").configure(bookmark_routes))
+    .service(web::scope("/media").configure(media_routes))
+    .service(web::scope("/sanitize").configure(sanitize_routes))
+    .service(web::scope("/preview").configure(preview_routes))
+    .service(web::scope("/public/posts").configure(public_post_routes))
+    .service(web::scope("/admin/placements").configure(placement_routes));
 }
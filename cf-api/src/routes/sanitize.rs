@@ -0,0 +1,6 @@
+use crate::handlers::sanitize::*;
+use actix_web::web;
+
+pub fn sanitize_routes(cfg: &mut web::ServiceConfig) {
+  cfg.service(preview_sanitize);
+}
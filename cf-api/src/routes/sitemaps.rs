@@ -0,0 +1,6 @@
+use crate::handlers::sitemaps::*;
+use actix_web::web;
+
+pub fn sitemap_routes(cfg: &mut web::ServiceConfig) {
+  cfg.service(get_sitemap_index).service(get_sitemap);
+}
@@ -0,0 +1,6 @@
+use crate::handlers::timeline::*;
+use actix_web::web;
+
+pub fn timeline_routes(cfg: &mut web::ServiceConfig) {
+  cfg.service(get_timeline);
+}
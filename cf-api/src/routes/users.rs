@@ -7,5 +7,8 @@ pub fn user_routes(cfg: &mut web::ServiceConfig) {
     .service(get_user)
     .service(create_user)
     .service(update_user)
+    .service(update_avatar)
+    .service(follow_user)
+    .service(unfollow_user)
     .service(delete_user);
 }
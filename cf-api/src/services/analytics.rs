@@ -0,0 +1,246 @@
+use std::net::{IpAddr, SocketAddr};
+use std::sync::Arc;
+
+use actix_web::HttpRequest;
+
+use cf_entity::entity::post_views;
+use cf_repository::repositories::{PostViewDailyRepository, PostViewsRepository, PostsRepository};
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use sea_orm::{DatabaseConnection, Set};
+use sha2::{Digest, Sha256};
+use tokio::sync::mpsc;
+use uuid::Uuid;
+
+use crate::config::analytics::AnalyticsConfig;
+use crate::config::custom_error::AppError;
+use crate::structs::analytics::{DailyViews, PostStats};
+
+/// `X-Forwarded-For` entries in the order the proxies added them.
+fn forwarded_for(req: &HttpRequest) -> Vec<String> {
+  req
+    .headers()
+    .get_all("x-forwarded-for")
+    .filter_map(|value| value.to_str().ok())
+    .flat_map(|value| value.split(','))
+    .map(|hop| hop.trim().to_string())
+    .filter(|hop| !hop.is_empty())
+    .collect()
+}
+
+/// An entry as a bare address or with a port.
+fn parse_hop(hop: &str) -> Option<IpAddr> {
+  hop
+    .parse()
+    .ok()
+    .or_else(|| hop.parse::<SocketAddr>().ok().map(|addr| addr.ip()))
+}
+
+/// Longest range the stats endpoint serves in one request.
+pub const MAX_STATS_DAYS: i64 = 366;
+
+struct ViewEvent {
+  post_id: Uuid,
+  fingerprint: String,
+  viewed_at: DateTime<Utc>,
+}
+
+/// Queues post views in memory and writes them in batches from a background
+/// task, so reading a post never waits on an insert.
+#[derive(Clone)]
+pub struct ViewRecorder {
+  sender: mpsc::Sender<ViewEvent>,
+  trusted_proxies: Arc<[IpAddr]>,
+}
+
+impl ViewRecorder {
+  /// Spawn the flush task. Must be called from inside the runtime.
+  pub fn start(db: Arc<DatabaseConnection>, config: AnalyticsConfig) -> Self {
+    let (sender, receiver) = mpsc::channel(config.queue_capacity);
+    let trusted_proxies = config.trusted_proxies.clone().into();
+    tokio::spawn(flush_views(db, config, receiver));
+    Self {
+      sender,
+      trusted_proxies,
+    }
+  }
+
+  /// Address of the visitor: the peer, or the client a trusted proxy
+  /// forwarded for. Forwarding headers from anyone else are ignored so they
+  /// cannot be used to inflate views.
+  pub fn client_ip(&self, req: &HttpRequest) -> Option<String> {
+    let peer = req.peer_addr()?.ip();
+    if !self.trusted_proxies.contains(&peer) {
+      return Some(peer.to_string());
+    }
+
+    // Every proxy appends the address it was reached from, so entries left
+    // of the first untrusted one from the right may be made up by the client
+    let mut client = peer.to_string();
+    for hop in forwarded_for(req).iter().rev() {
+      match parse_hop(hop) {
+        Some(ip) => {
+          client = ip.to_string();
+          if !self.trusted_proxies.contains(&ip) {
+            break;
+          }
+        }
+        None => {
+          client = hop.clone();
+          break;
+        }
+      }
+    }
+    Some(client)
+  }
+
+  /// Hash whatever identifies the visitor so raw addresses are never stored.
+  pub fn fingerprint(ip: Option<&str>, user_agent: Option<&str>, user_id: Option<Uuid>) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(ip.unwrap_or_default());
+    hasher.update(b"|");
+    hasher.update(user_agent.unwrap_or_default());
+    hasher.update(b"|");
+    if let Some(user_id) = user_id {
+      hasher.update(user_id.as_bytes());
+    }
+    hex::encode(hasher.finalize())
+  }
+
+  /// Never blocks. When the queue is full the view is dropped, losing a count
+  /// is preferable to slowing down reads.
+  pub fn record(&self, post_id: Uuid, fingerprint: String) {
+    let event = ViewEvent {
+      post_id,
+      fingerprint,
+      viewed_at: Utc::now(),
+    };
+    if self.sender.try_send(event).is_err() {
+      tracing::warn!("View queue full, dropping view of post {}", post_id);
+    }
+  }
+}
+
+async fn flush_views(
+  db: Arc<DatabaseConnection>,
+  config: AnalyticsConfig,
+  mut receiver: mpsc::Receiver<ViewEvent>,
+) {
+  let window_secs = config.dedup_window.as_secs() as i64;
+  let mut buffer: Vec<ViewEvent> = Vec::with_capacity(config.flush_batch_size);
+  let mut ticker = tokio::time::interval(config.flush_interval);
+
+  loop {
+    let closed = tokio::select! {
+      event = receiver.recv() => match event {
+        Some(event) => {
+          buffer.push(event);
+          if buffer.len() < config.flush_batch_size {
+            continue;
+          }
+          false
+        }
+        None => true,
+      },
+      _ = ticker.tick() => false,
+    };
+
+    if !buffer.is_empty() {
+      let views = buffer
+        .drain(..)
+        .map(|event| {
+          let secs = event.viewed_at.timestamp();
+          let window_start =
+            DateTime::<Utc>::from_timestamp(secs - secs.rem_euclid(window_secs), 0)
+              .unwrap_or(event.viewed_at);
+
+          post_views::ActiveModel {
+            id: Set(Uuid::new_v4()),
+            post_id: Set(event.post_id),
+            fingerprint: Set(event.fingerprint),
+            window_start: Set(window_start.into()),
+            viewed_at: Set(event.viewed_at.into()),
+          }
+        })
+        .collect();
+
+      if let Err(err) = PostViewsRepository::insert_many_deduplicated(&db, views).await {
+        tracing::error!("Failed to store post views: {}", err);
+      }
+    }
+
+    if closed {
+      break;
+    }
+  }
+}
+
+#[derive(Clone)]
+pub struct AnalyticsService {
+  db: Arc<DatabaseConnection>,
+}
+
+impl AnalyticsService {
+  pub fn new(db: Arc<DatabaseConnection>) -> Self {
+    Self { db }
+  }
+
+  /// Daily views of a post between `from` and `to` inclusive, defaulting to
+  /// the last 30 days. Days without views are filled with zeroes.
+  pub async fn get_post_stats(
+    &self,
+    user_id: Uuid,
+    post_id: Uuid,
+    from: Option<NaiveDate>,
+    to: Option<NaiveDate>,
+  ) -> Result<PostStats, AppError> {
+    let post = PostsRepository::find_readable(&self.db, post_id, Some(user_id))
+      .await
+      .map_err(AppError::from)?
+      .filter(|post| post.user_id == user_id)
+      .ok_or_else(|| AppError::NotFound("Post not found".to_string()))?;
+
+    let to = to.unwrap_or_else(|| Utc::now().date_naive());
+    let from = from.unwrap_or(to - Duration::days(29));
+    if from > to {
+      return Err(AppError::ValidationError(
+        "from must not be after to".to_string(),
+      ));
+    }
+    if (to - from).num_days() >= MAX_STATS_DAYS {
+      return Err(AppError::ValidationError(format!(
+        "Range cannot exceed {} days",
+        MAX_STATS_DAYS
+      )));
+    }
+
+    let rows = PostViewDailyRepository::find_by_post_between(&self.db, post_id, from, to)
+      .await
+      .map_err(AppError::from)?;
+
+    let mut rows = rows.into_iter().peekable();
+    let series = from
+      .iter_days()
+      .take_while(|day| *day <= to)
+      .map(|day| match rows.next_if(|row| row.day == day) {
+        Some(row) => DailyViews {
+          day,
+          views: row.views,
+          unique_visitors: row.unique_visitors,
+        },
+        None => DailyViews {
+          day,
+          views: 0,
+          unique_visitors: 0,
+        },
+      })
+      .collect();
+
+    Ok(PostStats {
+      post_id,
+      view_count: post.view_count,
+      from,
+      to,
+      series,
+    })
+  }
+}
@@ -0,0 +1,156 @@
+use std::sync::Arc;
+
+use bytes::Bytes;
+use cf_entity::entity::users;
+use cf_repository::repositories::UsersRepository;
+use cf_util::imaging::{self, Fit, ImageFormat, ImagingError};
+use cf_util::storage::Storage;
+use sea_orm::DatabaseConnection;
+use uuid::Uuid;
+
+use crate::config::custom_error::AppError;
+use crate::config::media::MediaConfig;
+use crate::dto::users::CropRect;
+
+#[derive(Clone)]
+pub struct AvatarService {
+  db: Arc<DatabaseConnection>,
+  storage: Arc<dyn Storage>,
+  config: MediaConfig,
+}
+
+impl AvatarService {
+  pub fn new(db: Arc<DatabaseConnection>, storage: Arc<dyn Storage>, config: MediaConfig) -> Self {
+    Self {
+      db,
+      storage,
+      config,
+    }
+  }
+
+  /// Crop the image, store one square rendition per configured size under
+  /// `avatars/{user_id}/{avatar_id}/` and point `avatar_key` at the largest.
+  /// Objects of the replaced avatar are removed once the update is committed.
+  pub async fn update_avatar(
+    &self,
+    user_id: Uuid,
+    data: Bytes,
+    crop: Option<CropRect>,
+  ) -> Result<users::Model, AppError> {
+    UsersRepository::find_by_id(&self.db, user_id)
+      .await
+      .map_err(AppError::from)?
+      .ok_or_else(|| AppError::NotFound("User not found".to_string()))?;
+
+    if data.is_empty() {
+      return Err(AppError::ValidationError("File is empty".to_string()));
+    }
+    if data.len() > self.config.max_upload_bytes {
+      return Err(AppError::ValidationError(format!(
+        "File cannot be larger than {} bytes",
+        self.config.max_upload_bytes
+      )));
+    }
+
+    if self.config.avatar_sizes.is_empty() {
+      return Err(AppError::InternalError(
+        "No avatar sizes configured".to_string(),
+      ));
+    }
+
+    let sizes = self.config.avatar_sizes.clone();
+    let renditions = tokio::task::spawn_blocking(move || render_avatars(&data, crop, &sizes))
+      .await
+      .map_err(|err| AppError::InternalError(err.to_string()))??;
+
+    let avatar_prefix = format!("avatars/{}/{}/", user_id, Uuid::new_v4());
+    let mut avatar_key = None;
+    for (size, format, data) in renditions {
+      let key = format!("{}{}.{}", avatar_prefix, size, imaging::extension(format));
+      self
+        .storage
+        .put(&key, data, imaging::mime_type(format))
+        .await?;
+      // Sizes are ascending, so the last one is the largest
+      avatar_key = Some(key);
+    }
+
+    let replaced = UsersRepository::replace_avatar(&self.db, user_id, avatar_key).await;
+    let (user, previous) = match replaced {
+      Ok(Some(replaced)) => replaced,
+      failed => {
+        self.delete_prefix(&avatar_prefix).await;
+        return Err(match failed {
+          Err(err) => AppError::from(err),
+          _ => AppError::NotFound("User not found".to_string()),
+        });
+      }
+    };
+
+    // The new avatar is committed; failing to clean up only leaves orphans
+    if let Some(previous) = previous
+      .as_deref()
+      .and_then(|key| avatar_prefix_of(key, user_id))
+    {
+      self.delete_prefix(&previous).await;
+    }
+
+    Ok(user)
+  }
+
+  async fn delete_prefix(&self, prefix: &str) {
+    match self.storage.list(prefix).await {
+      Ok(objects) => {
+        for object in objects {
+          if let Err(err) = self.storage.delete(&object.key).await {
+            tracing::warn!("Failed to delete avatar object {}: {}", object.key, err);
+          }
+        }
+      }
+      Err(err) => tracing::warn!("Failed to list avatar objects under {}: {}", prefix, err),
+    }
+  }
+}
+
+/// The `avatars/{user_id}/{avatar_id}/` prefix the renditions of a stored
+/// avatar key live under.
+fn avatar_prefix_of(key: &str, user_id: Uuid) -> Option<String> {
+  let user_prefix = format!("avatars/{}/", user_id);
+  let (avatar_id, _) = key.strip_prefix(&user_prefix)?.split_once('/')?;
+  Some(format!("{}{}/", user_prefix, avatar_id))
+}
+
+/// CPU bound part, run on the blocking pool.
+fn render_avatars(
+  data: &[u8],
+  crop: Option<CropRect>,
+  sizes: &[u32],
+) -> Result<Vec<(u32, ImageFormat, Bytes)>, AppError> {
+  let (image, format) = imaging::decode(data).map_err(|err| match err {
+    ImagingError::Unsupported => {
+      AppError::ValidationError("File must be a JPEG, PNG, GIF or WebP image".to_string())
+    }
+    other => AppError::ValidationError(other.to_string()),
+  })?;
+
+  let cropped = match crop {
+    Some(rect) => imaging::crop(&image, rect.x, rect.y, rect.width, rect.height)
+      .map_err(|err| AppError::ValidationError(err.to_string()))?,
+    None => image,
+  };
+
+  let output = match format {
+    ImageFormat::Gif => ImageFormat::Png,
+    other => other,
+  };
+
+  sizes
+    .iter()
+    .map(|&size| {
+      let square = imaging::resize(&cropped, Some(size), Some(size), Fit::Cover);
+      let data =
+        imaging::encode(&square, output).map_err(|err| AppError::InternalError(err.to_string()))?;
+      Ok((size, output, data))
+    })
+    .collect()
+}
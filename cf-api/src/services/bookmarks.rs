@@ -0,0 +1,219 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use cf_entity::entity::{bookmark_collections, bookmarks};
+use cf_repository::repositories::{
+  BookmarkCollectionsRepository, BookmarksRepository, PostsRepository,
+};
+use cf_repository::structs::pagination::OffsetPage;
+use sea_orm::{DatabaseConnection, IntoActiveModel, Set};
+use uuid::Uuid;
+
+use crate::config::custom_error::AppError;
+use crate::dto::bookmarks::{AddBookmarkRequest, BookmarkCollectionRequest, BookmarkSort};
+use crate::structs::bookmark::BookmarkResponse;
+
+#[derive(Clone)]
+pub struct BookmarkService {
+  db: Arc<DatabaseConnection>,
+}
+
+impl BookmarkService {
+  pub fn new(db: Arc<DatabaseConnection>) -> Self {
+    Self { db }
+  }
+
+  pub async fn get_bookmarks(
+    &self,
+    user_id: Uuid,
+    collection_id: Option<Uuid>,
+    sort: BookmarkSort,
+    page: u64,
+    per_page: u64,
+  ) -> Result<OffsetPage<BookmarkResponse>, AppError> {
+    if let Some(collection_id) = collection_id {
+      self.find_own_collection(user_id, collection_id).await?;
+    }
+
+    let mut result = BookmarksRepository::find_by_user(
+      &self.db,
+      user_id,
+      collection_id,
+      sort == BookmarkSort::Oldest,
+      page,
+      per_page,
+    )
+    .await
+    .map_err(AppError::from)?;
+
+    let items = std::mem::take(&mut result.items)
+      .into_iter()
+      .map(|(bookmark, post)| BookmarkResponse { bookmark, post })
+      .collect();
+    Ok(result.with_items(items))
+  }
+
+  /// Bookmark a post, or move an existing bookmark to another collection.
+  pub async fn add_bookmark(
+    &self,
+    user_id: Uuid,
+    post_id: Uuid,
+    req: AddBookmarkRequest,
+  ) -> Result<bookmarks::Model, AppError> {
+    PostsRepository::find_readable(&self.db, post_id, Some(user_id))
+      .await
+      .map_err(AppError::from)?
+      .ok_or_else(|| AppError::NotFound("Post not found".to_string()))?;
+
+    if let Some(collection_id) = req.collection_id {
+      self.find_own_collection(user_id, collection_id).await?;
+    }
+
+    let existing = BookmarksRepository::find_by_user_and_post(&self.db, user_id, post_id)
+      .await
+      .map_err(AppError::from)?;
+
+    match existing {
+      Some(bookmark) => {
+        let mut bookmark = bookmark.into_active_model();
+        bookmark.collection_id = Set(req.collection_id);
+        BookmarksRepository::update(&self.db, bookmark)
+          .await
+          .map_err(AppError::from)
+      }
+      None => {
+        let new_bookmark = bookmarks::ActiveModel {
+          id: Set(Uuid::new_v4()),
+          user_id: Set(user_id),
+          post_id: Set(post_id),
+          collection_id: Set(req.collection_id),
+          ..Default::default()
+        };
+        BookmarksRepository::create(&self.db, new_bookmark)
+          .await
+          .map_err(AppError::from)
+      }
+    }
+  }
+
+  pub async fn remove_bookmark(&self, user_id: Uuid, post_id: Uuid) -> Result<(), AppError> {
+    let result = BookmarksRepository::delete_by_user_and_post(&self.db, user_id, post_id)
+      .await
+      .map_err(AppError::from)?;
+
+    if result.rows_affected == 0 {
+      return Err(AppError::NotFound("Bookmark not found".to_string()));
+    }
+    Ok(())
+  }
+
+  /// Bookmark state of every requested post, keyed by post id.
+  pub async fn check_bookmarks(
+    &self,
+    user_id: Uuid,
+    post_ids: &[Uuid],
+  ) -> Result<HashMap<Uuid, bool>, AppError> {
+    let bookmarked = BookmarksRepository::find_bookmarked_post_ids(&self.db, user_id, post_ids)
+      .await
+      .map_err(AppError::from)?;
+
+    Ok(
+      post_ids
+        .iter()
+        .map(|id| (*id, bookmarked.contains(id)))
+        .collect(),
+    )
+  }
+
+  pub async fn get_collections(
+    &self,
+    user_id: Uuid,
+  ) -> Result<Vec<bookmark_collections::Model>, AppError> {
+    BookmarkCollectionsRepository::find_by_user(&self.db, user_id)
+      .await
+      .map_err(AppError::from)
+  }
+
+  pub async fn create_collection(
+    &self,
+    user_id: Uuid,
+    req: BookmarkCollectionRequest,
+  ) -> Result<bookmark_collections::Model, AppError> {
+    let name = req.name.trim().to_string();
+    self.ensure_name_free(user_id, &name, None).await?;
+
+    let new_collection = bookmark_collections::ActiveModel {
+      id: Set(Uuid::new_v4()),
+      user_id: Set(user_id),
+      name: Set(name),
+      ..Default::default()
+    };
+
+    BookmarkCollectionsRepository::create(&self.db, new_collection)
+      .await
+      .map_err(AppError::from)
+  }
+
+  pub async fn rename_collection(
+    &self,
+    user_id: Uuid,
+    id: Uuid,
+    req: BookmarkCollectionRequest,
+  ) -> Result<bookmark_collections::Model, AppError> {
+    let collection = self.find_own_collection(user_id, id).await?;
+    let name = req.name.trim().to_string();
+    self.ensure_name_free(user_id, &name, Some(id)).await?;
+
+    let mut collection_model = collection.into_active_model();
+    collection_model.name = Set(name);
+    collection_model.updated_at = Set(chrono::Utc::now().into());
+
+    BookmarkCollectionsRepository::update(&self.db, id, collection_model)
+      .await
+      .map_err(AppError::from)
+  }
+
+  pub async fn delete_collection(&self, user_id: Uuid, id: Uuid) -> Result<(), AppError> {
+    self.find_own_collection(user_id, id).await?;
+
+    BookmarkCollectionsRepository::delete(&self.db, id)
+      .await
+      .map(|_| ())
+      .map_err(AppError::from)
+  }
+
+  /// Collections of other users are reported as missing rather than forbidden.
+  async fn find_own_collection(
+    &self,
+    user_id: Uuid,
+    id: Uuid,
+  ) -> Result<bookmark_collections::Model, AppError> {
+    BookmarkCollectionsRepository::find_by_id(&self.db, id)
+      .await
+      .map_err(AppError::from)?
+      .filter(|collection| collection.user_id == user_id)
+      .ok_or_else(|| AppError::NotFound("Bookmark collection not found".to_string()))
+  }
+
+  async fn ensure_name_free(
+    &self,
+    user_id: Uuid,
+    name: &str,
+    except: Option<Uuid>,
+  ) -> Result<(), AppError> {
+    if name.is_empty() {
+      return Err(AppError::ValidationError("Name is required".to_string()));
+    }
+
+    let existing = BookmarkCollectionsRepository::find_by_user_and_name(&self.db, user_id, name)
+      .await
+      .map_err(AppError::from)?;
+
+    match existing {
+      Some(collection) if Some(collection.id) != except => Err(AppError::ValidationError(
+        "A collection with this name already exists".to_string(),
+      )),
+      _ => Ok(()),
+    }
+  }
+}
@@ -0,0 +1,230 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use cf_entity::entity::comments;
+use cf_entity::entity::sea_orm_active_enums::PostStatus;
+use cf_repository::repositories::{CommentsRepository, PostsRepository};
+use cf_util::sanitize::{SanitizePolicies, SanitizePolicy};
+use sea_orm::{DatabaseConnection, IntoActiveModel, Set};
+use uuid::Uuid;
+
+use crate::config::custom_error::AppError;
+use crate::config::sanitize::COMMENT_POLICY;
+use crate::dto::comments::{CreateCommentRequest, UpdateCommentRequest};
+use crate::structs::comment::CommentNode;
+
+/// Deepest reply level a comment may be created at; top level comments are depth 0.
+pub const MAX_COMMENT_DEPTH: i32 = 5;
+
+#[derive(Clone)]
+pub struct CommentService {
+  db: Arc<DatabaseConnection>,
+  policy: Arc<SanitizePolicy>,
+}
+
+impl CommentService {
+  pub fn new(db: Arc<DatabaseConnection>, policies: &SanitizePolicies) -> Self {
+    Self {
+      db,
+      policy: policies
+        .get(COMMENT_POLICY)
+        .unwrap_or_else(|| Arc::new(SanitizePolicy::strict())),
+    }
+  }
+
+  /// Comments of a post nested into threads, cut off below `max_depth`.
+  pub async fn get_comment_tree(
+    &self,
+    post_id: Uuid,
+    max_depth: Option<i32>,
+    viewer: Option<Uuid>,
+  ) -> Result<Vec<CommentNode>, AppError> {
+    self.ensure_post_readable(post_id, viewer).await?;
+
+    let max_depth = max_depth
+      .unwrap_or(MAX_COMMENT_DEPTH)
+      .clamp(0, MAX_COMMENT_DEPTH);
+    let comments = CommentsRepository::find_by_post(&self.db, post_id, max_depth)
+      .await
+      .map_err(AppError::from)?;
+
+    Ok(build_tree(comments))
+  }
+
+  /// Same thread order as the tree, flattened so each reply follows its parent.
+  pub async fn get_comment_list(
+    &self,
+    post_id: Uuid,
+    max_depth: Option<i32>,
+    viewer: Option<Uuid>,
+  ) -> Result<Vec<comments::Model>, AppError> {
+    let tree = self.get_comment_tree(post_id, max_depth, viewer).await?;
+
+    let mut flat = Vec::new();
+    flatten_tree(tree, &mut flat);
+    Ok(flat)
+  }
+
+  pub async fn create_comment(
+    &self,
+    user_id: Uuid,
+    post_id: Uuid,
+    req: CreateCommentRequest,
+  ) -> Result<comments::Model, AppError> {
+    let post = PostsRepository::find_readable(&self.db, post_id, Some(user_id))
+      .await
+      .map_err(AppError::from)?
+      .ok_or_else(|| AppError::NotFound("Post not found".to_string()))?;
+
+    if post.status != PostStatus::Published {
+      return Err(AppError::ValidationError(
+        "Comments are only allowed on published posts".to_string(),
+      ));
+    }
+
+    let depth = match req.parent_id {
+      Some(parent_id) => {
+        let parent = CommentsRepository::find_by_id(&self.db, parent_id)
+          .await
+          .map_err(AppError::from)?
+          .ok_or_else(|| AppError::NotFound("Parent comment not found".to_string()))?;
+
+        if parent.post_id != post_id {
+          return Err(AppError::ValidationError(
+            "Parent comment belongs to another post".to_string(),
+          ));
+        }
+        if parent.depth >= MAX_COMMENT_DEPTH {
+          return Err(AppError::ValidationError(format!(
+            "Replies cannot be nested deeper than {} levels",
+            MAX_COMMENT_DEPTH
+          )));
+        }
+
+        parent.depth + 1
+      }
+      None => 0,
+    };
+
+    let content = self.policy.clean(&req.content).html;
+    if content.trim().is_empty() {
+      return Err(AppError::ValidationError(
+        "Content is empty after sanitization".to_string(),
+      ));
+    }
+
+    let new_comment = comments::ActiveModel {
+      id: Set(Uuid::new_v4()),
+      post_id: Set(post_id),
+      user_id: Set(user_id),
+      parent_id: Set(req.parent_id),
+      depth: Set(depth),
+      content: Set(content),
+      ..Default::default()
+    };
+
+    CommentsRepository::create(&self.db, new_comment)
+      .await
+      .map_err(AppError::from)
+  }
+
+  pub async fn update_comment(
+    &self,
+    user_id: Uuid,
+    id: Uuid,
+    req: UpdateCommentRequest,
+  ) -> Result<comments::Model, AppError> {
+    let mut comment_model = self
+      .find_own_comment(user_id, id)
+      .await?
+      .into_active_model();
+
+    let content = self.policy.clean(&req.content).html;
+    if content.trim().is_empty() {
+      return Err(AppError::ValidationError(
+        "Content is empty after sanitization".to_string(),
+      ));
+    }
+
+    comment_model.content = Set(content);
+    comment_model.updated_at = Set(chrono::Utc::now().into());
+
+    CommentsRepository::update(&self.db, id, comment_model)
+      .await
+      .map_err(AppError::from)
+  }
+
+  pub async fn delete_comment(&self, user_id: Uuid, id: Uuid) -> Result<(), AppError> {
+    self.find_own_comment(user_id, id).await?;
+
+    let result = CommentsRepository::delete(&self.db, id)
+      .await
+      .map_err(AppError::from)?;
+
+    if result.rows_affected == 0 {
+      return Err(AppError::NotFound("Comment not found".to_string()));
+    }
+    Ok(())
+  }
+
+  /// Comments of other users are reported as not found, like missing ones.
+  async fn find_own_comment(&self, user_id: Uuid, id: Uuid) -> Result<comments::Model, AppError> {
+    CommentsRepository::find_by_id(&self.db, id)
+      .await
+      .map_err(AppError::from)?
+      .filter(|comment| comment.user_id == user_id)
+      .ok_or_else(|| AppError::NotFound("Comment not found".to_string()))
+  }
+
+  async fn ensure_post_readable(
+    &self,
+    post_id: Uuid,
+    viewer: Option<Uuid>,
+  ) -> Result<(), AppError> {
+    PostsRepository::find_readable(&self.db, post_id, viewer)
+      .await
+      .map_err(AppError::from)?
+      .ok_or_else(|| AppError::NotFound("Post not found".to_string()))
+      .map(|_| ())
+  }
+}
+
+/// Nest comments under their parents. Input must be ordered so parents come
+/// before replies, which creation order guarantees.
+fn build_tree(comments: Vec<comments::Model>) -> Vec<CommentNode> {
+  let mut roots = Vec::new();
+  let mut replies: HashMap<Uuid, Vec<comments::Model>> = HashMap::new();
+
+  for comment in comments {
+    match comment.parent_id {
+      Some(parent_id) => replies.entry(parent_id).or_default().push(comment),
+      None => roots.push(comment),
+    }
+  }
+
+  fn attach(
+    comment: comments::Model,
+    replies: &mut HashMap<Uuid, Vec<comments::Model>>,
+  ) -> CommentNode {
+    let children = replies.remove(&comment.id).unwrap_or_default();
+    CommentNode {
+      replies: children
+        .into_iter()
+        .map(|child| attach(child, replies))
+        .collect(),
+      comment,
+    }
+  }
+
+  roots
+    .into_iter()
+    .map(|root| attach(root, &mut replies))
+    .collect()
+}
+
+fn flatten_tree(nodes: Vec<CommentNode>, out: &mut Vec<comments::Model>) {
+  for node in nodes {
+    out.push(node.comment);
+    flatten_tree(node.replies, out);
+  }
+}
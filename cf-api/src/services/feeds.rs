@@ -0,0 +1,546 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use atom_syndication as atom;
+use cf_entity::entity::{posts, tags, users};
+use cf_repository::repositories::{PublicPostsRepository, TagsRepository, UsersRepository};
+use cf_util::sanitize::{SanitizePolicies, SanitizePolicy};
+use cf_util::tiptap;
+use chrono::{DateTime, FixedOffset, Utc};
+use rss::validation::Validate;
+use sea_orm::DatabaseConnection;
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+
+use crate::config::custom_error::AppError;
+use crate::config::sanitize::ARTICLE_POLICY;
+use crate::config::site::SiteConfig;
+use crate::dto::feeds::FeedFormat;
+
+/// Which posts a feed lists.
+pub enum FeedScope {
+  All,
+  Author(Uuid),
+  /// Tag slug
+  Tag(String),
+}
+
+/// A rendered feed with what conditional requests are checked against.
+pub struct RenderedFeed {
+  pub body: String,
+  pub content_type: &'static str,
+  /// Hex SHA-256 of the body
+  pub etag: String,
+  /// Latest `updated_at` of the listed posts, `None` for an empty feed
+  pub last_modified: Option<DateTime<Utc>>,
+}
+
+/// Feed level details, resolved from the scope.
+struct FeedInfo {
+  title: String,
+  description: String,
+  link: String,
+  /// Path under `/feeds/` without the extension
+  path: String,
+  author_id: Option<Uuid>,
+  tag_id: Option<Uuid>,
+}
+
+/// A post with everything an entry needs.
+struct FeedEntry {
+  post: posts::Model,
+  author: Option<users::Model>,
+  tags: Vec<tags::Model>,
+  body: EntryBody,
+}
+
+enum EntryBody {
+  /// Plain text
+  Excerpt(String),
+  /// The full post, sanitised
+  Html(String),
+}
+
+impl EntryBody {
+  fn as_str(&self) -> &str {
+    match self {
+      EntryBody::Excerpt(text) | EntryBody::Html(text) => text,
+    }
+  }
+}
+
+#[derive(Clone)]
+pub struct FeedService {
+  db: Arc<DatabaseConnection>,
+  site: SiteConfig,
+  policy: Arc<SanitizePolicy>,
+}
+
+impl FeedService {
+  pub fn new(db: Arc<DatabaseConnection>, site: SiteConfig, policies: &SanitizePolicies) -> Self {
+    Self {
+      db,
+      site,
+      policy: policies
+        .get(ARTICLE_POLICY)
+        .unwrap_or_else(|| Arc::new(SanitizePolicy::rich())),
+    }
+  }
+
+  /// Render the published posts in `scope` as RSS 2.0 or Atom.
+  pub async fn render(
+    &self,
+    scope: FeedScope,
+    format: FeedFormat,
+    full: bool,
+  ) -> Result<RenderedFeed, AppError> {
+    let info = self.feed_info(scope).await?;
+    let entries = self.load_entries(&info, full).await?;
+    let last_modified = entries.iter().map(|entry| entry.post.updated_at).max();
+
+    let (body, content_type) = match format {
+      FeedFormat::Rss => (
+        to_rss(&self.site, &info, &entries, last_modified)?,
+        "application/rss+xml; charset=utf-8",
+      ),
+      FeedFormat::Atom => {
+        let self_url = self.site.feed_url(&format!(
+          "{}.atom{}",
+          info.path,
+          if full { "?full=true" } else { "" }
+        ));
+        (
+          to_atom(&self.site, &info, &entries, last_modified, &self_url),
+          "application/atom+xml; charset=utf-8",
+        )
+      }
+    };
+
+    Ok(RenderedFeed {
+      etag: hex::encode(Sha256::digest(body.as_bytes())),
+      content_type,
+      last_modified: last_modified.map(|updated| updated.with_timezone(&Utc)),
+      body,
+    })
+  }
+
+  async fn feed_info(&self, scope: FeedScope) -> Result<FeedInfo, AppError> {
+    let info = match scope {
+      FeedScope::All => FeedInfo {
+        title: self.site.title.clone(),
+        description: self.site.description.clone(),
+        link: self.site.base_url.clone(),
+        path: "posts".to_string(),
+        author_id: None,
+        tag_id: None,
+      },
+      FeedScope::Author(user_id) => {
+        let user = UsersRepository::find_by_id(&self.db, user_id)
+          .await
+          .map_err(AppError::from)?
+          .ok_or_else(|| AppError::NotFound("User not found".to_string()))?;
+        FeedInfo {
+          title: format!("{} - {}", self.site.title, user.name),
+          description: format!("Posts by {}", user.name),
+          link: self.site.user_url(user.id),
+          path: format!("authors/{}", user.id),
+          author_id: Some(user.id),
+          tag_id: None,
+        }
+      }
+      FeedScope::Tag(slug) => {
+        let tag = TagsRepository::find_by_slug(&self.db, &slug)
+          .await
+          .map_err(AppError::from)?
+          .ok_or_else(|| AppError::NotFound("Tag not found".to_string()))?;
+        FeedInfo {
+          title: format!("{} - {}", self.site.title, tag.name),
+          description: format!("Posts tagged {}", tag.name),
+          link: format!("{}/tags/{}", self.site.base_url, tag.slug),
+          path: format!("tags/{}", tag.slug),
+          author_id: None,
+          tag_id: Some(tag.id),
+        }
+      }
+    };
+    Ok(info)
+  }
+
+  async fn load_entries(&self, info: &FeedInfo, full: bool) -> Result<Vec<FeedEntry>, AppError> {
+    let posts = PublicPostsRepository::find_latest(
+      &self.db,
+      info.author_id,
+      info.tag_id,
+      self.site.feed_item_limit,
+    )
+    .await
+    .map_err(AppError::from)?;
+
+    let ids: Vec<Uuid> = posts.iter().map(|post| post.id).collect();
+    let mut author_ids: Vec<Uuid> = posts.iter().map(|post| post.user_id).collect();
+    author_ids.sort_unstable();
+    author_ids.dedup();
+
+    let mut tags = TagsRepository::find_by_post_ids(&self.db, &ids)
+      .await
+      .map_err(AppError::from)?;
+    let authors: HashMap<Uuid, users::Model> = UsersRepository::find_by_ids(&self.db, &author_ids)
+      .await
+      .map_err(AppError::from)?
+      .into_iter()
+      .map(|user| (user.id, user))
+      .collect();
+
+    Ok(
+      posts
+        .into_iter()
+        .map(|post| FeedEntry {
+          body: self.entry_body(&post.content, full),
+          author: authors.get(&post.user_id).cloned(),
+          tags: tags.remove(&post.id).unwrap_or_default(),
+          post,
+        })
+        .collect(),
+    )
+  }
+
+  /// Excerpt or sanitised HTML of a post.
+  fn entry_body(&self, content: &str, full: bool) -> EntryBody {
+    let document = tiptap::parse_document(content);
+
+    if full {
+      EntryBody::Html(self.policy.clean(&tiptap::render_html(&document)).html)
+    } else {
+      EntryBody::Excerpt(tiptap::create_excerpt(
+        &document,
+        self.site.feed_excerpt_words,
+      ))
+    }
+  }
+}
+
+fn to_rss(
+  site: &SiteConfig,
+  info: &FeedInfo,
+  entries: &[FeedEntry],
+  last_modified: Option<DateTime<FixedOffset>>,
+) -> Result<String, AppError> {
+  let items = entries
+    .iter()
+    .map(|entry| {
+      let url = site.post_url(entry.post.id);
+      rss::ItemBuilder::default()
+        .title(Some(entry.post.title.clone()))
+        .link(Some(url.clone()))
+        .guid(Some(
+          rss::GuidBuilder::default()
+            .value(url)
+            .permalink(true)
+            .build(),
+        ))
+        .pub_date(Some(published_at(&entry.post).to_rfc2822()))
+        .description(Some(entry.body.as_str().to_string()))
+        .categories(
+          entry
+            .tags
+            .iter()
+            .map(|tag| {
+              rss::CategoryBuilder::default()
+                .name(tag.name.clone())
+                .build()
+            })
+            .collect::<Vec<_>>(),
+        )
+        .build()
+    })
+    .collect::<Vec<_>>();
+
+  let channel = rss::ChannelBuilder::default()
+    .title(info.title.clone())
+    .link(info.link.clone())
+    .description(info.description.clone())
+    .last_build_date(last_modified.map(|updated| updated.to_rfc2822()))
+    .items(items)
+    .build();
+
+  // Catch malformed dates or links before a reader does
+  channel
+    .validate()
+    .map_err(|err| AppError::InternalError(format!("Invalid RSS feed: {}", err)))?;
+  Ok(channel.to_string())
+}
+
+/// When a post went out, so drafts published long after they were started
+/// are dated by publication. `created_at` stands in when it is unset.
+fn published_at(post: &posts::Model) -> DateTime<FixedOffset> {
+  post.published_at.unwrap_or(post.created_at)
+}
+
+fn to_atom(
+  site: &SiteConfig,
+  info: &FeedInfo,
+  entries: &[FeedEntry],
+  last_modified: Option<DateTime<FixedOffset>>,
+  self_url: &str,
+) -> String {
+  let link = |href: String, rel: &str| {
+    atom::LinkBuilder::default()
+      .href(href)
+      .rel(rel.to_string())
+      .build()
+  };
+
+  let atom_entries = entries
+    .iter()
+    .map(|entry| {
+      let url = site.post_url(entry.post.id);
+      let mut builder = atom::EntryBuilder::default();
+      builder
+        .id(format!("urn:uuid:{}", entry.post.id))
+        .title(entry.post.title.clone())
+        .updated(entry.post.updated_at)
+        .published(Some(published_at(&entry.post)))
+        .links(vec![link(url, "alternate")])
+        .authors(
+          entry
+            .author
+            .iter()
+            .map(|author| {
+              atom::PersonBuilder::default()
+                .name(author.name.clone())
+                .uri(Some(site.user_url(author.id)))
+                .build()
+            })
+            .collect::<Vec<_>>(),
+        )
+        .categories(
+          entry
+            .tags
+            .iter()
+            .map(|tag| {
+              atom::CategoryBuilder::default()
+                .term(tag.slug.clone())
+                .label(Some(tag.name.clone()))
+                .build()
+            })
+            .collect::<Vec<_>>(),
+        );
+
+      match &entry.body {
+        EntryBody::Excerpt(text) => {
+          builder.summary(Some(atom::Text::plain(text.clone())));
+        }
+        EntryBody::Html(html) => {
+          builder.content(Some(
+            atom::ContentBuilder::default()
+              .value(Some(html.clone()))
+              .content_type(Some("html".to_string()))
+              .build(),
+          ));
+        }
+      }
+      builder.build()
+    })
+    .collect::<Vec<_>>();
+
+  atom::FeedBuilder::default()
+    .id(self_url.to_string())
+    .title(info.title.clone())
+    .subtitle(Some(atom::Text::plain(info.description.clone())))
+    // An empty feed has no date of its own; a fixed one keeps the ETag stable
+    .updated(last_modified.unwrap_or(DateTime::UNIX_EPOCH.fixed_offset()))
+    .links(vec![
+      link(self_url.to_string(), "self"),
+      link(info.link.clone(), "alternate"),
+    ])
+    .entries(atom_entries)
+    .build()
+    .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use cf_entity::entity::sea_orm_active_enums::{PostStatus, PostVisibility};
+  use std::time::Duration;
+
+  fn site() -> SiteConfig {
+    SiteConfig {
+      base_url: "https://example.com".to_string(),
+      title: "Example".to_string(),
+      description: "Latest posts".to_string(),
+      feed_item_limit: 20,
+      feed_excerpt_words: 50,
+      feed_base_url: "https://api.example.com".to_string(),
+      sitemap_base_url: "https://api.example.com".to_string(),
+      sitemap_refresh_interval: Duration::from_secs(3600),
+    }
+  }
+
+  fn info() -> FeedInfo {
+    FeedInfo {
+      title: "Example".to_string(),
+      description: "Latest posts".to_string(),
+      link: "https://example.com".to_string(),
+      path: "posts".to_string(),
+      author_id: None,
+      tag_id: None,
+    }
+  }
+
+  fn date(rfc3339: &str) -> DateTime<FixedOffset> {
+    DateTime::parse_from_rfc3339(rfc3339).unwrap()
+  }
+
+  fn entries() -> Vec<FeedEntry> {
+    let author = users::Model {
+      id: Uuid::from_u128(1),
+      name: "Ada <Admin>".to_string(),
+      email: "ada@example.com".to_string(),
+      avatar_key: None,
+      created_at: date("2024-01-01T00:00:00Z"),
+      updated_at: date("2024-01-01T00:00:00Z"),
+    };
+    let tag = tags::Model {
+      id: Uuid::from_u128(2),
+      name: "Rust & Web".to_string(),
+      slug: "rust-web".to_string(),
+      created_at: date("2024-01-01T00:00:00Z"),
+    };
+    let post = |id: u128, title: &str, published: Option<&str>, updated: &str| posts::Model {
+      id: Uuid::from_u128(id),
+      user_id: author.id,
+      title: title.to_string(),
+      content: String::new(),
+      status: PostStatus::Published,
+      visibility: PostVisibility::Public,
+      view_count: 0,
+      header_image_id: None,
+      meta_title: None,
+      meta_description: None,
+      canonical_url: None,
+      social_image_id: None,
+      published_at: published.map(date),
+      created_at: date("2024-01-15T08:00:00Z"),
+      updated_at: date(updated),
+    };
+
+    vec![
+      FeedEntry {
+        post: post(
+          10,
+          "First <post>",
+          Some("2024-02-01T09:30:00+07:00"),
+          "2024-02-02T10:00:00Z",
+        ),
+        author: Some(author.clone()),
+        tags: vec![tag],
+        body: EntryBody::Excerpt("An excerpt & more".to_string()),
+      },
+      FeedEntry {
+        post: post(11, "Second", None, "2024-02-03T10:00:00Z"),
+        author: None,
+        tags: Vec::new(),
+        body: EntryBody::Html("<p>Full <b>body</b></p>".to_string()),
+      },
+    ]
+  }
+
+  #[test]
+  fn rss_feed_is_valid() {
+    let entries = entries();
+    let last_modified = entries.iter().map(|entry| entry.post.updated_at).max();
+    let body = to_rss(&site(), &info(), &entries, last_modified).unwrap();
+
+    let channel = rss::Channel::read_from(body.as_bytes()).unwrap();
+    channel.validate().unwrap();
+    assert_eq!(channel.items().len(), 2);
+    assert_eq!(
+      channel.items()[0].link(),
+      Some(site().post_url(Uuid::from_u128(10)).as_str())
+    );
+    assert_eq!(channel.items()[0].title(), Some("First <post>"));
+  }
+
+  #[test]
+  fn feeds_date_entries_by_publication() {
+    let site = site();
+    let entries = entries();
+    let last_modified = entries.iter().map(|entry| entry.post.updated_at).max();
+
+    let rss = to_rss(&site, &info(), &entries, last_modified).unwrap();
+    let channel = rss::Channel::read_from(rss.as_bytes()).unwrap();
+    assert_eq!(
+      channel.items()[0].pub_date(),
+      Some(date("2024-02-01T09:30:00+07:00").to_rfc2822().as_str())
+    );
+    assert_eq!(
+      channel.items()[1].pub_date(),
+      Some(date("2024-01-15T08:00:00Z").to_rfc2822().as_str())
+    );
+
+    let atom = to_atom(&site, &info(), &entries, last_modified, "");
+    let feed = atom::Feed::read_from(atom.as_bytes()).unwrap();
+    assert_eq!(
+      feed.entries()[0].published(),
+      Some(&date("2024-02-01T09:30:00+07:00"))
+    );
+    assert_eq!(
+      feed.entries()[1].published(),
+      Some(&date("2024-01-15T08:00:00Z"))
+    );
+  }
+
+  #[test]
+  fn empty_rss_feed_is_valid() {
+    let body = to_rss(&site(), &info(), &[], None).unwrap();
+    rss::Channel::read_from(body.as_bytes())
+      .unwrap()
+      .validate()
+      .unwrap();
+  }
+
+  #[test]
+  fn atom_feed_is_valid_and_links_to_itself() {
+    let site = site();
+    let self_url = site.feed_url("posts.atom");
+    let entries = entries();
+    let last_modified = entries.iter().map(|entry| entry.post.updated_at).max();
+    let body = to_atom(&site, &info(), &entries, last_modified, &self_url);
+
+    let feed = atom::Feed::read_from(body.as_bytes()).unwrap();
+    assert_eq!(feed.id(), "https://api.example.com/feeds/posts.atom");
+    assert!(
+      feed
+        .links()
+        .iter()
+        .any(|link| link.rel() == "self" && link.href() == self_url)
+    );
+    assert_eq!(*feed.updated(), date("2024-02-03T10:00:00Z"));
+    assert_eq!(feed.entries().len(), 2);
+    for entry in feed.entries() {
+      assert!(entry.id().starts_with("urn:uuid:"));
+      assert!(entry.links().iter().any(|link| link.rel() == "alternate"));
+    }
+    assert_eq!(feed.entries()[0].title().as_str(), "First <post>");
+    assert_eq!(feed.entries()[0].authors()[0].name(), "Ada <Admin>");
+    assert_eq!(
+      feed.entries()[1]
+        .content()
+        .and_then(|content| content.value()),
+      Some("<p>Full <b>body</b></p>")
+    );
+  }
+
+  #[test]
+  fn empty_atom_feed_is_stable() {
+    let site = site();
+    let self_url = site.feed_url("posts.atom");
+    let first = to_atom(&site, &info(), &[], None, &self_url);
+    let second = to_atom(&site, &info(), &[], None, &self_url);
+
+    assert_eq!(first, second);
+    let feed = atom::Feed::read_from(first.as_bytes()).unwrap();
+    assert_eq!(*feed.updated(), DateTime::UNIX_EPOCH.fixed_offset());
+  }
+}
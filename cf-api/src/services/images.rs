@@ -0,0 +1,157 @@
+use std::sync::Arc;
+
+use bytes::Bytes;
+use cf_entity::entity::sea_orm_active_enums::MediaStatus;
+use cf_repository::repositories::MediaRepository;
+use cf_util::imaging::{self, Fit, ImageFormat};
+use cf_util::storage::{Storage, StorageError};
+use sea_orm::DatabaseConnection;
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+
+use crate::config::custom_error::AppError;
+use crate::config::media::MediaConfig;
+use crate::dto::images::{ImageFit, ImageOutputFormat, ImageParam};
+
+/// Bump when resizing or encoding changes so cached variants and ETags are
+/// not reused for different output.
+const VARIANT_VERSION: &str = "v1";
+
+/// A resolved `/img` request. The ETag only depends on the media id and the
+/// parameters, so conditional requests are answered without touching storage.
+pub struct ImageVariant {
+  pub etag: String,
+  pub content_type: &'static str,
+  source_key: String,
+  storage_key: String,
+  width: Option<u32>,
+  height: Option<u32>,
+  fit: Fit,
+  format: ImageFormat,
+}
+
+#[derive(Clone)]
+pub struct ImageService {
+  db: Arc<DatabaseConnection>,
+  storage: Arc<dyn Storage>,
+  config: MediaConfig,
+}
+
+impl ImageService {
+  pub fn new(db: Arc<DatabaseConnection>, storage: Arc<dyn Storage>, config: MediaConfig) -> Self {
+    Self {
+      db,
+      storage,
+      config,
+    }
+  }
+
+  /// Validate the parameters against the allow-list and work out which
+  /// object serves them. Only `READY` media is served: the original of
+  /// anything still processing may carry metadata.
+  pub async fn resolve(&self, media_id: Uuid, param: ImageParam) -> Result<ImageVariant, AppError> {
+    for size in [param.w, param.h].into_iter().flatten() {
+      if !self.config.variant_sizes.contains(&size) {
+        return Err(AppError::ValidationError(format!(
+          "Size {} is not allowed, use one of {:?}",
+          size, self.config.variant_sizes
+        )));
+      }
+    }
+
+    let media = MediaRepository::find_by_id(&self.db, media_id)
+      .await
+      .map_err(AppError::from)?
+      .filter(|media| media.status == MediaStatus::Ready)
+      .ok_or_else(|| AppError::NotFound("Image not found".to_string()))?;
+
+    let source = ImageFormat::from_mime_type(&media.mime_type)
+      .ok_or_else(|| AppError::NotFound("Image not found".to_string()))?;
+    let format = match param.format {
+      Some(ImageOutputFormat::Jpeg) => ImageFormat::Jpeg,
+      Some(ImageOutputFormat::Png) => ImageFormat::Png,
+      Some(ImageOutputFormat::Webp) => ImageFormat::WebP,
+      // GIFs are not re-encoded, their first frame becomes a PNG
+      None if source == ImageFormat::Gif && (param.w.is_some() || param.h.is_some()) => {
+        ImageFormat::Png
+      }
+      None => source,
+    };
+    let fit = match param.fit.unwrap_or_default() {
+      ImageFit::Cover => Fit::Cover,
+      ImageFit::Contain => Fit::Contain,
+      ImageFit::Fill => Fit::Fill,
+    };
+
+    let storage_key = if param.w.is_none() && param.h.is_none() && format == source {
+      media.storage_key.clone()
+    } else {
+      let fit_name = match (param.w, param.h, fit) {
+        (Some(_), Some(_), Fit::Cover) => "cover",
+        (Some(_), Some(_), Fit::Contain) => "contain",
+        (Some(_), Some(_), Fit::Fill) => "fill",
+        _ => "scale",
+      };
+      format!(
+        "variants/{}/{}/{}x{}-{}.{}",
+        media.id,
+        VARIANT_VERSION,
+        param.w.unwrap_or(0),
+        param.h.unwrap_or(0),
+        fit_name,
+        imaging::extension(format)
+      )
+    };
+
+    let mut hasher = Sha256::new();
+    hasher.update(VARIANT_VERSION.as_bytes());
+    hasher.update(storage_key.as_bytes());
+    let etag = hex::encode(hasher.finalize())[..32].to_string();
+
+    Ok(ImageVariant {
+      etag,
+      content_type: imaging::mime_type(format),
+      source_key: media.storage_key,
+      storage_key,
+      width: param.w,
+      height: param.h,
+      fit,
+      format,
+    })
+  }
+
+  /// Load the variant from storage, generating and storing it from the
+  /// original on first request.
+  pub async fn render(&self, variant: &ImageVariant) -> Result<Bytes, AppError> {
+    match self.storage.get(&variant.storage_key).await {
+      Ok(object) => return Ok(object.data),
+      Err(StorageError::NotFound(_)) => {}
+      Err(err) => return Err(err.into()),
+    }
+
+    let original = self.storage.get(&variant.source_key).await?.data;
+    let (width, height, fit, format) = (variant.width, variant.height, variant.fit, variant.format);
+    let data = tokio::task::spawn_blocking(move || {
+      let (image, _) = imaging::decode(&original)?;
+      imaging::encode(&imaging::resize(&image, width, height, fit), format)
+    })
+    .await
+    .map_err(|err| AppError::InternalError(err.to_string()))?
+    .map_err(|err| AppError::InternalError(err.to_string()))?;
+
+    // Serving the fresh variant matters more than caching it
+    if let Err(err) = self
+      .storage
+      .put(&variant.storage_key, data.clone(), variant.content_type)
+      .await
+    {
+      tracing::warn!(
+        "Failed to cache image variant {}: {}",
+        variant.storage_key,
+        err
+      );
+    }
+
+    Ok(data)
+  }
+}
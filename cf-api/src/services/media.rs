@@ -0,0 +1,449 @@
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+
+use bytes::Bytes;
+use cf_entity::entity::sea_orm_active_enums::MediaStatus;
+use cf_entity::entity::{media, media_uploads};
+use cf_repository::repositories::{
+  MediaRenditionsRepository, MediaRepository, MediaUploadsRepository,
+};
+use cf_repository::structs::media::MediaFilter;
+use cf_repository::structs::pagination::OffsetPage;
+use cf_util::imaging::{self, ImagingError};
+use cf_util::storage::Storage;
+use cf_util::urls::UrlResolver;
+use chrono::{NaiveDate, NaiveTime, Utc};
+use sea_orm::{DatabaseConnection, IntoActiveModel, Set, SqlErr, TransactionTrait};
+use uuid::Uuid;
+
+use crate::config::custom_error::AppError;
+use crate::config::media::MediaConfig;
+use crate::dto::media::{CreateUploadRequest, MediaListParam, UpdateMediaRequest};
+use crate::services::media_processing::MediaProcessor;
+use crate::structs::media::{MediaResponse, PresignedUpload, RenditionResponse};
+
+/// Accepted upload types and the extension their objects are stored with.
+pub const ALLOWED_IMAGE_TYPES: &[(&str, &str)] = &[
+  ("image/jpeg", "jpg"),
+  ("image/png", "png"),
+  ("image/gif", "gif"),
+  ("image/webp", "webp"),
+];
+
+#[derive(Clone)]
+pub struct MediaService {
+  db: Arc<DatabaseConnection>,
+  storage: Arc<dyn Storage>,
+  processor: MediaProcessor,
+  urls: UrlResolver,
+  config: MediaConfig,
+}
+
+impl MediaService {
+  pub fn new(
+    db: Arc<DatabaseConnection>,
+    storage: Arc<dyn Storage>,
+    processor: MediaProcessor,
+    urls: UrlResolver,
+    config: MediaConfig,
+  ) -> Self {
+    Self {
+      db,
+      storage,
+      processor,
+      urls,
+      config,
+    }
+  }
+
+  pub async fn get_media(&self, id: Uuid) -> Result<MediaResponse, AppError> {
+    let media = MediaRepository::find_by_id(&self.db, id)
+      .await
+      .map_err(AppError::from)?
+      .ok_or_else(|| AppError::NotFound("Media not found".to_string()))?;
+    self.to_response(media).await
+  }
+
+  pub async fn list_media(
+    &self,
+    param: MediaListParam,
+    page: u64,
+    per_page: u64,
+  ) -> Result<OffsetPage<MediaResponse>, AppError> {
+    if let (Some(from), Some(to)) = (param.from, param.to)
+      && from > to
+    {
+      return Err(AppError::ValidationError(
+        "from must not be after to".to_string(),
+      ));
+    }
+
+    let day_start = |day: NaiveDate| day.and_time(NaiveTime::MIN).and_utc().fixed_offset();
+    let filter = MediaFilter {
+      user_id: param.user_id,
+      mime_type: param.mime_type,
+      from: param.from.map(day_start),
+      to: param.to.and_then(|day| day.succ_opt()).map(day_start),
+    };
+
+    let mut result = MediaRepository::find_filtered(&self.db, filter, page, per_page)
+      .await
+      .map_err(AppError::from)?;
+    let items = self.to_responses(std::mem::take(&mut result.items)).await?;
+    Ok(result.with_items(items))
+  }
+
+  /// Edit the descriptive fields of the caller's own media.
+  pub async fn update_media(
+    &self,
+    user_id: Uuid,
+    id: Uuid,
+    req: UpdateMediaRequest,
+  ) -> Result<MediaResponse, AppError> {
+    let media = self.find_owned(user_id, id).await?;
+
+    let mut media_model = media.into_active_model();
+    if let Some(alt_text) = req.alt_text {
+      media_model.alt_text = Set(alt_text);
+    }
+    if let Some(caption) = req.caption {
+      media_model.caption = Set(caption);
+    }
+    if let Some(credit) = req.credit {
+      media_model.credit = Set(credit);
+    }
+
+    let media = MediaRepository::update(self.db.as_ref(), media_model)
+      .await
+      .map_err(AppError::from)?;
+    self.to_response(media).await
+  }
+
+  /// Delete the caller's own media along with its renditions and cached
+  /// `/img` variants. Refused while any post still uses it.
+  pub async fn delete_media(&self, user_id: Uuid, id: Uuid) -> Result<(), AppError> {
+    let media = self.find_owned(user_id, id).await?;
+
+    let references = MediaRepository::count_post_references(&self.db, &[id])
+      .await
+      .map_err(AppError::from)?
+      .remove(&id)
+      .unwrap_or_default();
+    if references > 0 {
+      return Err(AppError::ValidationError(format!(
+        "Media is used by {} post(s)",
+        references
+      )));
+    }
+
+    let mut keys: Vec<String> = MediaRenditionsRepository::find_by_media_ids(&self.db, &[id])
+      .await
+      .map_err(AppError::from)?
+      .into_iter()
+      .map(|rendition| rendition.storage_key)
+      .collect();
+    keys.push(media.storage_key);
+
+    // Rendition rows go with the media row through the foreign key cascade.
+    // A post may have started using it since the check above
+    MediaRepository::delete(&self.db, id)
+      .await
+      .map_err(|err| match err.sql_err() {
+        Some(SqlErr::ForeignKeyConstraintViolation(_)) => {
+          AppError::ValidationError("Media is used by a post".to_string())
+        }
+        _ => AppError::from(err),
+      })?;
+
+    // The row is gone, so leftover objects are only orphans; log and move on
+    match self.storage.list(&format!("variants/{}/", id)).await {
+      Ok(variants) => keys.extend(variants.into_iter().map(|object| object.key)),
+      Err(err) => tracing::warn!("Failed to list variants of media {}: {}", id, err),
+    }
+    for key in keys {
+      if let Err(err) = self.storage.delete(&key).await {
+        tracing::warn!("Failed to delete media object {}: {}", key, err);
+      }
+    }
+
+    Ok(())
+  }
+
+  /// Media of other users is reported as not found, like missing media.
+  async fn find_owned(&self, user_id: Uuid, id: Uuid) -> Result<media::Model, AppError> {
+    MediaRepository::find_by_id(&self.db, id)
+      .await
+      .map_err(AppError::from)?
+      .filter(|media| media.user_id == user_id)
+      .ok_or_else(|| AppError::NotFound("Media not found".to_string()))
+  }
+
+  /// Validate an uploaded image by its content, strip its metadata, store it
+  /// and queue its renditions. Whatever content type the client sent is
+  /// ignored.
+  pub async fn upload_image(&self, user_id: Uuid, data: Bytes) -> Result<MediaResponse, AppError> {
+    if data.is_empty() {
+      return Err(AppError::ValidationError("File is empty".to_string()));
+    }
+    if data.len() > self.config.max_upload_bytes {
+      return Err(AppError::ValidationError(format!(
+        "File cannot be larger than {} bytes",
+        self.config.max_upload_bytes
+      )));
+    }
+
+    let (data, format, width, height) = tokio::task::spawn_blocking(move || {
+      let (image, format) = imaging::decode(&data).map_err(|err| match err {
+        ImagingError::Unsupported => {
+          AppError::ValidationError("File must be a JPEG, PNG, GIF or WebP image".to_string())
+        }
+        other => AppError::ValidationError(other.to_string()),
+      })?;
+      let stripped = imaging::strip_metadata(data, &image, format).map_err(|err| match err {
+        ImagingError::Animated => AppError::ValidationError(err.to_string()),
+        other => AppError::InternalError(other.to_string()),
+      })?;
+      Ok::<_, AppError>((stripped, format, image.width(), image.height()))
+    })
+    .await
+    .map_err(|err| AppError::InternalError(err.to_string()))??;
+
+    let mime_type = imaging::mime_type(format);
+    let id = Uuid::new_v4();
+    let storage_key = format!("media/{}.{}", id, imaging::extension(format));
+    let size_bytes = data.len() as i64;
+    self.storage.put(&storage_key, data, mime_type).await?;
+
+    let new_media = media::ActiveModel {
+      id: Set(id),
+      user_id: Set(user_id),
+      storage_key: Set(storage_key),
+      mime_type: Set(mime_type.to_string()),
+      size_bytes: Set(size_bytes),
+      width: Set(Some(width as i32)),
+      height: Set(Some(height as i32)),
+      status: Set(MediaStatus::Processing),
+      ..Default::default()
+    };
+
+    let media = MediaRepository::create(self.db.as_ref(), new_media)
+      .await
+      .map_err(AppError::from)?;
+    self.processor.enqueue(media.id);
+    self.to_response(media).await
+  }
+
+  /// Hand out a presigned URL the client uploads to directly, scoped to a
+  /// key under the caller's own prefix.
+  pub async fn create_upload(
+    &self,
+    user_id: Uuid,
+    req: CreateUploadRequest,
+  ) -> Result<PresignedUpload, AppError> {
+    let (mime_type, extension) = allowed_image_type(&req.content_type)?;
+    if req.size_bytes > self.config.max_direct_upload_bytes {
+      return Err(AppError::ValidationError(format!(
+        "File cannot be larger than {} bytes",
+        self.config.max_direct_upload_bytes
+      )));
+    }
+
+    let id = Uuid::new_v4();
+    let storage_key = format!("uploads/{}/{}.{}", user_id, id, extension);
+    let url = self
+      .storage
+      .presign_put(
+        &storage_key,
+        mime_type,
+        Some(req.size_bytes),
+        self.config.presign_expiry,
+      )
+      .await?;
+    let expires_at = Utc::now()
+      + chrono::Duration::from_std(self.config.presign_expiry)
+        .map_err(|err| AppError::InternalError(err.to_string()))?;
+
+    let new_upload = media_uploads::ActiveModel {
+      id: Set(id),
+      user_id: Set(user_id),
+      storage_key: Set(storage_key.clone()),
+      mime_type: Set(mime_type.to_string()),
+      size_bytes: Set(req.size_bytes as i64),
+      expires_at: Set(expires_at.into()),
+      ..Default::default()
+    };
+    MediaUploadsRepository::create(&self.db, new_upload)
+      .await
+      .map_err(AppError::from)?;
+
+    Ok(PresignedUpload {
+      upload_id: id,
+      method: "PUT".to_string(),
+      url,
+      headers: BTreeMap::from([
+        ("Content-Type".to_string(), mime_type.to_string()),
+        ("Content-Length".to_string(), req.size_bytes.to_string()),
+      ]),
+      storage_key,
+      expires_at,
+    })
+  }
+
+  /// Check the uploaded object is really there with the signed size and its
+  /// bytes are an image of the signed type, then turn the pending upload into
+  /// a media row with the same id.
+  pub async fn complete_upload(
+    &self,
+    user_id: Uuid,
+    upload_id: Uuid,
+  ) -> Result<MediaResponse, AppError> {
+    let upload = MediaUploadsRepository::find_by_id(&self.db, upload_id)
+      .await
+      .map_err(AppError::from)?
+      .filter(|upload| upload.user_id == user_id)
+      .ok_or_else(|| AppError::NotFound("Upload not found".to_string()))?;
+
+    if upload.expires_at < Utc::now() {
+      return Err(AppError::ValidationError("Upload has expired".to_string()));
+    }
+
+    let object = self
+      .storage
+      .head(&upload.storage_key)
+      .await?
+      .ok_or_else(|| AppError::ValidationError("File has not been uploaded yet".to_string()))?;
+
+    if object.size != upload.size_bytes as u64 {
+      return Err(AppError::ValidationError(format!(
+        "Uploaded file is {} bytes, expected {}",
+        object.size, upload.size_bytes
+      )));
+    }
+    // The stored content type is whatever the client sent, so check the
+    // magic bytes instead
+    let stored = self.storage.get(&upload.storage_key).await?;
+    let sniffed = imaging::sniff_format(&stored.data).map(imaging::mime_type);
+    if sniffed != Some(upload.mime_type.as_str()) {
+      return Err(AppError::ValidationError(format!(
+        "Uploaded file is not a '{}' image",
+        upload.mime_type
+      )));
+    }
+
+    let new_media = media::ActiveModel {
+      id: Set(upload.id),
+      user_id: Set(upload.user_id),
+      storage_key: Set(upload.storage_key),
+      mime_type: Set(upload.mime_type),
+      size_bytes: Set(upload.size_bytes),
+      width: Set(None),
+      height: Set(None),
+      status: Set(MediaStatus::Processing),
+      ..Default::default()
+    };
+
+    let txn = self.db.begin().await.map_err(AppError::from)?;
+    let media = MediaRepository::create(&txn, new_media)
+      .await
+      .map_err(AppError::from)?;
+    MediaUploadsRepository::delete(&txn, upload.id)
+      .await
+      .map_err(AppError::from)?;
+    txn.commit().await.map_err(AppError::from)?;
+
+    self.processor.enqueue(media.id);
+    self.to_response(media).await
+  }
+
+  /// Delete objects and rows of uploads that expired without being completed.
+  /// Returns how many were removed. An upload whose object can't be deleted
+  /// keeps its row, so the next sweep tries it again.
+  pub async fn sweep_expired_uploads(&self) -> Result<u64, AppError> {
+    const BATCH_SIZE: u64 = 100;
+    let now = Utc::now().into();
+    let mut removed = 0;
+    // Uploads left behind by failed deletes stay at the front of the order
+    let mut skipped = 0;
+
+    loop {
+      let expired = MediaUploadsRepository::find_expired(&self.db, now, skipped, BATCH_SIZE)
+        .await
+        .map_err(AppError::from)?;
+      let done = (expired.len() as u64) < BATCH_SIZE;
+
+      for upload in expired {
+        if let Err(err) = self.storage.delete(&upload.storage_key).await {
+          tracing::warn!(
+            "Failed to delete expired upload {}: {}",
+            upload.storage_key,
+            err
+          );
+          skipped += 1;
+          continue;
+        }
+        MediaUploadsRepository::delete(self.db.as_ref(), upload.id)
+          .await
+          .map_err(AppError::from)?;
+        removed += 1;
+      }
+
+      if done {
+        return Ok(removed);
+      }
+    }
+  }
+
+  pub async fn to_response(&self, media: media::Model) -> Result<MediaResponse, AppError> {
+    let mut responses = self.to_responses(vec![media]).await?;
+    Ok(responses.remove(0))
+  }
+
+  /// Attach URLs and renditions, loading renditions for the whole batch at once.
+  pub async fn to_responses(
+    &self,
+    media: Vec<media::Model>,
+  ) -> Result<Vec<MediaResponse>, AppError> {
+    let ids: Vec<Uuid> = media.iter().map(|m| m.id).collect();
+    let mut references = MediaRepository::count_post_references(&self.db, &ids)
+      .await
+      .map_err(AppError::from)?;
+    let mut renditions: HashMap<Uuid, Vec<RenditionResponse>> = HashMap::new();
+    for rendition in MediaRenditionsRepository::find_by_media_ids(&self.db, &ids)
+      .await
+      .map_err(AppError::from)?
+    {
+      renditions
+        .entry(rendition.media_id)
+        .or_default()
+        .push(RenditionResponse {
+          url: self.urls.resolve(&rendition.storage_key).await?,
+          name: rendition.name,
+          mime_type: rendition.mime_type,
+          width: rendition.width,
+          height: rendition.height,
+          size_bytes: rendition.size_bytes,
+        });
+    }
+
+    let mut responses = Vec::with_capacity(media.len());
+    for media in media {
+      responses.push(MediaResponse {
+        url: self.urls.resolve(&media.storage_key).await?,
+        renditions: renditions.remove(&media.id).unwrap_or_default(),
+        reference_count: references.remove(&media.id).unwrap_or_default(),
+        media,
+      });
+    }
+    Ok(responses)
+  }
+}
+
+fn allowed_image_type(content_type: &str) -> Result<(&'static str, &'static str), AppError> {
+  ALLOWED_IMAGE_TYPES
+    .iter()
+    .find(|(mime_type, _)| *mime_type == content_type)
+    .copied()
+    .ok_or_else(|| {
+      AppError::ValidationError(format!("Unsupported content type '{}'", content_type))
+    })
+}
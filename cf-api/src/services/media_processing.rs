@@ -0,0 +1,224 @@
+use std::sync::Arc;
+
+use bytes::Bytes;
+use cf_entity::entity::sea_orm_active_enums::MediaStatus;
+use cf_entity::entity::{media, media_renditions};
+use cf_repository::repositories::{MediaRenditionsRepository, MediaRepository};
+use cf_util::imaging::{self, ImageFormat};
+use cf_util::storage::Storage;
+use sea_orm::{DatabaseConnection, IntoActiveModel, Set, TransactionTrait};
+use tokio::sync::mpsc;
+use uuid::Uuid;
+
+use crate::config::custom_error::AppError;
+use crate::config::media::RenditionSpec;
+
+/// Pending jobs kept in memory; media left `PROCESSING` by a full queue or a
+/// restart is picked up again on the next start.
+const QUEUE_CAPACITY: usize = 1024;
+
+struct EncodedRendition {
+  name: String,
+  format: ImageFormat,
+  data: Bytes,
+  width: u32,
+  height: u32,
+}
+
+/// Original after validation, plus everything derived from it.
+struct Processed {
+  format: ImageFormat,
+  /// Set when the stored original still had metadata and was re-encoded
+  stripped: Option<Bytes>,
+  width: u32,
+  height: u32,
+  renditions: Vec<EncodedRendition>,
+}
+
+/// Generates renditions for uploaded media on a background task.
+#[derive(Clone)]
+pub struct MediaProcessor {
+  sender: mpsc::Sender<Uuid>,
+}
+
+impl MediaProcessor {
+  /// Spawn the worker and requeue media a previous run did not finish.
+  /// Must be called from inside the runtime.
+  pub fn start(
+    db: Arc<DatabaseConnection>,
+    storage: Arc<dyn Storage>,
+    renditions: Vec<RenditionSpec>,
+  ) -> Self {
+    let (sender, receiver) = mpsc::channel(QUEUE_CAPACITY);
+    let processor = Self { sender };
+
+    tokio::spawn(run_worker(db.clone(), storage, renditions, receiver));
+
+    let requeue = processor.clone();
+    tokio::spawn(async move {
+      match MediaRepository::find_by_status(&db, MediaStatus::Processing).await {
+        Ok(pending) => pending.into_iter().for_each(|m| requeue.enqueue(m.id)),
+        Err(err) => tracing::error!("Failed to load unprocessed media: {}", err),
+      }
+    });
+
+    processor
+  }
+
+  pub fn enqueue(&self, media_id: Uuid) {
+    if self.sender.try_send(media_id).is_err() {
+      tracing::warn!("Media queue full, media {} stays unprocessed", media_id);
+    }
+  }
+}
+
+async fn run_worker(
+  db: Arc<DatabaseConnection>,
+  storage: Arc<dyn Storage>,
+  renditions: Vec<RenditionSpec>,
+  mut receiver: mpsc::Receiver<Uuid>,
+) {
+  let renditions = Arc::new(renditions);
+
+  while let Some(media_id) = receiver.recv().await {
+    let result = process_media(&db, storage.as_ref(), renditions.clone(), media_id).await;
+
+    if let Err(err) = result {
+      tracing::error!("Processing media {} failed: {}", media_id, err);
+      if let Ok(Some(media)) = MediaRepository::find_by_id(&db, media_id).await {
+        let mut media_model = media.into_active_model();
+        media_model.status = Set(MediaStatus::Failed);
+        let _ = MediaRepository::update(db.as_ref(), media_model).await;
+      }
+    }
+  }
+}
+
+async fn process_media(
+  db: &DatabaseConnection,
+  storage: &dyn Storage,
+  renditions: Arc<Vec<RenditionSpec>>,
+  media_id: Uuid,
+) -> Result<(), AppError> {
+  let Some(media) = MediaRepository::find_by_id(db, media_id)
+    .await
+    .map_err(AppError::from)?
+  else {
+    return Ok(());
+  };
+  if media.status != MediaStatus::Processing {
+    return Ok(());
+  }
+
+  let original = storage.get(&media.storage_key).await?.data;
+  // Direct uploads never went through validation, so they have no size yet
+  let needs_stripping = media.width.is_none();
+
+  let processed =
+    tokio::task::spawn_blocking(move || process_image(original, needs_stripping, &renditions))
+      .await
+      .map_err(|err| AppError::InternalError(err.to_string()))??;
+
+  if imaging::mime_type(processed.format) != media.mime_type {
+    return Err(AppError::ValidationError(format!(
+      "Content is {}, not {}",
+      imaging::mime_type(processed.format),
+      media.mime_type
+    )));
+  }
+
+  let mut size_bytes = media.size_bytes;
+  if let Some(stripped) = processed.stripped {
+    size_bytes = stripped.len() as i64;
+    storage
+      .put(&media.storage_key, stripped, &media.mime_type)
+      .await?;
+  }
+
+  let mut rows = Vec::with_capacity(processed.renditions.len());
+  for rendition in processed.renditions {
+    let storage_key = format!(
+      "renditions/{}/{}.{}",
+      media.id,
+      rendition.name,
+      imaging::extension(rendition.format)
+    );
+    let mime_type = imaging::mime_type(rendition.format);
+    let rendition_size = rendition.data.len() as i64;
+    storage.put(&storage_key, rendition.data, mime_type).await?;
+
+    rows.push(media_renditions::ActiveModel {
+      id: Set(Uuid::new_v4()),
+      media_id: Set(media.id),
+      name: Set(rendition.name),
+      storage_key: Set(storage_key),
+      mime_type: Set(mime_type.to_string()),
+      size_bytes: Set(rendition_size),
+      width: Set(rendition.width as i32),
+      height: Set(rendition.height as i32),
+      ..Default::default()
+    });
+  }
+
+  let mut media_model: media::ActiveModel = media.into_active_model();
+  media_model.size_bytes = Set(size_bytes);
+  media_model.width = Set(Some(processed.width as i32));
+  media_model.height = Set(Some(processed.height as i32));
+  media_model.status = Set(MediaStatus::Ready);
+
+  let txn = db.begin().await.map_err(AppError::from)?;
+  MediaRenditionsRepository::replace_for_media(&txn, media_id, rows)
+    .await
+    .map_err(AppError::from)?;
+  MediaRepository::update(&txn, media_model)
+    .await
+    .map_err(AppError::from)?;
+  txn.commit().await.map_err(AppError::from)
+}
+
+/// CPU bound part, run on the blocking pool.
+fn process_image(
+  original: Bytes,
+  strip: bool,
+  renditions: &[RenditionSpec],
+) -> Result<Processed, AppError> {
+  let (image, format) =
+    imaging::decode(&original).map_err(|err| AppError::ValidationError(err.to_string()))?;
+
+  let stripped = if strip {
+    let stripped = imaging::strip_metadata(original.clone(), &image, format)
+      .map_err(|err| AppError::InternalError(err.to_string()))?;
+    (stripped != original).then_some(stripped)
+  } else {
+    None
+  };
+
+  let renditions = renditions
+    .iter()
+    .map(|spec| {
+      let output = spec.format.unwrap_or(match format {
+        ImageFormat::Gif => ImageFormat::Png,
+        other => other,
+      });
+      let resized = imaging::resize_to_width(&image, spec.max_width);
+      let data = imaging::encode(&resized, output)
+        .map_err(|err| AppError::InternalError(err.to_string()))?;
+
+      Ok(EncodedRendition {
+        name: spec.name.clone(),
+        format: output,
+        data,
+        width: resized.width(),
+        height: resized.height(),
+      })
+    })
+    .collect::<Result<Vec<_>, AppError>>()?;
+
+  Ok(Processed {
+    format,
+    stripped,
+    width: image.width(),
+    height: image.height(),
+    renditions,
+  })
+}
@@ -1,2 +1,17 @@
+pub mod analytics;
+pub mod avatars;
+pub mod bookmarks;
+pub mod comments;
+pub mod feeds;
+pub mod images;
+pub mod media;
+pub mod media_processing;
+pub mod placements;
+pub mod post_meta;
 pub mod posts;
+pub mod previews;
+pub mod reactions;
+pub mod related_posts;
+pub mod sanitize;
+pub mod sitemaps;
 pub mod users;
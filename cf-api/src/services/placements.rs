@@ -0,0 +1,140 @@
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use cf_entity::entity::post_placements;
+use cf_entity::entity::sea_orm_active_enums::{PlacementKind, PostStatus};
+use cf_repository::repositories::{PostPlacementsRepository, PostsRepository};
+use chrono::Utc;
+use sea_orm::DatabaseConnection;
+use uuid::Uuid;
+
+use crate::config::custom_error::AppError;
+use crate::dto::placements::{PlacePostRequest, ReorderPlacementsRequest};
+
+/// Most active placements of one kind. Pins lead the first page of a list,
+/// so this also bounds how much of that page they take.
+pub const MAX_ACTIVE_PLACEMENTS: usize = 10;
+
+/// Editor curation: posts pinned to the top of lists and the featured
+/// carousel. Callers are checked by the handlers.
+#[derive(Clone)]
+pub struct PlacementService {
+  db: Arc<DatabaseConnection>,
+}
+
+impl PlacementService {
+  pub fn new(db: Arc<DatabaseConnection>) -> Self {
+    Self { db }
+  }
+
+  /// Active placements of `kind` in position order.
+  pub async fn list(&self, kind: PlacementKind) -> Result<Vec<post_placements::Model>, AppError> {
+    PostPlacementsRepository::find_active(self.db.as_ref(), kind)
+      .await
+      .map_err(AppError::from)
+  }
+
+  /// Pin or feature a published post, or move it and replace its expiry if
+  /// it already is.
+  pub async fn place(
+    &self,
+    admin_id: Uuid,
+    kind: PlacementKind,
+    post_id: Uuid,
+    req: PlacePostRequest,
+  ) -> Result<post_placements::Model, AppError> {
+    let post = PostsRepository::find_by_id(&self.db, post_id)
+      .await
+      .map_err(AppError::from)?
+      .ok_or_else(|| AppError::NotFound("Post not found".to_string()))?;
+    if post.status != PostStatus::Published {
+      return Err(AppError::ValidationError(
+        "Only published posts can be pinned or featured".to_string(),
+      ));
+    }
+    if req
+      .expires_at
+      .is_some_and(|expires_at| expires_at <= Utc::now())
+    {
+      return Err(AppError::ValidationError(
+        "Expiry must be in the future".to_string(),
+      ));
+    }
+
+    PostPlacementsRepository::place(
+      &self.db,
+      kind,
+      post_id,
+      req.position,
+      req.expires_at.map(Into::into),
+      admin_id,
+      MAX_ACTIVE_PLACEMENTS,
+    )
+    .await
+    .map_err(AppError::from)?
+    .ok_or_else(|| {
+      AppError::ValidationError(format!(
+        "At most {} posts can be {} at a time",
+        MAX_ACTIVE_PLACEMENTS,
+        kind_label(kind)
+      ))
+    })
+  }
+
+  pub async fn remove(&self, kind: PlacementKind, post_id: Uuid) -> Result<(), AppError> {
+    let result = PostPlacementsRepository::delete(&self.db, kind, post_id)
+      .await
+      .map_err(AppError::from)?;
+    if result.rows_affected == 0 {
+      return Err(AppError::NotFound(format!(
+        "Post is not {}",
+        kind_label(kind)
+      )));
+    }
+    Ok(())
+  }
+
+  /// Put the listed placements first in the order given. Returns the active
+  /// placements in their new order.
+  pub async fn reorder(
+    &self,
+    kind: PlacementKind,
+    req: ReorderPlacementsRequest,
+  ) -> Result<Vec<post_placements::Model>, AppError> {
+    let active: HashSet<Uuid> = PostPlacementsRepository::find_active(self.db.as_ref(), kind)
+      .await
+      .map_err(AppError::from)?
+      .into_iter()
+      .map(|placement| placement.post_id)
+      .collect();
+
+    let mut seen = HashSet::new();
+    for post_id in &req.post_ids {
+      if !seen.insert(*post_id) {
+        return Err(AppError::ValidationError(format!(
+          "Post {} is listed more than once",
+          post_id
+        )));
+      }
+      if !active.contains(post_id) {
+        return Err(AppError::ValidationError(format!(
+          "Post {} is not {}",
+          post_id,
+          kind_label(kind)
+        )));
+      }
+    }
+
+    PostPlacementsRepository::reorder(&self.db, kind, &req.post_ids)
+      .await
+      .map_err(AppError::from)?;
+    self.list(kind).await
+  }
+}
+
+fn kind_label(kind: PlacementKind) -> &'static str {
+  match kind {
+    PlacementKind::Pinned => "pinned",
+    PlacementKind::Featured => "featured",
+  }
+}
@@ -0,0 +1,165 @@
+use std::sync::Arc;
+
+use cf_entity::entity::posts;
+use cf_entity::entity::sea_orm_active_enums::MediaStatus;
+use cf_repository::repositories::{
+  MediaRepository, PostsRepository, TagsRepository, UsersRepository,
+};
+use cf_util::tiptap;
+use cf_util::urls::UrlResolver;
+use chrono::SecondsFormat;
+use sea_orm::DatabaseConnection;
+use serde_json::json;
+use uuid::Uuid;
+
+use crate::config::custom_error::AppError;
+use crate::config::site::SiteConfig;
+use crate::structs::post::{MetaTag, PostMeta};
+
+/// Words in the description generated when a post has no meta description.
+const DESCRIPTION_WORDS: usize = 30;
+
+fn tag(key: &str, content: impl Into<String>) -> MetaTag {
+  MetaTag {
+    key: key.to_string(),
+    content: content.into(),
+  }
+}
+
+#[derive(Clone)]
+pub struct PostMetaService {
+  db: Arc<DatabaseConnection>,
+  urls: UrlResolver,
+  site: SiteConfig,
+}
+
+impl PostMetaService {
+  pub fn new(db: Arc<DatabaseConnection>, urls: UrlResolver, site: SiteConfig) -> Self {
+    Self { db, urls, site }
+  }
+
+  /// Metadata of a post of `owner` in any state, so drafts can be previewed;
+  /// posts of other authors are reported as not found.
+  pub async fn get_post_meta(&self, owner: Uuid, id: Uuid) -> Result<PostMeta, AppError> {
+    let post = PostsRepository::find_by_id(&self.db, id)
+      .await
+      .map_err(AppError::from)?
+      .filter(|post| post.user_id == owner)
+      .ok_or_else(|| AppError::NotFound("Post not found".to_string()))?;
+    self.build(post).await
+  }
+
+  /// Metadata of a post `viewer` may read.
+  pub async fn get_public_post_meta(
+    &self,
+    id: Uuid,
+    viewer: Option<Uuid>,
+  ) -> Result<PostMeta, AppError> {
+    let post = PostsRepository::find_readable(&self.db, id, viewer)
+      .await
+      .map_err(AppError::from)?
+      .ok_or_else(|| AppError::NotFound("Post not found".to_string()))?;
+    self.build(post).await
+  }
+
+  /// SEO and link preview metadata, with unset fields falling back to the
+  /// title, an excerpt and the header image.
+  async fn build(&self, post: posts::Model) -> Result<PostMeta, AppError> {
+    let author = UsersRepository::find_by_id(&self.db, post.user_id)
+      .await
+      .map_err(AppError::from)?;
+    let tags = TagsRepository::find_by_post_ids(&self.db, &[post.id])
+      .await
+      .map_err(AppError::from)?
+      .remove(&post.id)
+      .unwrap_or_default();
+
+    let title = post.meta_title.clone().unwrap_or(post.title.clone());
+    let description = post.meta_description.clone().unwrap_or_else(|| {
+      tiptap::create_excerpt(&tiptap::parse_document(&post.content), DESCRIPTION_WORDS)
+    });
+    let url = self.site.post_url(post.id);
+    let canonical_url = post.canonical_url.clone().unwrap_or(url.clone());
+    let image_url = self
+      .image_url(post.social_image_id.or(post.header_image_id))
+      .await?;
+    let published = post.created_at.to_rfc3339_opts(SecondsFormat::Secs, true);
+    let modified = post.updated_at.to_rfc3339_opts(SecondsFormat::Secs, true);
+
+    let mut open_graph = vec![
+      tag("og:type", "article"),
+      tag("og:site_name", self.site.title.clone()),
+      tag("og:title", title.clone()),
+      tag("og:description", description.clone()),
+      tag("og:url", canonical_url.clone()),
+      tag("article:published_time", published.clone()),
+      tag("article:modified_time", modified.clone()),
+    ];
+    if let Some(author) = &author {
+      open_graph.push(tag("article:author", self.site.user_url(author.id)));
+    }
+    open_graph.extend(tags.iter().map(|t| tag("article:tag", t.name.clone())));
+
+    let mut twitter = vec![
+      tag(
+        "twitter:card",
+        if image_url.is_some() {
+          "summary_large_image"
+        } else {
+          "summary"
+        },
+      ),
+      tag("twitter:title", title.clone()),
+      tag("twitter:description", description.clone()),
+    ];
+    if let Some(image_url) = &image_url {
+      open_graph.push(tag("og:image", image_url.clone()));
+      twitter.push(tag("twitter:image", image_url.clone()));
+    }
+
+    let json_ld = json!({
+      "@context": "https://schema.org",
+      "@type": "Article",
+      "headline": title,
+      "description": description,
+      "url": url,
+      "mainEntityOfPage": { "@type": "WebPage", "@id": canonical_url },
+      "image": image_url.iter().collect::<Vec<_>>(),
+      "datePublished": published,
+      "dateModified": modified,
+      "author": author.as_ref().map(|author| json!({
+        "@type": "Person",
+        "name": author.name,
+        "url": self.site.user_url(author.id),
+      })),
+      "publisher": { "@type": "Organization", "name": self.site.title },
+      "keywords": tags.iter().map(|t| t.name.as_str()).collect::<Vec<_>>().join(", "),
+    });
+
+    Ok(PostMeta {
+      title,
+      description,
+      canonical_url,
+      image_url,
+      open_graph,
+      twitter,
+      json_ld,
+    })
+  }
+
+  /// URL of processed media; anything else has no usable image yet.
+  async fn image_url(&self, media_id: Option<Uuid>) -> Result<Option<String>, AppError> {
+    let Some(media_id) = media_id else {
+      return Ok(None);
+    };
+    let media = MediaRepository::find_by_id(&self.db, media_id)
+      .await
+      .map_err(AppError::from)?
+      .filter(|media| media.status == MediaStatus::Ready);
+
+    match media {
+      Some(media) => Ok(Some(self.urls.resolve(&media.storage_key).await?)),
+      None => Ok(None),
+    }
+  }
+}
@@ -1,64 +1,296 @@
 use crate::config::custom_error::AppError;
 use crate::dto::posts::{CreatePostRequest, UpdatePostRequest};
+use crate::services::reactions::ReactionService;
+use crate::services::related_posts::RelatedPostsCache;
+use crate::structs::post::{PostResponse, PublicPostResponse};
 use cf_entity::entity::posts;
-use cf_entity::entity::sea_orm_active_enums::PostStatus;
-use cf_repository::repositories::PostsRepository;
-use sea_orm::{DatabaseConnection, IntoActiveModel, Set};
+use cf_entity::entity::sea_orm_active_enums::{
+  PlacementKind, PostStatus, PostVisibility, TrendingPeriod,
+};
+use cf_repository::repositories::{
+  BookmarksRepository, CommentsRepository, MediaRepository, PostPlacementsRepository,
+  PostTrendingRepository, PostsRepository, PublicPostsRepository, ReactionTargetType,
+  TagsRepository,
+};
+use cf_repository::structs::pagination::{Cursor, CursorPage, OffsetPage, PinnedCursor};
+use cf_util::slug::slugify;
+use cf_util::tiptap;
+use sea_orm::{ConnectionTrait, DatabaseConnection, IntoActiveModel, Set, TransactionTrait};
 use std::sync::Arc;
 use uuid::Uuid;
 
+/// Longest tag name accepted.
+const MAX_TAG_LENGTH: usize = 50;
+/// Related posts computed and cached per post; requests take a prefix.
+const MAX_RELATED_POSTS: u64 = 20;
+
 #[derive(Clone)]
 pub struct PostService {
   db: Arc<DatabaseConnection>,
+  related: RelatedPostsCache,
 }
 
 impl PostService {
   pub fn new(db: Arc<DatabaseConnection>) -> Self {
-    Self { db }
+    Self {
+      db,
+      related: RelatedPostsCache::default(),
+    }
   }
 
+  /// Every post of `owner`, drafts and archived ones included.
   pub async fn get_all_posts(
     &self,
+    owner: Uuid,
     page: u64,
     per_page: u64,
-  ) -> Result<(Vec<posts::Model>, u64), AppError> {
-    PostsRepository::find_all(&self.db, page, per_page)
+  ) -> Result<OffsetPage<PostResponse>, AppError> {
+    let mut result =
+      PostsRepository::find_all(&self.db, PostsRepository::owned_by(owner), page, per_page)
+        .await
+        .map_err(AppError::from)?;
+
+    let items = self
+      .to_responses(std::mem::take(&mut result.items), Some(owner))
+      .await?;
+    Ok(result.with_items(items))
+  }
+
+  pub async fn get_posts_by_cursor(
+    &self,
+    owner: Uuid,
+    cursor: Option<&str>,
+    limit: u64,
+  ) -> Result<CursorPage<PostResponse>, AppError> {
+    let mut result = PostsRepository::find_all_by_cursor(
+      &self.db,
+      PostsRepository::owned_by(owner),
+      decode_pinned_cursor(cursor)?,
+      limit,
+    )
+    .await
+    .map_err(AppError::from)?;
+
+    let items = self
+      .to_responses(std::mem::take(&mut result.items), Some(owner))
+      .await?;
+    Ok(result.with_items(items))
+  }
+
+  /// A post of `owner` in any state; posts of other authors are reported as
+  /// not found.
+  pub async fn get_post(&self, owner: Uuid, id: Uuid) -> Result<PostResponse, AppError> {
+    let post = PostsRepository::find_by_id(&self.db, id)
       .await
-      .map_err(AppError::from)
+      .map_err(AppError::from)?
+      .filter(|post| post.user_id == owner)
+      .ok_or_else(|| AppError::NotFound("Post not found".to_string()))?;
+
+    let mut responses = self.to_responses(vec![post], Some(owner)).await?;
+    Ok(responses.remove(0))
   }
 
-  pub async fn get_post(&self, id: Uuid) -> Result<posts::Model, AppError> {
-    PostsRepository::find_by_id(&self.db, id)
+  /// Public posts related to one `viewer` may read, best first.
+  pub async fn get_related_posts(
+    &self,
+    id: Uuid,
+    limit: u64,
+    viewer: Option<Uuid>,
+  ) -> Result<Vec<PostResponse>, AppError> {
+    PostsRepository::find_readable(&self.db, id, viewer)
       .await
       .map_err(AppError::from)?
-      .ok_or_else(|| AppError::NotFound("Post not found".to_string()))
+      .ok_or_else(|| AppError::NotFound("Post not found".to_string()))?;
+
+    let ids = match self.related.get(id) {
+      Some(ids) => ids,
+      None => {
+        let ids = PublicPostsRepository::find_related_ids(&self.db, id, MAX_RELATED_POSTS)
+          .await
+          .map_err(AppError::from)?;
+        self.related.insert(id, ids.clone());
+        ids
+      }
+    };
+
+    let limit = (limit as usize).min(ids.len());
+    let posts = PublicPostsRepository::find_by_ids(&self.db, &ids[..limit])
+      .await
+      .map_err(AppError::from)?;
+    self.to_responses(posts, viewer).await
+  }
+
+  /// Posts ranked by the last trending refresh for `period`.
+  pub async fn get_trending_posts(
+    &self,
+    period: TrendingPeriod,
+    page: u64,
+    per_page: u64,
+    viewer: Option<Uuid>,
+  ) -> Result<OffsetPage<PostResponse>, AppError> {
+    let mut result = PostTrendingRepository::find_page(&self.db, period, page, per_page)
+      .await
+      .map_err(AppError::from)?;
+
+    let items = self
+      .to_responses(std::mem::take(&mut result.items), viewer)
+      .await?;
+    Ok(result.with_items(items))
+  }
+
+  /// The featured carousel, in the order editors set.
+  pub async fn get_featured_posts(
+    &self,
+    viewer: Option<Uuid>,
+  ) -> Result<Vec<PostResponse>, AppError> {
+    let posts = PostPlacementsRepository::find_posts(
+      &self.db,
+      PlacementKind::Featured,
+      PostsRepository::listed_for(viewer),
+    )
+    .await
+    .map_err(AppError::from)?;
+    self.to_responses(posts, viewer).await
   }
 
-  pub async fn create_post(&self, req: CreatePostRequest) -> Result<posts::Model, AppError> {
+  /// Home timeline of `viewer`, see [`PostsRepository::find_timeline_by_cursor`].
+  pub async fn get_timeline(
+    &self,
+    viewer: Uuid,
+    include_own: bool,
+    cursor: Option<&str>,
+    limit: u64,
+  ) -> Result<CursorPage<PostResponse>, AppError> {
+    let mut result = PostsRepository::find_timeline_by_cursor(
+      &self.db,
+      viewer,
+      include_own,
+      decode_cursor(cursor)?,
+      limit,
+    )
+    .await
+    .map_err(AppError::from)?;
+
+    let items = self
+      .to_responses(std::mem::take(&mut result.items), Some(viewer))
+      .await?;
+    Ok(result.with_items(items))
+  }
+
+  /// Published posts for anonymous readers.
+  pub async fn get_public_posts(
+    &self,
+    page: u64,
+    per_page: u64,
+  ) -> Result<OffsetPage<PublicPostResponse>, AppError> {
+    let mut result = PublicPostsRepository::find_all(&self.db, page, per_page)
+      .await
+      .map_err(AppError::from)?;
+
+    let items = self
+      .to_responses(std::mem::take(&mut result.items), None)
+      .await?;
+    Ok(result.with_items(items.into_iter().map(PublicPostResponse::from).collect()))
+  }
+
+  pub async fn get_public_posts_by_cursor(
+    &self,
+    cursor: Option<&str>,
+    limit: u64,
+  ) -> Result<CursorPage<PublicPostResponse>, AppError> {
+    let mut result =
+      PublicPostsRepository::find_all_by_cursor(&self.db, decode_pinned_cursor(cursor)?, limit)
+        .await
+        .map_err(AppError::from)?;
+
+    let items = self
+      .to_responses(std::mem::take(&mut result.items), None)
+      .await?;
+    Ok(result.with_items(items.into_iter().map(PublicPostResponse::from).collect()))
+  }
+
+  /// A published post `viewer` may read, unlisted ones included since they
+  /// are shared by link; anything else is reported as not found.
+  pub async fn get_public_post(
+    &self,
+    id: Uuid,
+    viewer: Option<Uuid>,
+  ) -> Result<PublicPostResponse, AppError> {
+    let post = PostsRepository::find_readable(&self.db, id, viewer)
+      .await
+      .map_err(AppError::from)?
+      .ok_or_else(|| AppError::NotFound("Post not found".to_string()))?;
+
+    let mut responses = self.to_responses(vec![post], viewer).await?;
+    Ok(responses.remove(0).into())
+  }
+
+  pub async fn create_post(
+    &self,
+    user_id: Uuid,
+    req: CreatePostRequest,
+  ) -> Result<posts::Model, AppError> {
+    for media_id in [req.header_image_id, req.social_image_id]
+      .into_iter()
+      .flatten()
+    {
+      self.ensure_image(user_id, media_id).await?;
+    }
+    let tags = normalize_tags(req.tags)?;
+
     let new_post = posts::ActiveModel {
       id: Set(Uuid::new_v4()),
-      user_id: Set(req.user_id),
+      user_id: Set(user_id),
       title: Set(req.title),
       content: Set(req.content),
       status: Set(PostStatus::Draft),
+      visibility: Set(req.visibility.unwrap_or(PostVisibility::Public)),
+      header_image_id: Set(req.header_image_id),
+      meta_title: Set(req.meta_title),
+      meta_description: Set(req.meta_description),
+      canonical_url: Set(req.canonical_url),
+      social_image_id: Set(req.social_image_id),
       ..Default::default()
     };
 
-    PostsRepository::create(&self.db, new_post)
+    let txn = self.db.begin().await.map_err(AppError::from)?;
+    let post = PostsRepository::create(&txn, new_post)
       .await
-      .map_err(AppError::from)
+      .map_err(AppError::from)?;
+    set_post_tags(&txn, post.id, tags).await?;
+    MediaRepository::set_embedded_in_post(
+      &txn,
+      post.id,
+      post.user_id,
+      &embedded_media_ids(&post.content),
+    )
+    .await
+    .map_err(AppError::from)?;
+    txn.commit().await.map_err(AppError::from)?;
+
+    Ok(post)
   }
 
   pub async fn update_post(
     &self,
+    user_id: Uuid,
     id: Uuid,
     req: UpdatePostRequest,
   ) -> Result<posts::Model, AppError> {
-    let mut post_model: posts::ActiveModel = PostsRepository::find_by_id(&self.db, id)
-      .await
-      .map_err(AppError::from)?
-      .ok_or_else(|| AppError::NotFound("Post not found".to_string()))?
-      .into_active_model();
+    let post = self.find_own_post(user_id, id).await?;
+
+    for media_id in [req.header_image_id, req.social_image_id]
+      .into_iter()
+      .flatten()
+      .flatten()
+    {
+      self.ensure_image(post.user_id, media_id).await?;
+    }
+    let tags = req.tags.map(normalize_tags).transpose()?;
+
+    let first_publish = post.published_at.is_none() && req.status == Some(PostStatus::Published);
+    let content_changed = req.content.is_some();
+    let mut post_model = post.into_active_model();
 
     if let Some(title) = req.title {
       post_model.title = Set(title);
@@ -72,17 +304,200 @@ impl PostService {
       post_model.status = Set(status);
     }
 
+    if first_publish {
+      post_model.published_at = Set(Some(chrono::Utc::now().into()));
+    }
+
+    if let Some(visibility) = req.visibility {
+      post_model.visibility = Set(visibility);
+    }
+
+    if let Some(header_image_id) = req.header_image_id {
+      post_model.header_image_id = Set(header_image_id);
+    }
+
+    if let Some(meta_title) = req.meta_title {
+      post_model.meta_title = Set(meta_title);
+    }
+
+    if let Some(meta_description) = req.meta_description {
+      post_model.meta_description = Set(meta_description);
+    }
+
+    if let Some(canonical_url) = req.canonical_url {
+      post_model.canonical_url = Set(canonical_url);
+    }
+
+    if let Some(social_image_id) = req.social_image_id {
+      post_model.social_image_id = Set(social_image_id);
+    }
+
     post_model.updated_at = Set(chrono::Utc::now().into());
 
-    PostsRepository::update(&self.db, id, post_model)
+    let txn = self.db.begin().await.map_err(AppError::from)?;
+    let post = PostsRepository::update(&txn, id, post_model)
       .await
-      .map_err(AppError::from)
+      .map_err(AppError::from)?;
+    if let Some(tags) = tags {
+      set_post_tags(&txn, id, tags).await?;
+    }
+    if content_changed {
+      MediaRepository::set_embedded_in_post(
+        &txn,
+        id,
+        post.user_id,
+        &embedded_media_ids(&post.content),
+      )
+      .await
+      .map_err(AppError::from)?;
+    }
+    txn.commit().await.map_err(AppError::from)?;
+
+    self.related.invalidate(id);
+
+    Ok(post)
   }
 
-  pub async fn delete_post(&self, id: Uuid) -> Result<(), AppError> {
+  pub async fn delete_post(&self, user_id: Uuid, id: Uuid) -> Result<(), AppError> {
+    self.find_own_post(user_id, id).await?;
     PostsRepository::delete(&self.db, id)
       .await
+      .map_err(AppError::from)?;
+    self.related.invalidate(id);
+    Ok(())
+  }
+
+  /// Posts of other users are reported as not found, as in `get_post`.
+  async fn find_own_post(&self, user_id: Uuid, id: Uuid) -> Result<posts::Model, AppError> {
+    PostsRepository::find_by_id(&self.db, id)
+      .await
+      .map_err(AppError::from)?
+      .filter(|post| post.user_id == user_id)
+      .ok_or_else(|| AppError::NotFound("Post not found".to_string()))
+  }
+
+  /// Header and social images must be media uploaded by the post author.
+  async fn ensure_image(&self, author_id: Uuid, media_id: Uuid) -> Result<(), AppError> {
+    MediaRepository::find_by_id(&self.db, media_id)
+      .await
+      .map_err(AppError::from)?
+      .filter(|media| media.user_id == author_id)
+      .ok_or_else(|| AppError::NotFound("Image not found".to_string()))
       .map(|_| ())
-      .map_err(AppError::from)
   }
+
+  /// Attach aggregates to posts with one query per aggregate for the whole batch.
+  async fn to_responses(
+    &self,
+    posts: Vec<posts::Model>,
+    viewer: Option<Uuid>,
+  ) -> Result<Vec<PostResponse>, AppError> {
+    let ids: Vec<Uuid> = posts.iter().map(|p| p.id).collect();
+    let mut tags = TagsRepository::find_by_post_ids(&self.db, &ids)
+      .await
+      .map_err(AppError::from)?;
+    let comment_counts = CommentsRepository::count_by_posts(&self.db, &ids)
+      .await
+      .map_err(AppError::from)?;
+    let reactions = ReactionService::new(self.db.clone())
+      .get_summaries(ReactionTargetType::Post, &ids, viewer)
+      .await?;
+    let bookmarked = match viewer {
+      Some(user_id) => BookmarksRepository::find_bookmarked_post_ids(&self.db, user_id, &ids)
+        .await
+        .map_err(AppError::from)?,
+      None => Default::default(),
+    };
+
+    Ok(
+      posts
+        .into_iter()
+        .zip(reactions)
+        .map(|(post, reactions)| PostResponse {
+          tags: tags.remove(&post.id).unwrap_or_default(),
+          comment_count: comment_counts.get(&post.id).copied().unwrap_or(0),
+          reactions,
+          bookmarked: bookmarked.contains(&post.id),
+          post,
+        })
+        .collect(),
+    )
+  }
+}
+
+fn decode_cursor(cursor: Option<&str>) -> Result<Option<Cursor>, AppError> {
+  cursor
+    .map(|c| {
+      Cursor::decode(c).ok_or_else(|| AppError::ValidationError("Invalid cursor".to_string()))
+    })
+    .transpose()
+}
+
+/// Like [`decode_cursor`] for listings that lead with their pins.
+fn decode_pinned_cursor(cursor: Option<&str>) -> Result<Option<PinnedCursor>, AppError> {
+  cursor
+    .map(|c| {
+      PinnedCursor::decode(c).ok_or_else(|| AppError::ValidationError("Invalid cursor".to_string()))
+    })
+    .transpose()
+}
+
+/// Trim, slug and deduplicate tag names into `(slug, name)` pairs.
+fn normalize_tags(names: Vec<String>) -> Result<Vec<(String, String)>, AppError> {
+  let mut tags: Vec<(String, String)> = Vec::with_capacity(names.len());
+  for name in names {
+    let name = name.trim().to_string();
+    if name.chars().count() > MAX_TAG_LENGTH {
+      return Err(AppError::ValidationError(format!(
+        "Tags cannot be longer than {} characters",
+        MAX_TAG_LENGTH
+      )));
+    }
+    let slug = slugify(&name);
+    if slug.is_empty() {
+      return Err(AppError::ValidationError(format!(
+        "Tag '{}' must contain letters or digits",
+        name
+      )));
+    }
+    if !tags.iter().any(|(existing, _)| *existing == slug) {
+      tags.push((slug, name));
+    }
+  }
+  Ok(tags)
+}
+
+async fn set_post_tags<C: ConnectionTrait>(
+  db: &C,
+  post_id: Uuid,
+  tags: Vec<(String, String)>,
+) -> Result<(), AppError> {
+  let tag_ids: Vec<Uuid> = TagsRepository::find_or_create(db, tags)
+    .await
+    .map_err(AppError::from)?
+    .into_iter()
+    .map(|tag| tag.id)
+    .collect();
+  TagsRepository::set_for_post(db, post_id, &tag_ids)
+    .await
+    .map_err(AppError::from)
+}
+
+/// Ids of the media embedded as images in post content. Image URLs carry the
+/// media id in their path (`media/{id}.png`, `renditions/{id}/...`,
+/// `/img/{id}`); ids that aren't media are dropped when the links are stored.
+fn embedded_media_ids(content: &str) -> Vec<Uuid> {
+  let mut ids = Vec::new();
+  for src in tiptap::image_sources(&tiptap::parse_document(content)) {
+    let path = src.split(['?', '#']).next().unwrap_or_default();
+    for segment in path.split('/') {
+      let stem = segment.split('.').next().unwrap_or_default();
+      if let Ok(id) = Uuid::parse_str(stem)
+        && !ids.contains(&id)
+      {
+        ids.push(id);
+      }
+    }
+  }
+  ids
 }
@@ -0,0 +1,191 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use cf_entity::entity::{post_previews, posts};
+use cf_repository::repositories::{PostPreviewsRepository, PostsRepository};
+use cf_util::sanitize::{SanitizePolicies, SanitizePolicy};
+use cf_util::tiptap;
+use chrono::Utc;
+use hmac::{Hmac, Mac};
+use sea_orm::{DatabaseConnection, Set};
+use sha2::Sha256;
+use uuid::Uuid;
+
+use crate::config::custom_error::AppError;
+use crate::config::preview::PreviewConfig;
+use crate::config::sanitize::ARTICLE_POLICY;
+use crate::config::site::SiteConfig;
+use crate::dto::previews::CreatePreviewRequest;
+use crate::structs::preview::{DraftPreview, PreviewLinkResponse};
+
+/// Signed, expiring links that show a post as it was when the link was made,
+/// published or not. A token is `{link id}.{signature}`; the signature covers
+/// the link id, post and expiry, so tokens cannot be guessed from ids.
+#[derive(Clone)]
+pub struct PreviewService {
+  db: Arc<DatabaseConnection>,
+  config: PreviewConfig,
+  site: SiteConfig,
+  policy: Arc<SanitizePolicy>,
+}
+
+impl PreviewService {
+  pub fn new(
+    db: Arc<DatabaseConnection>,
+    config: PreviewConfig,
+    site: SiteConfig,
+    policies: &SanitizePolicies,
+  ) -> Self {
+    Self {
+      db,
+      config,
+      site,
+      policy: policies
+        .get(ARTICLE_POLICY)
+        .unwrap_or_else(|| Arc::new(SanitizePolicy::rich())),
+    }
+  }
+
+  /// Link to the current revision of a post, for its author only.
+  pub async fn create_link(
+    &self,
+    user_id: Uuid,
+    post_id: Uuid,
+    req: CreatePreviewRequest,
+  ) -> Result<PreviewLinkResponse, AppError> {
+    let post = self.find_own_post(user_id, post_id).await?;
+
+    let ttl = match req.expires_in_secs {
+      Some(secs) if secs == 0 || secs > self.config.max_ttl.as_secs() => {
+        return Err(AppError::ValidationError(format!(
+          "Expiry must be between 1 and {} seconds",
+          self.config.max_ttl.as_secs()
+        )));
+      }
+      Some(secs) => Duration::from_secs(secs),
+      None => self.config.default_ttl,
+    };
+    let expires_at = Utc::now()
+      + chrono::Duration::from_std(ttl).map_err(|err| AppError::InternalError(err.to_string()))?;
+
+    let link = PostPreviewsRepository::create(
+      &self.db,
+      post_previews::ActiveModel {
+        id: Set(Uuid::new_v4()),
+        post_id: Set(post.id),
+        created_by: Set(user_id),
+        title: Set(post.title),
+        content: Set(post.content),
+        revision: Set(post.updated_at),
+        expires_at: Set(expires_at.into()),
+        ..Default::default()
+      },
+    )
+    .await
+    .map_err(AppError::from)?;
+
+    Ok(self.to_response(link))
+  }
+
+  /// Links of a post that are neither revoked nor expired.
+  pub async fn list_links(
+    &self,
+    user_id: Uuid,
+    post_id: Uuid,
+  ) -> Result<Vec<PreviewLinkResponse>, AppError> {
+    self.find_own_post(user_id, post_id).await?;
+
+    let links = PostPreviewsRepository::find_active_by_post(&self.db, post_id, Utc::now().into())
+      .await
+      .map_err(AppError::from)?;
+    Ok(
+      links
+        .into_iter()
+        .map(|link| self.to_response(link))
+        .collect(),
+    )
+  }
+
+  pub async fn revoke_link(
+    &self,
+    user_id: Uuid,
+    post_id: Uuid,
+    link_id: Uuid,
+  ) -> Result<(), AppError> {
+    self.find_own_post(user_id, post_id).await?;
+
+    let result = PostPreviewsRepository::revoke(&self.db, link_id, post_id, Utc::now().into())
+      .await
+      .map_err(AppError::from)?;
+    if result.rows_affected == 0 {
+      return Err(AppError::NotFound("Preview link not found".to_string()));
+    }
+    Ok(())
+  }
+
+  /// The draft behind a token. Forged, revoked and expired tokens, and
+  /// tokens of deleted posts, are all reported as not found.
+  pub async fn get_preview(&self, token: &str) -> Result<DraftPreview, AppError> {
+    let not_found = || AppError::NotFound("Preview not found".to_string());
+
+    let (id, signature) = token.split_once('.').ok_or_else(not_found)?;
+    let id = Uuid::try_parse(id).map_err(|_| not_found())?;
+    let signature = hex::decode(signature).map_err(|_| not_found())?;
+
+    let link = PostPreviewsRepository::find_by_id(&self.db, id)
+      .await
+      .map_err(AppError::from)?
+      .ok_or_else(not_found)?;
+    self
+      .mac(&link)
+      .verify_slice(&signature)
+      .map_err(|_| not_found())?;
+    if link.revoked_at.is_some() || link.expires_at <= Utc::now() {
+      return Err(not_found());
+    }
+
+    let document = tiptap::parse_document(&link.content);
+    Ok(DraftPreview {
+      post_id: link.post_id,
+      title: link.title,
+      html: self.policy.clean(&tiptap::render_html(&document)).html,
+      revision: link.revision,
+      expires_at: link.expires_at,
+    })
+  }
+
+  /// Posts of other users are reported as not found, like missing ones.
+  async fn find_own_post(&self, user_id: Uuid, post_id: Uuid) -> Result<posts::Model, AppError> {
+    PostsRepository::find_by_id(&self.db, post_id)
+      .await
+      .map_err(AppError::from)?
+      .filter(|post| post.user_id == user_id)
+      .ok_or_else(|| AppError::NotFound("Post not found".to_string()))
+  }
+
+  fn mac(&self, link: &post_previews::Model) -> Hmac<Sha256> {
+    let mut mac =
+      Hmac::<Sha256>::new_from_slice(&self.config.secret).expect("HMAC accepts any key length");
+    mac.update(link.id.as_bytes());
+    mac.update(link.post_id.as_bytes());
+    mac.update(&link.expires_at.timestamp().to_be_bytes());
+    mac
+  }
+
+  fn to_response(&self, link: post_previews::Model) -> PreviewLinkResponse {
+    let token = format!(
+      "{}.{}",
+      link.id.simple(),
+      hex::encode(self.mac(&link).finalize().into_bytes())
+    );
+    PreviewLinkResponse {
+      id: link.id,
+      post_id: link.post_id,
+      url: self.site.preview_url(&token),
+      token,
+      revision: link.revision,
+      expires_at: link.expires_at,
+      created_at: link.created_at,
+    }
+  }
+}
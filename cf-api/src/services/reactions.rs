@@ -0,0 +1,142 @@
+use std::sync::Arc;
+
+use cf_entity::entity::posts;
+use cf_entity::entity::sea_orm_active_enums::{PostStatus, ReactionKind};
+use cf_repository::repositories::{
+  CommentsRepository, PostsRepository, ReactionTarget, ReactionTargetType, ReactionsRepository,
+};
+use sea_orm::DatabaseConnection;
+use uuid::Uuid;
+
+use crate::config::custom_error::AppError;
+use crate::structs::reaction::ReactionSummary;
+
+#[derive(Clone)]
+pub struct ReactionService {
+  db: Arc<DatabaseConnection>,
+}
+
+impl ReactionService {
+  pub fn new(db: Arc<DatabaseConnection>) -> Self {
+    Self { db }
+  }
+
+  /// Add, switch or remove the caller's reaction and return the new totals.
+  pub async fn toggle_reaction(
+    &self,
+    user_id: Uuid,
+    target: ReactionTarget,
+    kind: ReactionKind,
+  ) -> Result<ReactionSummary, AppError> {
+    self.ensure_target_reactable(user_id, target).await?;
+
+    let existing = ReactionsRepository::find_by_user(&self.db, user_id, target)
+      .await
+      .map_err(AppError::from)?;
+
+    match existing {
+      Some(reaction) if reaction.kind == kind => {
+        ReactionsRepository::delete(&self.db, reaction.id)
+          .await
+          .map_err(AppError::from)?;
+      }
+      Some(reaction) => {
+        ReactionsRepository::update_kind(&self.db, reaction.id, kind)
+          .await
+          .map_err(AppError::from)?;
+      }
+      None => {
+        ReactionsRepository::create(&self.db, user_id, target, kind)
+          .await
+          .map_err(AppError::from)?;
+      }
+    }
+
+    self.get_summary(target, Some(user_id)).await
+  }
+
+  /// Totals of a post or comment the viewer may read.
+  pub async fn get_summary(
+    &self,
+    target: ReactionTarget,
+    viewer: Option<Uuid>,
+  ) -> Result<ReactionSummary, AppError> {
+    self.find_readable_post(target, viewer).await?;
+
+    let mut summaries = self
+      .get_summaries(target.target_type, &[target.id], viewer)
+      .await?;
+    Ok(summaries.remove(0))
+  }
+
+  /// Summaries for many targets of one type, in the order of `ids`, using
+  /// one count query plus one query for the viewer's own reactions.
+  pub async fn get_summaries(
+    &self,
+    target_type: ReactionTargetType,
+    ids: &[Uuid],
+    viewer: Option<Uuid>,
+  ) -> Result<Vec<ReactionSummary>, AppError> {
+    let mut counts = ReactionsRepository::count_by_targets(&self.db, target_type, ids)
+      .await
+      .map_err(AppError::from)?;
+
+    let mine = match viewer {
+      Some(user_id) => ReactionsRepository::find_user_kinds(&self.db, user_id, target_type, ids)
+        .await
+        .map_err(AppError::from)?,
+      None => Default::default(),
+    };
+
+    Ok(
+      ids
+        .iter()
+        .map(|id| {
+          ReactionSummary::new(counts.remove(id).unwrap_or_default(), mine.get(id).copied())
+        })
+        .collect(),
+    )
+  }
+
+  /// The post of a target, or of the comment it is, if `viewer` may read it.
+  async fn find_readable_post(
+    &self,
+    target: ReactionTarget,
+    viewer: Option<Uuid>,
+  ) -> Result<posts::Model, AppError> {
+    let post_id = match target.target_type {
+      ReactionTargetType::Post => target.id,
+      ReactionTargetType::Comment => {
+        CommentsRepository::find_by_id(&self.db, target.id)
+          .await
+          .map_err(AppError::from)?
+          .ok_or_else(|| AppError::NotFound("Comment not found".to_string()))?
+          .post_id
+      }
+    };
+
+    let not_found = match target.target_type {
+      ReactionTargetType::Post => "Post not found",
+      ReactionTargetType::Comment => "Comment not found",
+    };
+    PostsRepository::find_readable(&self.db, post_id, viewer)
+      .await
+      .map_err(AppError::from)?
+      .ok_or_else(|| AppError::NotFound(not_found.to_string()))
+  }
+
+  async fn ensure_target_reactable(
+    &self,
+    user_id: Uuid,
+    target: ReactionTarget,
+  ) -> Result<(), AppError> {
+    let post = self.find_readable_post(target, Some(user_id)).await?;
+
+    if target.target_type == ReactionTargetType::Post && post.status != PostStatus::Published {
+      return Err(AppError::ValidationError(
+        "Reactions are only allowed on published posts".to_string(),
+      ));
+    }
+    Ok(())
+  }
+}
@@ -0,0 +1,55 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use uuid::Uuid;
+
+/// How long a computed list is reused. Edits to the post itself or to a
+/// post it lists drop it right away; a post that becomes related through an
+/// edit shows up once the list expires.
+const TTL: Duration = Duration::from_secs(600);
+/// Posts whose lists are kept at once.
+const CAPACITY: usize = 10_000;
+
+/// When a list was computed and the ids in it.
+type Entry = (Instant, Vec<Uuid>);
+
+/// Ids of the related posts of each post, best first. Only ids are kept so
+/// per-viewer details and counts are always loaded fresh.
+///
+/// The cache lives in the process, so invalidation only reaches the replica
+/// that handled the edit; other replicas serve their lists until `TTL`.
+#[derive(Clone, Default)]
+pub struct RelatedPostsCache {
+  entries: Arc<Mutex<HashMap<Uuid, Entry>>>,
+}
+
+impl RelatedPostsCache {
+  pub fn get(&self, post_id: Uuid) -> Option<Vec<Uuid>> {
+    let entries = self.entries.lock().unwrap_or_else(|err| err.into_inner());
+    entries
+      .get(&post_id)
+      .filter(|(computed_at, _)| computed_at.elapsed() < TTL)
+      .map(|(_, ids)| ids.clone())
+  }
+
+  pub fn insert(&self, post_id: Uuid, ids: Vec<Uuid>) {
+    let mut entries = self.entries.lock().unwrap_or_else(|err| err.into_inner());
+    if entries.len() >= CAPACITY {
+      entries.retain(|_, (computed_at, _)| computed_at.elapsed() < TTL);
+      if entries.len() >= CAPACITY {
+        entries.clear();
+      }
+    }
+    entries.insert(post_id, (Instant::now(), ids));
+  }
+
+  /// Drops the list of `post_id` and every list that contains it.
+  pub fn invalidate(&self, post_id: Uuid) {
+    self
+      .entries
+      .lock()
+      .unwrap_or_else(|err| err.into_inner())
+      .retain(|id, (_, ids)| *id != post_id && !ids.contains(&post_id));
+  }
+}
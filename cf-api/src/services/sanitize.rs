@@ -0,0 +1,27 @@
+use cf_util::sanitize::SanitizePolicies;
+
+use crate::config::custom_error::AppError;
+use crate::structs::sanitize::SanitizePreview;
+
+#[derive(Clone)]
+pub struct SanitizeService {
+  policies: SanitizePolicies,
+}
+
+impl SanitizeService {
+  pub fn new(policies: SanitizePolicies) -> Self {
+    Self { policies }
+  }
+
+  /// Clean `html` with the named policy without storing anything.
+  pub fn preview(&self, policy: &str, html: &str) -> Result<SanitizePreview, AppError> {
+    let policy = self.policies.get(policy).ok_or_else(|| {
+      AppError::NotFound(format!(
+        "Unknown policy '{}', use one of {}",
+        policy,
+        self.policies.names().join(", ")
+      ))
+    })?;
+    Ok(policy.clean(html).into())
+  }
+}
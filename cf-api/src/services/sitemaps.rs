@@ -0,0 +1,242 @@
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::sync::Arc;
+
+use bytes::Bytes;
+use cf_repository::repositories::{PublicPostsRepository, UsersRepository};
+use cf_util::storage::{Storage, StorageError};
+use chrono::{DateTime, FixedOffset, SecondsFormat};
+use sea_orm::DatabaseConnection;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use tokio::sync::Mutex;
+use uuid::Uuid;
+
+use crate::config::custom_error::AppError;
+use crate::config::site::SiteConfig;
+
+/// Most URLs a single sitemap may list, per the sitemaps protocol.
+pub const MAX_SITEMAP_URLS: usize = 50_000;
+
+const PREFIX: &str = "sitemaps/";
+const INDEX_KEY: &str = "sitemaps/index.xml";
+const MANIFEST_KEY: &str = "sitemaps/manifest.json";
+const CONTENT_TYPE: &str = "application/xml";
+
+/// What was written on the last run, so unchanged parts are not rewritten.
+#[derive(Default, Serialize, Deserialize)]
+struct Manifest {
+  /// Base URLs the parts were written with; a change rewrites everything
+  base_url: String,
+  /// Part file name to its state
+  parts: BTreeMap<String, PartState>,
+}
+
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct PartState {
+  /// Hash over the ids and `updated_at` of the listed URLs
+  fingerprint: String,
+  /// Latest `updated_at` in the part, as a Unix timestamp
+  lastmod: i64,
+}
+
+/// Sitemaps are generated into storage by a background job and served from
+/// there: `sitemaps/index.xml` plus one part per [`MAX_SITEMAP_URLS`] posts
+/// or profiles.
+#[derive(Clone)]
+pub struct SitemapService {
+  db: Arc<DatabaseConnection>,
+  storage: Arc<dyn Storage>,
+  site: SiteConfig,
+  /// Keeps the job and a first request from generating at the same time
+  lock: Arc<Mutex<()>>,
+}
+
+impl SitemapService {
+  pub fn new(db: Arc<DatabaseConnection>, storage: Arc<dyn Storage>, site: SiteConfig) -> Self {
+    Self {
+      db,
+      storage,
+      site,
+      lock: Arc::new(Mutex::new(())),
+    }
+  }
+
+  /// The sitemap index, generated first if it was never stored.
+  pub async fn index(&self) -> Result<Bytes, AppError> {
+    match self.storage.get(INDEX_KEY).await {
+      Ok(object) => Ok(object.data),
+      Err(StorageError::NotFound(_)) => {
+        self.regenerate().await?;
+        Ok(self.storage.get(INDEX_KEY).await?.data)
+      }
+      Err(err) => Err(err.into()),
+    }
+  }
+
+  /// A part listed in the index, such as `posts-1.xml`.
+  pub async fn part(&self, name: &str) -> Result<Bytes, AppError> {
+    let valid = name
+      .strip_suffix(".xml")
+      .and_then(|stem| stem.split_once('-'))
+      .is_some_and(|(kind, number)| {
+        matches!(kind, "posts" | "users") && number.parse::<u32>().is_ok_and(|n| n > 0)
+      });
+    if !valid {
+      return Err(AppError::NotFound("Sitemap not found".to_string()));
+    }
+
+    match self.storage.get(&format!("{}{}", PREFIX, name)).await {
+      Ok(object) => Ok(object.data),
+      Err(StorageError::NotFound(_)) => Err(AppError::NotFound("Sitemap not found".to_string())),
+      Err(err) => Err(err.into()),
+    }
+  }
+
+  /// Bring the stored sitemaps up to date. Only parts whose URLs or
+  /// `lastmod` changed are rewritten; returns how many were.
+  pub async fn regenerate(&self) -> Result<usize, AppError> {
+    let _guard = self.lock.lock().await;
+
+    let posts = PublicPostsRepository::find_stamps(&self.db)
+      .await
+      .map_err(AppError::from)?;
+    let users = UsersRepository::find_public_stamps(&self.db)
+      .await
+      .map_err(AppError::from)?;
+
+    let base_url = format!("{} {}", self.site.base_url, self.site.sitemap_base_url);
+    let previous = Some(self.load_manifest().await?)
+      .filter(|previous| previous.base_url == base_url)
+      .unwrap_or_default();
+    let mut manifest = Manifest {
+      base_url,
+      ..Manifest::default()
+    };
+    let mut written = 0;
+
+    for (kind, stamps) in [("posts", posts), ("users", users)] {
+      for (number, chunk) in stamps.chunks(MAX_SITEMAP_URLS).enumerate() {
+        let name = format!("{}-{}.xml", kind, number + 1);
+        let state = PartState {
+          fingerprint: fingerprint(chunk),
+          lastmod: chunk
+            .iter()
+            .map(|(_, updated_at)| updated_at.timestamp())
+            .max()
+            .unwrap_or_default(),
+        };
+
+        if previous.parts.get(&name) != Some(&state) {
+          let body = self.render_part(kind, chunk);
+          self
+            .storage
+            .put(
+              &format!("{}{}", PREFIX, name),
+              Bytes::from(body),
+              CONTENT_TYPE,
+            )
+            .await?;
+          written += 1;
+        }
+        manifest.parts.insert(name, state);
+      }
+    }
+
+    // Parts left over from when there were more URLs
+    for name in previous.parts.keys() {
+      if !manifest.parts.contains_key(name) {
+        self.storage.delete(&format!("{}{}", PREFIX, name)).await?;
+        written += 1;
+      }
+    }
+
+    let index_missing = self.storage.head(INDEX_KEY).await?.is_none();
+    if written > 0 || index_missing {
+      self
+        .storage
+        .put(
+          INDEX_KEY,
+          Bytes::from(self.render_index(&manifest)),
+          CONTENT_TYPE,
+        )
+        .await?;
+      let json =
+        serde_json::to_vec(&manifest).map_err(|err| AppError::InternalError(err.to_string()))?;
+      self
+        .storage
+        .put(MANIFEST_KEY, Bytes::from(json), "application/json")
+        .await?;
+    }
+
+    Ok(written)
+  }
+
+  /// The stored manifest; a missing or unreadable one means rewriting all.
+  async fn load_manifest(&self) -> Result<Manifest, AppError> {
+    match self.storage.get(MANIFEST_KEY).await {
+      Ok(object) => Ok(serde_json::from_slice(&object.data).unwrap_or_default()),
+      Err(StorageError::NotFound(_)) => Ok(Manifest::default()),
+      Err(err) => Err(err.into()),
+    }
+  }
+
+  fn render_part(&self, kind: &str, chunk: &[(Uuid, DateTime<FixedOffset>)]) -> String {
+    let mut xml = String::from(
+      "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+       <urlset xmlns=\"http://www.sitemaps.org/schemas/sitemap/0.9\">\n",
+    );
+    for (id, updated_at) in chunk {
+      let loc = match kind {
+        "posts" => self.site.post_url(*id),
+        _ => self.site.user_url(*id),
+      };
+      let _ = writeln!(
+        xml,
+        "<url><loc>{}</loc><lastmod>{}</lastmod></url>",
+        escape(&loc),
+        updated_at.to_rfc3339_opts(SecondsFormat::Secs, true)
+      );
+    }
+    xml.push_str("</urlset>\n");
+    xml
+  }
+
+  fn render_index(&self, manifest: &Manifest) -> String {
+    let mut xml = String::from(
+      "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+       <sitemapindex xmlns=\"http://www.sitemaps.org/schemas/sitemap/0.9\">\n",
+    );
+    for (name, state) in &manifest.parts {
+      let loc = format!("{}/sitemaps/{}", self.site.sitemap_base_url, name);
+      let _ = writeln!(
+        xml,
+        "<sitemap><loc>{}</loc><lastmod>{}</lastmod></sitemap>",
+        escape(&loc),
+        DateTime::from_timestamp(state.lastmod, 0)
+          .unwrap_or_default()
+          .to_rfc3339_opts(SecondsFormat::Secs, true)
+      );
+    }
+    xml.push_str("</sitemapindex>\n");
+    xml
+  }
+}
+
+fn fingerprint(chunk: &[(Uuid, DateTime<FixedOffset>)]) -> String {
+  let mut hasher = Sha256::new();
+  for (id, updated_at) in chunk {
+    hasher.update(id.as_bytes());
+    hasher.update(updated_at.timestamp_micros().to_be_bytes());
+  }
+  hex::encode(hasher.finalize())
+}
+
+fn escape(text: &str) -> String {
+  text
+    .replace('&', "&amp;")
+    .replace('<', "&lt;")
+    .replace('>', "&gt;")
+    .replace('"', "&quot;")
+    .replace('\'', "&apos;")
+}
@@ -1,7 +1,10 @@
 use crate::config::custom_error::AppError;
 use crate::dto::users::{CreateUserRequest, UpdateUserRequest};
+use crate::structs::profile::UserProfileResponse;
 use cf_entity::entity::users;
-use cf_repository::repositories::UsersRepository;
+use cf_repository::repositories::{FollowsRepository, UsersRepository};
+use cf_repository::structs::pagination::{Cursor, CursorPage, OffsetPage};
+use cf_util::urls::UrlResolver;
 use sea_orm::{DatabaseConnection, IntoActiveModel, Set};
 use std::sync::Arc;
 use uuid::Uuid;
@@ -9,23 +12,40 @@ use uuid::Uuid;
 #[derive(Clone)]
 pub struct UserService {
   db: Arc<DatabaseConnection>,
+  urls: UrlResolver,
 }
 
 impl UserService {
-  pub fn new(db: Arc<DatabaseConnection>) -> Self {
-    Self { db }
+  pub fn new(db: Arc<DatabaseConnection>, urls: UrlResolver) -> Self {
+    Self { db, urls }
   }
 
   pub async fn get_all_users(
     &self,
     page: u64,
     per_page: u64,
-  ) -> Result<(Vec<users::Model>, u64), AppError> {
+  ) -> Result<OffsetPage<users::Model>, AppError> {
     UsersRepository::find_all(&self.db, page, per_page)
       .await
       .map_err(AppError::from)
   }
 
+  pub async fn get_users_by_cursor(
+    &self,
+    cursor: Option<&str>,
+    limit: u64,
+  ) -> Result<CursorPage<users::Model>, AppError> {
+    let cursor = cursor
+      .map(|c| {
+        Cursor::decode(c).ok_or_else(|| AppError::ValidationError("Invalid cursor".to_string()))
+      })
+      .transpose()?;
+
+    UsersRepository::find_all_by_cursor(&self.db, cursor, limit)
+      .await
+      .map_err(AppError::from)
+  }
+
   pub async fn get_user(&self, id: Uuid) -> Result<users::Model, AppError> {
     UsersRepository::find_by_id(&self.db, id)
       .await
@@ -85,4 +105,50 @@ impl UserService {
       .map(|_| ())
       .map_err(AppError::from)
   }
+
+  /// Follow `followee_id`, which lets the caller read their followers-only
+  /// posts. Following again is a no-op.
+  pub async fn follow(&self, follower_id: Uuid, followee_id: Uuid) -> Result<(), AppError> {
+    if follower_id == followee_id {
+      return Err(AppError::ValidationError(
+        "Cannot follow yourself".to_string(),
+      ));
+    }
+    self.get_user(followee_id).await?;
+
+    FollowsRepository::follow(&self.db, follower_id, followee_id)
+      .await
+      .map_err(AppError::from)
+  }
+
+  pub async fn unfollow(&self, follower_id: Uuid, followee_id: Uuid) -> Result<(), AppError> {
+    let result = FollowsRepository::unfollow(&self.db, follower_id, followee_id)
+      .await
+      .map_err(AppError::from)?;
+    if result.rows_affected == 0 {
+      return Err(AppError::NotFound("Not following this user".to_string()));
+    }
+    Ok(())
+  }
+
+  pub async fn to_response(&self, user: users::Model) -> Result<UserProfileResponse, AppError> {
+    let mut responses = self.to_responses(vec![user]).await?;
+    Ok(responses.remove(0))
+  }
+
+  /// Resolve every avatar key to the URL clients should load it from.
+  pub async fn to_responses(
+    &self,
+    users: Vec<users::Model>,
+  ) -> Result<Vec<UserProfileResponse>, AppError> {
+    let mut responses = Vec::with_capacity(users.len());
+    for user in users {
+      let avatar_url = match &user.avatar_key {
+        Some(key) => Some(self.urls.resolve(key).await?),
+        None => None,
+      };
+      responses.push(UserProfileResponse { user, avatar_url });
+    }
+    Ok(responses)
+  }
 }
@@ -0,0 +1,24 @@
+use chrono::NaiveDate;
+use serde::Serialize;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+#[derive(Serialize, Debug, ToSchema)]
+pub struct DailyViews {
+  #[schema(value_type = String, format = Date)]
+  pub day: NaiveDate,
+  pub views: i64,
+  pub unique_visitors: i64,
+}
+
+#[derive(Serialize, Debug, ToSchema)]
+pub struct PostStats {
+  pub post_id: Uuid,
+  /// Lifetime views as of the last rollup
+  pub view_count: i64,
+  #[schema(value_type = String, format = Date)]
+  pub from: NaiveDate,
+  #[schema(value_type = String, format = Date)]
+  pub to: NaiveDate,
+  pub series: Vec<DailyViews>,
+}
@@ -0,0 +1,10 @@
+use cf_entity::entity::{bookmarks, posts};
+use serde::Serialize;
+use utoipa::ToSchema;
+
+#[derive(Serialize, Debug, ToSchema)]
+pub struct BookmarkResponse {
+  #[serde(flatten)]
+  pub bookmark: bookmarks::Model,
+  pub post: posts::Model,
+}
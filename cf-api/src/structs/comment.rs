@@ -0,0 +1,12 @@
+use cf_entity::entity::comments;
+use serde::Serialize;
+use utoipa::ToSchema;
+
+/// Comment with its replies nested underneath.
+#[derive(Serialize, Debug, ToSchema)]
+pub struct CommentNode {
+  #[serde(flatten)]
+  pub comment: comments::Model,
+  #[schema(no_recursion)]
+  pub replies: Vec<CommentNode>,
+}
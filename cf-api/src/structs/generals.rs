@@ -1,10 +1,133 @@
+use actix_web::HttpRequest;
+use cf_repository::structs::pagination::{CursorPage, OffsetPage};
 use serde::{Deserialize, Serialize};
 use utoipa::{IntoParams, ToSchema};
 
+use crate::config::custom_error::AppError;
+use crate::config::pagination::PaginationConfig;
+
+/// Page mode uses `page`/`per_page`; passing `cursor` or `limit` switches the
+/// listing to keyset mode ordered by `(created_at, id)`.
 #[derive(Deserialize, Debug, IntoParams, ToSchema)]
 pub struct PaginationParam {
+  #[param(minimum = 1)]
   pub page: Option<u64>,
+  /// Capped by `PAGINATION_MAX_PER_PAGE`
+  #[param(minimum = 1)]
   pub per_page: Option<u64>,
+  /// Opaque cursor taken from `next_cursor` or `prev_cursor` of a previous response
+  pub cursor: Option<String>,
+  /// Capped by `PAGINATION_MAX_PER_PAGE`
+  #[param(minimum = 1)]
+  pub limit: Option<u64>,
+}
+
+impl PaginationParam {
+  pub fn is_cursor_mode(&self) -> bool {
+    self.cursor.is_some() || self.limit.is_some()
+  }
+
+  /// Validated `(page, per_page)` for page mode.
+  pub fn page_bounds(&self, config: &PaginationConfig) -> Result<(u64, u64), AppError> {
+    let page = self.page.unwrap_or(1);
+    if page < 1 {
+      return Err(AppError::ValidationError(
+        "page must be greater than or equal to 1".to_string(),
+      ));
+    }
+
+    let per_page = Self::size_bounds("per_page", self.per_page, config)?;
+    Ok((page, per_page))
+  }
+
+  /// Validated `limit` for cursor mode.
+  pub fn limit_bounds(&self, config: &PaginationConfig) -> Result<u64, AppError> {
+    Self::size_bounds("limit", self.limit, config)
+  }
+
+  fn size_bounds(
+    name: &str,
+    value: Option<u64>,
+    config: &PaginationConfig,
+  ) -> Result<u64, AppError> {
+    let value = value.unwrap_or(config.default_per_page);
+    if value < 1 || value > config.max_per_page {
+      return Err(AppError::ValidationError(format!(
+        "{} must be between 1 and {}",
+        name, config.max_per_page
+      )));
+    }
+    Ok(value)
+  }
+}
+
+#[derive(Serialize, Debug, ToSchema)]
+pub struct PaginationLinks {
+  pub next: Option<String>,
+  pub prev: Option<String>,
+}
+
+#[derive(Serialize, Debug, ToSchema)]
+pub struct Paginated<T> {
+  pub items: Vec<T>,
+  pub page: u64,
+  pub per_page: u64,
+  pub total_items: u64,
+  pub total_pages: u64,
+  pub links: PaginationLinks,
+}
+
+impl<T> Paginated<T> {
+  /// Wrap a repository page, building neighbour links from the request so
+  /// any filters in its query string carry over.
+  pub fn from_page(page: OffsetPage<T>, req: &HttpRequest) -> Self {
+    let filters: Vec<&str> = req
+      .query_string()
+      .split('&')
+      .filter(|pair| {
+        !pair.is_empty() && !pair.starts_with("page=") && !pair.starts_with("per_page=")
+      })
+      .collect();
+    let link = |target: u64| {
+      let mut query = filters.clone();
+      let page_pair = format!("page={}", target);
+      let per_page_pair = format!("per_page={}", page.per_page);
+      query.push(&page_pair);
+      query.push(&per_page_pair);
+      format!("{}?{}", req.path(), query.join("&"))
+    };
+
+    let next = (page.page < page.total_pages).then(|| link(page.page + 1));
+    // Past the end still points back at the last real page
+    let prev =
+      (page.page > 1 && page.total_pages > 0).then(|| link((page.page - 1).min(page.total_pages)));
+
+    Self {
+      items: page.items,
+      page: page.page,
+      per_page: page.per_page,
+      total_items: page.total_items,
+      total_pages: page.total_pages,
+      links: PaginationLinks { next, prev },
+    }
+  }
+}
+
+#[derive(Serialize, Debug, ToSchema)]
+pub struct CursorPaginated<T> {
+  pub items: Vec<T>,
+  pub next_cursor: Option<String>,
+  pub prev_cursor: Option<String>,
+}
+
+impl<T> From<CursorPage<T>> for CursorPaginated<T> {
+  fn from(page: CursorPage<T>) -> Self {
+    Self {
+      items: page.items,
+      next_cursor: page.next_cursor,
+      prev_cursor: page.prev_cursor,
+    }
+  }
 }
 
 #[derive(Deserialize, Serialize, Debug, ToSchema, Clone)]
@@ -0,0 +1,42 @@
+use std::collections::BTreeMap;
+
+use cf_entity::entity::media;
+use serde::Serialize;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+/// Where and how to send a direct upload. The client sends the file with
+/// `method` to `url` including every header in `headers`, then calls the
+/// completion endpoint with `upload_id`.
+#[derive(Serialize, Debug, ToSchema)]
+pub struct PresignedUpload {
+  pub upload_id: Uuid,
+  pub method: String,
+  pub url: String,
+  pub headers: BTreeMap<String, String>,
+  pub storage_key: String,
+  #[schema(value_type = String, format = DateTime)]
+  pub expires_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Serialize, Debug, ToSchema)]
+pub struct RenditionResponse {
+  pub name: String,
+  pub url: String,
+  pub mime_type: String,
+  pub width: i32,
+  pub height: i32,
+  pub size_bytes: i64,
+}
+
+/// Uploaded media with the URL of the original and of every rendition
+/// generated so far; `renditions` is empty while `status` is `PROCESSING`.
+#[derive(Serialize, Debug, ToSchema)]
+pub struct MediaResponse {
+  #[serde(flatten)]
+  pub media: media::Model,
+  pub url: String,
+  pub renditions: Vec<RenditionResponse>,
+  /// Posts using this media; it cannot be deleted while this is not zero
+  pub reference_count: u64,
+}
@@ -1,2 +1,11 @@
+pub mod analytics;
+pub mod bookmark;
+pub mod comment;
 pub mod generals;
+pub mod media;
+pub mod post;
+pub mod preview;
+pub mod profile;
+pub mod reaction;
 pub mod response_api;
+pub mod sanitize;
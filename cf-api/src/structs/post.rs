@@ -0,0 +1,78 @@
+use cf_entity::entity::{posts, tags};
+use sea_orm::prelude::DateTimeWithTimeZone;
+use serde::Serialize;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::structs::reaction::ReactionSummary;
+
+/// Post with the aggregates shown alongside it in listings and detail.
+#[derive(Serialize, Debug, ToSchema)]
+pub struct PostResponse {
+  #[serde(flatten)]
+  pub post: posts::Model,
+  pub tags: Vec<tags::Model>,
+  pub comment_count: u64,
+  pub reactions: ReactionSummary,
+  /// Whether the caller bookmarked the post, always false for anonymous callers
+  pub bookmarked: bool,
+}
+
+/// A `<meta>` tag.
+#[derive(Serialize, Debug, ToSchema)]
+pub struct MetaTag {
+  /// The `property` attribute for Open Graph, `name` for Twitter cards
+  pub key: String,
+  pub content: String,
+}
+
+/// Everything the frontend embeds in a post page's `<head>`.
+#[derive(Serialize, Debug, ToSchema)]
+pub struct PostMeta {
+  /// `meta_title`, or the post title
+  pub title: String,
+  /// `meta_description`, or an excerpt of the content
+  pub description: String,
+  /// `canonical_url`, or the post's own URL
+  pub canonical_url: String,
+  /// Social image, or the header image
+  pub image_url: Option<String>,
+  pub open_graph: Vec<MetaTag>,
+  pub twitter: Vec<MetaTag>,
+  /// schema.org `Article`, for a `<script type="application/ld+json">`
+  #[schema(value_type = Object)]
+  pub json_ld: serde_json::Value,
+}
+
+/// Post as shown to anonymous readers, without workflow and internal fields.
+#[derive(Serialize, Debug, ToSchema)]
+pub struct PublicPostResponse {
+  pub id: Uuid,
+  pub author_id: Uuid,
+  pub title: String,
+  pub content: String,
+  pub tags: Vec<tags::Model>,
+  pub comment_count: u64,
+  pub reactions: ReactionSummary,
+  #[schema(value_type = String, format = DateTime)]
+  pub created_at: DateTimeWithTimeZone,
+  #[schema(value_type = String, format = DateTime)]
+  pub updated_at: DateTimeWithTimeZone,
+}
+
+impl From<PostResponse> for PublicPostResponse {
+  fn from(response: PostResponse) -> Self {
+    let post = response.post;
+    Self {
+      id: post.id,
+      author_id: post.user_id,
+      title: post.title,
+      content: post.content,
+      tags: response.tags,
+      comment_count: response.comment_count,
+      reactions: response.reactions,
+      created_at: post.created_at,
+      updated_at: post.updated_at,
+    }
+  }
+}
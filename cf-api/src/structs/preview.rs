@@ -0,0 +1,35 @@
+use chrono::{DateTime, FixedOffset};
+use serde::Serialize;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+/// A preview link as shown to the post author.
+#[derive(Serialize, Debug, ToSchema)]
+pub struct PreviewLinkResponse {
+  pub id: Uuid,
+  pub post_id: Uuid,
+  /// Signed token for `GET /api/preview/{token}`
+  pub token: String,
+  /// Frontend page that shows the preview
+  pub url: String,
+  /// `updated_at` of the post revision the link shows
+  #[schema(value_type = String, format = DateTime)]
+  pub revision: DateTime<FixedOffset>,
+  #[schema(value_type = String, format = DateTime)]
+  pub expires_at: DateTime<FixedOffset>,
+  #[schema(value_type = String, format = DateTime)]
+  pub created_at: DateTime<FixedOffset>,
+}
+
+/// A draft as it was when its preview link was created.
+#[derive(Serialize, Debug, ToSchema)]
+pub struct DraftPreview {
+  pub post_id: Uuid,
+  pub title: String,
+  /// Content rendered to sanitised HTML
+  pub html: String,
+  #[schema(value_type = String, format = DateTime)]
+  pub revision: DateTime<FixedOffset>,
+  #[schema(value_type = String, format = DateTime)]
+  pub expires_at: DateTime<FixedOffset>,
+}
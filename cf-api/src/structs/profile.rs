@@ -0,0 +1,12 @@
+use cf_entity::entity::users;
+use serde::Serialize;
+use utoipa::ToSchema;
+
+/// User with the URL of their avatar, public or presigned depending on the
+/// storage visibility of `avatar_key`.
+#[derive(Serialize, Debug, ToSchema)]
+pub struct UserProfileResponse {
+  #[serde(flatten)]
+  pub user: users::Model,
+  pub avatar_url: Option<String>,
+}
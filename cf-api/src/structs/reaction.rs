@@ -0,0 +1,36 @@
+use std::cmp::Reverse;
+
+use cf_entity::entity::sea_orm_active_enums::ReactionKind;
+use serde::Serialize;
+use utoipa::ToSchema;
+
+#[derive(Serialize, Debug, Clone, ToSchema)]
+pub struct ReactionCount {
+  pub kind: ReactionKind,
+  pub count: u64,
+}
+
+/// Reaction totals of a post or comment as seen by the caller.
+#[derive(Serialize, Debug, Clone, Default, ToSchema)]
+pub struct ReactionSummary {
+  pub total: u64,
+  pub counts: Vec<ReactionCount>,
+  /// The caller's own reaction, always empty for anonymous callers
+  pub my_reaction: Option<ReactionKind>,
+}
+
+impl ReactionSummary {
+  pub fn new(counts: Vec<(ReactionKind, u64)>, my_reaction: Option<ReactionKind>) -> Self {
+    let mut counts: Vec<ReactionCount> = counts
+      .into_iter()
+      .map(|(kind, count)| ReactionCount { kind, count })
+      .collect();
+    counts.sort_by_key(|c| Reverse(c.count));
+
+    Self {
+      total: counts.iter().map(|c| c.count).sum(),
+      counts,
+      my_reaction,
+    }
+  }
+}
@@ -0,0 +1,45 @@
+use std::collections::BTreeMap;
+
+use cf_util::sanitize::{SanitizeReport, Sanitized};
+use serde::Serialize;
+use utoipa::ToSchema;
+
+/// What a policy removed, for the editor to warn the author about.
+#[derive(Serialize, Debug, ToSchema)]
+pub struct SanitizeReportResponse {
+  /// Removed elements and how often, by tag name
+  pub removed_tags: BTreeMap<String, usize>,
+  /// Removed attributes and how often, as `tag[attribute]`
+  pub removed_attributes: BTreeMap<String, usize>,
+  /// Embed URLs dropped because their host is not allowed
+  pub blocked_embeds: Vec<String>,
+}
+
+impl From<SanitizeReport> for SanitizeReportResponse {
+  fn from(report: SanitizeReport) -> Self {
+    Self {
+      removed_tags: report.removed_tags,
+      removed_attributes: report.removed_attributes,
+      blocked_embeds: report.blocked_embeds,
+    }
+  }
+}
+
+#[derive(Serialize, Debug, ToSchema)]
+pub struct SanitizePreview {
+  /// HTML as it would be stored
+  pub html: String,
+  /// Whether anything was removed
+  pub modified: bool,
+  pub report: SanitizeReportResponse,
+}
+
+impl From<Sanitized> for SanitizePreview {
+  fn from(sanitized: Sanitized) -> Self {
+    Self {
+      html: sanitized.html,
+      modified: !sanitized.report.is_empty(),
+      report: sanitized.report.into(),
+    }
+  }
+}
@@ -0,0 +1,45 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize, ToSchema)]
+#[sea_orm(table_name = "bookmark_collections")]
+#[schema(as = BookmarkCollections)]
+pub struct Model {
+  #[sea_orm(primary_key, auto_increment = false)]
+  pub id: Uuid,
+  pub user_id: Uuid,
+  pub name: String,
+  #[schema(value_type = String, format = DateTime)]
+  pub created_at: DateTimeWithTimeZone,
+  #[schema(value_type = String, format = DateTime)]
+  pub updated_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+  #[sea_orm(
+    belongs_to = "super::users::Entity",
+    from = "Column::UserId",
+    to = "super::users::Column::Id",
+    on_update = "Cascade",
+    on_delete = "Cascade"
+  )]
+  Users,
+  #[sea_orm(has_many = "super::bookmarks::Entity")]
+  Bookmarks,
+}
+
+impl Related<super::users::Entity> for Entity {
+  fn to() -> RelationDef {
+    Relation::Users.def()
+  }
+}
+
+impl Related<super::bookmarks::Entity> for Entity {
+  fn to() -> RelationDef {
+    Relation::Bookmarks.def()
+  }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,64 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize, ToSchema)]
+#[sea_orm(table_name = "bookmarks")]
+#[schema(as = Bookmarks)]
+pub struct Model {
+  #[sea_orm(primary_key, auto_increment = false)]
+  pub id: Uuid,
+  pub user_id: Uuid,
+  pub post_id: Uuid,
+  pub collection_id: Option<Uuid>,
+  #[schema(value_type = String, format = DateTime)]
+  pub created_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+  #[sea_orm(
+    belongs_to = "super::users::Entity",
+    from = "Column::UserId",
+    to = "super::users::Column::Id",
+    on_update = "Cascade",
+    on_delete = "Cascade"
+  )]
+  Users,
+  #[sea_orm(
+    belongs_to = "super::posts::Entity",
+    from = "Column::PostId",
+    to = "super::posts::Column::Id",
+    on_update = "Cascade",
+    on_delete = "Cascade"
+  )]
+  Posts,
+  #[sea_orm(
+    belongs_to = "super::bookmark_collections::Entity",
+    from = "Column::CollectionId",
+    to = "super::bookmark_collections::Column::Id",
+    on_update = "Cascade",
+    on_delete = "SetNull"
+  )]
+  BookmarkCollections,
+}
+
+impl Related<super::users::Entity> for Entity {
+  fn to() -> RelationDef {
+    Relation::Users.def()
+  }
+}
+
+impl Related<super::posts::Entity> for Entity {
+  fn to() -> RelationDef {
+    Relation::Posts.def()
+  }
+}
+
+impl Related<super::bookmark_collections::Entity> for Entity {
+  fn to() -> RelationDef {
+    Relation::BookmarkCollections.def()
+  }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,71 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize, ToSchema)]
+#[sea_orm(table_name = "comments")]
+#[schema(as = Comments)]
+pub struct Model {
+  #[sea_orm(primary_key, auto_increment = false)]
+  pub id: Uuid,
+  pub post_id: Uuid,
+  pub user_id: Uuid,
+  pub parent_id: Option<Uuid>,
+  pub depth: i32,
+  #[sea_orm(column_type = "Text")]
+  pub content: String,
+  #[schema(value_type = String, format = DateTime)]
+  pub created_at: DateTimeWithTimeZone,
+  #[schema(value_type = String, format = DateTime)]
+  pub updated_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+  #[sea_orm(
+    belongs_to = "super::posts::Entity",
+    from = "Column::PostId",
+    to = "super::posts::Column::Id",
+    on_update = "Cascade",
+    on_delete = "Cascade"
+  )]
+  Posts,
+  #[sea_orm(
+    belongs_to = "super::users::Entity",
+    from = "Column::UserId",
+    to = "super::users::Column::Id",
+    on_update = "Cascade",
+    on_delete = "Cascade"
+  )]
+  Users,
+  #[sea_orm(
+    belongs_to = "Entity",
+    from = "Column::ParentId",
+    to = "Column::Id",
+    on_update = "Cascade",
+    on_delete = "Cascade"
+  )]
+  SelfRef,
+  #[sea_orm(has_many = "super::reactions::Entity")]
+  Reactions,
+}
+
+impl Related<super::posts::Entity> for Entity {
+  fn to() -> RelationDef {
+    Relation::Posts.def()
+  }
+}
+
+impl Related<super::users::Entity> for Entity {
+  fn to() -> RelationDef {
+    Relation::Users.def()
+  }
+}
+
+impl Related<super::reactions::Entity> for Entity {
+  fn to() -> RelationDef {
+    Relation::Reactions.def()
+  }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,35 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize)]
+#[sea_orm(table_name = "follows")]
+pub struct Model {
+  #[sea_orm(primary_key, auto_increment = false)]
+  pub follower_id: Uuid,
+  /// The user being followed
+  #[sea_orm(primary_key, auto_increment = false)]
+  pub followee_id: Uuid,
+  pub created_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+  #[sea_orm(
+    belongs_to = "super::users::Entity",
+    from = "Column::FollowerId",
+    to = "super::users::Column::Id",
+    on_update = "Cascade",
+    on_delete = "Cascade"
+  )]
+  Follower,
+  #[sea_orm(
+    belongs_to = "super::users::Entity",
+    from = "Column::FolloweeId",
+    to = "super::users::Column::Id",
+    on_update = "Cascade",
+    on_delete = "Cascade"
+  )]
+  Followee,
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,66 @@
+use super::sea_orm_active_enums::MediaStatus;
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize, ToSchema)]
+#[sea_orm(table_name = "media")]
+#[schema(as = Media)]
+pub struct Model {
+  #[sea_orm(primary_key, auto_increment = false)]
+  pub id: Uuid,
+  /// Uploader
+  pub user_id: Uuid,
+  /// Object key in the media bucket
+  #[sea_orm(unique)]
+  pub storage_key: String,
+  pub mime_type: String,
+  pub size_bytes: i64,
+  pub width: Option<i32>,
+  pub height: Option<i32>,
+  /// Renditions are generated in the background while `PROCESSING`
+  pub status: MediaStatus,
+  #[sea_orm(column_type = "Text", nullable)]
+  pub alt_text: Option<String>,
+  #[sea_orm(column_type = "Text", nullable)]
+  pub caption: Option<String>,
+  pub credit: Option<String>,
+  #[schema(value_type = String, format = DateTime)]
+  pub created_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+  #[sea_orm(
+    belongs_to = "super::users::Entity",
+    from = "Column::UserId",
+    to = "super::users::Column::Id",
+    on_update = "Cascade",
+    on_delete = "Cascade"
+  )]
+  Users,
+  #[sea_orm(has_many = "super::posts::Entity")]
+  Posts,
+  #[sea_orm(has_many = "super::media_renditions::Entity")]
+  MediaRenditions,
+}
+
+impl Related<super::users::Entity> for Entity {
+  fn to() -> RelationDef {
+    Relation::Users.def()
+  }
+}
+
+impl Related<super::posts::Entity> for Entity {
+  fn to() -> RelationDef {
+    Relation::Posts.def()
+  }
+}
+
+impl Related<super::media_renditions::Entity> for Entity {
+  fn to() -> RelationDef {
+    Relation::MediaRenditions.def()
+  }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
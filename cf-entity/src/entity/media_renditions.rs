@@ -0,0 +1,42 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// A resized or re-encoded copy of an uploaded image.
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize, ToSchema)]
+#[sea_orm(table_name = "media_renditions")]
+#[schema(as = MediaRenditions)]
+pub struct Model {
+  #[sea_orm(primary_key, auto_increment = false)]
+  pub id: Uuid,
+  pub media_id: Uuid,
+  /// Rendition name from the configuration, e.g. `thumbnail`
+  pub name: String,
+  pub storage_key: String,
+  pub mime_type: String,
+  pub size_bytes: i64,
+  pub width: i32,
+  pub height: i32,
+  #[schema(value_type = String, format = DateTime)]
+  pub created_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+  #[sea_orm(
+    belongs_to = "super::media::Entity",
+    from = "Column::MediaId",
+    to = "super::media::Column::Id",
+    on_update = "Cascade",
+    on_delete = "Cascade"
+  )]
+  Media,
+}
+
+impl Related<super::media::Entity> for Entity {
+  fn to() -> RelationDef {
+    Relation::Media.def()
+  }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -1,4 +1,20 @@
+pub mod bookmark_collections;
+pub mod bookmarks;
+pub mod comments;
+pub mod follows;
+pub mod media;
+pub mod media_renditions;
+pub mod media_uploads;
+pub mod post_media;
+pub mod post_placements;
+pub mod post_previews;
+pub mod post_tags;
+pub mod post_trending;
+pub mod post_view_daily;
+pub mod post_views;
 pub mod posts;
 pub mod prelude;
+pub mod reactions;
 pub mod sea_orm_active_enums;
+pub mod tags;
 pub mod users;
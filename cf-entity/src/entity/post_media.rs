@@ -0,0 +1,46 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Media embedded as an image in a post's content.
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize)]
+#[sea_orm(table_name = "post_media")]
+pub struct Model {
+  #[sea_orm(primary_key, auto_increment = false)]
+  pub post_id: Uuid,
+  #[sea_orm(primary_key, auto_increment = false)]
+  pub media_id: Uuid,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+  #[sea_orm(
+    belongs_to = "super::posts::Entity",
+    from = "Column::PostId",
+    to = "super::posts::Column::Id",
+    on_update = "Cascade",
+    on_delete = "Cascade"
+  )]
+  Posts,
+  #[sea_orm(
+    belongs_to = "super::media::Entity",
+    from = "Column::MediaId",
+    to = "super::media::Column::Id",
+    on_update = "Cascade",
+    on_delete = "Restrict"
+  )]
+  Media,
+}
+
+impl Related<super::posts::Entity> for Entity {
+  fn to() -> RelationDef {
+    Relation::Posts.def()
+  }
+}
+
+impl Related<super::media::Entity> for Entity {
+  fn to() -> RelationDef {
+    Relation::Media.def()
+  }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,51 @@
+use super::sea_orm_active_enums::PlacementKind;
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// A post an editor pinned or featured. Expired rows stay until removed but
+/// are ignored everywhere.
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize, ToSchema)]
+#[sea_orm(table_name = "post_placements")]
+#[schema(as = PostPlacements)]
+pub struct Model {
+  #[sea_orm(primary_key, auto_increment = false)]
+  pub kind: PlacementKind,
+  #[sea_orm(primary_key, auto_increment = false)]
+  pub post_id: Uuid,
+  /// Order within the kind, lowest first
+  pub position: i32,
+  #[schema(value_type = Option<String>, format = DateTime)]
+  pub expires_at: Option<DateTimeWithTimeZone>,
+  pub created_by: Uuid,
+  #[schema(value_type = String, format = DateTime)]
+  pub created_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+  #[sea_orm(
+    belongs_to = "super::posts::Entity",
+    from = "Column::PostId",
+    to = "super::posts::Column::Id",
+    on_update = "Cascade",
+    on_delete = "Cascade"
+  )]
+  Posts,
+  #[sea_orm(
+    belongs_to = "super::users::Entity",
+    from = "Column::CreatedBy",
+    to = "super::users::Column::Id",
+    on_update = "Cascade",
+    on_delete = "Cascade"
+  )]
+  Users,
+}
+
+impl Related<super::posts::Entity> for Entity {
+  fn to() -> RelationDef {
+    Relation::Posts.def()
+  }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,48 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// A shareable preview link with the post as it was when the link was made.
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize)]
+#[sea_orm(table_name = "post_previews")]
+pub struct Model {
+  #[sea_orm(primary_key, auto_increment = false)]
+  pub id: Uuid,
+  pub post_id: Uuid,
+  pub created_by: Uuid,
+  pub title: String,
+  #[sea_orm(column_type = "Text")]
+  pub content: String,
+  /// `updated_at` of the post when the link was created
+  pub revision: DateTimeWithTimeZone,
+  pub expires_at: DateTimeWithTimeZone,
+  pub revoked_at: Option<DateTimeWithTimeZone>,
+  pub created_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+  #[sea_orm(
+    belongs_to = "super::posts::Entity",
+    from = "Column::PostId",
+    to = "super::posts::Column::Id",
+    on_update = "Cascade",
+    on_delete = "Cascade"
+  )]
+  Posts,
+  #[sea_orm(
+    belongs_to = "super::users::Entity",
+    from = "Column::CreatedBy",
+    to = "super::users::Column::Id",
+    on_update = "Cascade",
+    on_delete = "Cascade"
+  )]
+  Users,
+}
+
+impl Related<super::posts::Entity> for Entity {
+  fn to() -> RelationDef {
+    Relation::Posts.def()
+  }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
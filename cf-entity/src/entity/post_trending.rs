@@ -0,0 +1,37 @@
+use super::sea_orm_active_enums::TrendingPeriod;
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// A post's score in a trending ranking, rebuilt wholesale by a background
+/// job.
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "post_trending")]
+pub struct Model {
+  #[sea_orm(primary_key, auto_increment = false)]
+  pub period: TrendingPeriod,
+  #[sea_orm(primary_key, auto_increment = false)]
+  pub post_id: Uuid,
+  /// Time-decayed sum of weighted views, reactions and comments
+  pub score: f64,
+  pub computed_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+  #[sea_orm(
+    belongs_to = "super::posts::Entity",
+    from = "Column::PostId",
+    to = "super::posts::Column::Id",
+    on_update = "Cascade",
+    on_delete = "Cascade"
+  )]
+  Posts,
+}
+
+impl Related<super::posts::Entity> for Entity {
+  fn to() -> RelationDef {
+    Relation::Posts.def()
+  }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,36 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize, ToSchema)]
+#[sea_orm(table_name = "post_view_daily")]
+#[schema(as = PostViewDaily)]
+pub struct Model {
+  #[sea_orm(primary_key, auto_increment = false)]
+  pub post_id: Uuid,
+  #[sea_orm(primary_key, auto_increment = false)]
+  #[schema(value_type = String, format = Date)]
+  pub day: Date,
+  pub views: i64,
+  pub unique_visitors: i64,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+  #[sea_orm(
+    belongs_to = "super::posts::Entity",
+    from = "Column::PostId",
+    to = "super::posts::Column::Id",
+    on_update = "Cascade",
+    on_delete = "Cascade"
+  )]
+  Posts,
+}
+
+impl Related<super::posts::Entity> for Entity {
+  fn to() -> RelationDef {
+    Relation::Posts.def()
+  }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
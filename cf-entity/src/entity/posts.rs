@@ -1,4 +1,4 @@
-use super::sea_orm_active_enums::PostStatus;
+use super::sea_orm_active_enums::{PostStatus, PostVisibility};
 use sea_orm::entity::prelude::*;
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
@@ -13,6 +13,22 @@ pub struct Model {
   #[sea_orm(column_type = "Text")]
   pub content: String,
   pub status: PostStatus,
+  pub visibility: PostVisibility,
+  /// Deduplicated views, refreshed by the analytics rollup
+  pub view_count: i64,
+  pub header_image_id: Option<Uuid>,
+  /// Overrides `title` in search results and link previews
+  pub meta_title: Option<String>,
+  /// Overrides the excerpt in search results and link previews
+  #[sea_orm(column_type = "Text", nullable)]
+  pub meta_description: Option<String>,
+  /// Set when the post was first published elsewhere
+  pub canonical_url: Option<String>,
+  /// Image for link previews, the header image when unset
+  pub social_image_id: Option<Uuid>,
+  /// When the post was first published, kept if it is unpublished later
+  #[schema(value_type = Option<String>, format = DateTime)]
+  pub published_at: Option<DateTimeWithTimeZone>,
   #[schema(value_type = String, format = DateTime)]
   pub created_at: DateTimeWithTimeZone,
   #[schema(value_type = String, format = DateTime)]
@@ -29,6 +45,30 @@ pub enum Relation {
     on_delete = "Cascade"
   )]
   Users,
+  #[sea_orm(
+    belongs_to = "super::media::Entity",
+    from = "Column::HeaderImageId",
+    to = "super::media::Column::Id",
+    on_update = "Cascade",
+    on_delete = "NoAction"
+  )]
+  Media,
+  #[sea_orm(
+    belongs_to = "super::media::Entity",
+    from = "Column::SocialImageId",
+    to = "super::media::Column::Id",
+    on_update = "Cascade",
+    on_delete = "NoAction"
+  )]
+  SocialImage,
+  #[sea_orm(has_many = "super::comments::Entity")]
+  Comments,
+  #[sea_orm(has_many = "super::reactions::Entity")]
+  Reactions,
+  #[sea_orm(has_many = "super::bookmarks::Entity")]
+  Bookmarks,
+  #[sea_orm(has_many = "super::post_tags::Entity")]
+  PostTags,
 }
 
 impl Related<super::users::Entity> for Entity {
@@ -37,4 +77,34 @@ impl Related<super::users::Entity> for Entity {
   }
 }
 
+impl Related<super::media::Entity> for Entity {
+  fn to() -> RelationDef {
+    Relation::Media.def()
+  }
+}
+
+impl Related<super::comments::Entity> for Entity {
+  fn to() -> RelationDef {
+    Relation::Comments.def()
+  }
+}
+
+impl Related<super::reactions::Entity> for Entity {
+  fn to() -> RelationDef {
+    Relation::Reactions.def()
+  }
+}
+
+impl Related<super::bookmarks::Entity> for Entity {
+  fn to() -> RelationDef {
+    Relation::Bookmarks.def()
+  }
+}
+
+impl Related<super::post_tags::Entity> for Entity {
+  fn to() -> RelationDef {
+    Relation::PostTags.def()
+  }
+}
+
 impl ActiveModelBehavior for ActiveModel {}
@@ -12,3 +12,76 @@ pub enum PostStatus {
     #[sea_orm(string_value = "ARCHIVED")]
     Archived,
 }
+
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Hash, EnumIter, DeriveActiveEnum, Serialize, Deserialize, ToSchema,
+)]
+#[sea_orm(rs_type = "String", db_type = "String(StringLen::None)")]
+pub enum ReactionKind {
+    #[sea_orm(string_value = "LIKE")]
+    Like,
+    #[sea_orm(string_value = "LOVE")]
+    Love,
+    #[sea_orm(string_value = "LAUGH")]
+    Laugh,
+    #[sea_orm(string_value = "WOW")]
+    Wow,
+    #[sea_orm(string_value = "SAD")]
+    Sad,
+    #[sea_orm(string_value = "CELEBRATE")]
+    Celebrate,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumIter, DeriveActiveEnum, Serialize, Deserialize, ToSchema)]
+#[sea_orm(rs_type = "String", db_type = "String(StringLen::None)")]
+pub enum MediaStatus {
+    #[sea_orm(string_value = "PROCESSING")]
+    Processing,
+    #[sea_orm(string_value = "READY")]
+    Ready,
+    #[sea_orm(string_value = "FAILED")]
+    Failed,
+}
+
+/// Who can read a published post. Authors can always read their own posts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumIter, DeriveActiveEnum, Serialize, Deserialize, ToSchema)]
+#[sea_orm(rs_type = "String", db_type = "String(StringLen::None)")]
+pub enum PostVisibility {
+    /// Listed everywhere
+    #[sea_orm(string_value = "PUBLIC")]
+    Public,
+    /// Readable by anyone with the link, never listed
+    #[sea_orm(string_value = "UNLISTED")]
+    Unlisted,
+    /// Only the author
+    #[sea_orm(string_value = "PRIVATE")]
+    Private,
+    /// Users following the author
+    #[sea_orm(string_value = "FOLLOWERS_ONLY")]
+    FollowersOnly,
+}
+
+/// Time span a trending ranking covers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumIter, DeriveActiveEnum, Serialize, Deserialize, ToSchema)]
+#[sea_orm(rs_type = "String", db_type = "String(StringLen::None)")]
+pub enum TrendingPeriod {
+    #[sea_orm(string_value = "24h")]
+    #[serde(rename = "24h")]
+    Day,
+    #[sea_orm(string_value = "7d")]
+    #[serde(rename = "7d")]
+    Week,
+}
+
+/// Where editors placed a post.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumIter, DeriveActiveEnum, Serialize, Deserialize, ToSchema)]
+#[sea_orm(rs_type = "String", db_type = "String(StringLen::None)")]
+#[serde(rename_all = "lowercase")]
+pub enum PlacementKind {
+    /// At the top of post lists
+    #[sea_orm(string_value = "PINNED")]
+    Pinned,
+    /// In the featured carousel
+    #[sea_orm(string_value = "FEATURED")]
+    Featured,
+}
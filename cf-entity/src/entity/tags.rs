@@ -0,0 +1,32 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize, ToSchema)]
+#[sea_orm(table_name = "tags")]
+#[schema(as = Tag)]
+pub struct Model {
+  #[sea_orm(primary_key, auto_increment = false)]
+  pub id: Uuid,
+  /// Name as first written by an author
+  pub name: String,
+  /// Lowercase form used in URLs and to match names
+  #[sea_orm(unique)]
+  pub slug: String,
+  #[schema(value_type = String, format = DateTime)]
+  pub created_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+  #[sea_orm(has_many = "super::post_tags::Entity")]
+  PostTags,
+}
+
+impl Related<super::post_tags::Entity> for Entity {
+  fn to() -> RelationDef {
+    Relation::PostTags.def()
+  }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
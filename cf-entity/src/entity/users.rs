@@ -10,6 +10,9 @@ pub struct Model {
   pub name: String,
   #[sea_orm(unique)]
   pub email: String,
+  /// Storage key of the largest square avatar rendition, if the user has set
+  /// one. Responses resolve it to `avatar_url`.
+  pub avatar_key: Option<String>,
   #[schema(value_type = String, format = DateTime)]
   pub created_at: DateTimeWithTimeZone,
   #[schema(value_type = String, format = DateTime)]
@@ -20,6 +23,18 @@ pub struct Model {
 pub enum Relation {
   #[sea_orm(has_many = "super::posts::Entity")]
   Posts,
+  #[sea_orm(has_many = "super::comments::Entity")]
+  Comments,
+  #[sea_orm(has_many = "super::reactions::Entity")]
+  Reactions,
+  #[sea_orm(has_many = "super::bookmarks::Entity")]
+  Bookmarks,
+  #[sea_orm(has_many = "super::bookmark_collections::Entity")]
+  BookmarkCollections,
+  #[sea_orm(has_many = "super::media::Entity")]
+  Media,
+  #[sea_orm(has_many = "super::media_uploads::Entity")]
+  MediaUploads,
 }
 
 impl Related<super::posts::Entity> for Entity {
@@ -28,4 +43,40 @@ impl Related<super::posts::Entity> for Entity {
   }
 }
 
+impl Related<super::comments::Entity> for Entity {
+  fn to() -> RelationDef {
+    Relation::Comments.def()
+  }
+}
+
+impl Related<super::reactions::Entity> for Entity {
+  fn to() -> RelationDef {
+    Relation::Reactions.def()
+  }
+}
+
+impl Related<super::bookmarks::Entity> for Entity {
+  fn to() -> RelationDef {
+    Relation::Bookmarks.def()
+  }
+}
+
+impl Related<super::bookmark_collections::Entity> for Entity {
+  fn to() -> RelationDef {
+    Relation::BookmarkCollections.def()
+  }
+}
+
+impl Related<super::media::Entity> for Entity {
+  fn to() -> RelationDef {
+    Relation::Media.def()
+  }
+}
+
+impl Related<super::media_uploads::Entity> for Entity {
+  fn to() -> RelationDef {
+    Relation::MediaUploads.def()
+  }
+}
+
 impl ActiveModelBehavior for ActiveModel {}
@@ -2,6 +2,27 @@ pub use sea_orm_migration::prelude::*;
 
 mod m20250101_000001_create_table_users;
 mod m20250101_000002_create_table_posts;
+mod m20250101_000003_create_index_cursor_pagination;
+mod m20250101_000004_create_table_comments;
+mod m20250101_000005_create_table_reactions;
+mod m20250101_000006_create_table_bookmarks;
+mod m20250101_000007_create_table_post_views;
+mod m20250101_000008_create_table_media;
+mod m20250101_000009_create_table_media_uploads;
+mod m20250101_000010_create_table_media_renditions;
+mod m20250101_000011_add_users_avatar;
+mod m20250101_000012_add_media_metadata;
+mod m20250101_000013_create_table_tags;
+mod m20250101_000014_add_posts_seo;
+mod m20250101_000015_create_table_follows;
+mod m20250101_000016_add_posts_visibility;
+mod m20250101_000017_create_table_post_previews;
+mod m20250101_000018_add_posts_published_at;
+mod m20250101_000019_create_table_post_trending;
+mod m20250101_000020_create_index_posts_title_search;
+mod m20250101_000021_create_table_post_placements;
+mod m20250101_000022_create_table_post_media;
+mod m20250101_000023_create_index_posts_published_at;
 
 pub struct Migrator;
 
@@ -11,6 +32,27 @@ impl MigratorTrait for Migrator {
     vec![
       Box::new(m20250101_000001_create_table_users::Migration),
       Box::new(m20250101_000002_create_table_posts::Migration),
+      Box::new(m20250101_000003_create_index_cursor_pagination::Migration),
+      Box::new(m20250101_000004_create_table_comments::Migration),
+      Box::new(m20250101_000005_create_table_reactions::Migration),
+      Box::new(m20250101_000006_create_table_bookmarks::Migration),
+      Box::new(m20250101_000007_create_table_post_views::Migration),
+      Box::new(m20250101_000008_create_table_media::Migration),
+      Box::new(m20250101_000009_create_table_media_uploads::Migration),
+      Box::new(m20250101_000010_create_table_media_renditions::Migration),
+      Box::new(m20250101_000011_add_users_avatar::Migration),
+      Box::new(m20250101_000012_add_media_metadata::Migration),
+      Box::new(m20250101_000013_create_table_tags::Migration),
+      Box::new(m20250101_000014_add_posts_seo::Migration),
+      Box::new(m20250101_000015_create_table_follows::Migration),
+      Box::new(m20250101_000016_add_posts_visibility::Migration),
+      Box::new(m20250101_000017_create_table_post_previews::Migration),
+      Box::new(m20250101_000018_add_posts_published_at::Migration),
+      Box::new(m20250101_000019_create_table_post_trending::Migration),
+      Box::new(m20250101_000020_create_index_posts_title_search::Migration),
+      Box::new(m20250101_000021_create_table_post_placements::Migration),
+      Box::new(m20250101_000022_create_table_post_media::Migration),
+      Box::new(m20250101_000023_create_index_posts_published_at::Migration),
     ]
   }
 }
@@ -0,0 +1,68 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+  async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+    // Keyset pagination walks (created_at, id), so both listings need it indexed
+    manager
+      .create_index(
+        Index::create()
+          .name("idx_posts_created_at_id")
+          .table(Posts::Table)
+          .col(Posts::CreatedAt)
+          .col(Posts::Id)
+          .if_not_exists()
+          .to_owned(),
+      )
+      .await?;
+
+    manager
+      .create_index(
+        Index::create()
+          .name("idx_users_created_at_id")
+          .table(Users::Table)
+          .col(Users::CreatedAt)
+          .col(Users::Id)
+          .if_not_exists()
+          .to_owned(),
+      )
+      .await
+  }
+
+  async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+    manager
+      .drop_index(
+        Index::drop()
+          .name("idx_users_created_at_id")
+          .table(Users::Table)
+          .to_owned(),
+      )
+      .await?;
+
+    manager
+      .drop_index(
+        Index::drop()
+          .name("idx_posts_created_at_id")
+          .table(Posts::Table)
+          .to_owned(),
+      )
+      .await
+  }
+}
+
+#[derive(DeriveIden)]
+enum Posts {
+  Table,
+  Id,
+  CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum Users {
+  Table,
+  Id,
+  CreatedAt,
+}
@@ -0,0 +1,110 @@
+use sea_orm_migration::prelude::*;
+
+use super::m20250101_000001_create_table_users::Users;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+  async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+    manager
+      .create_table(
+        Table::create()
+          .table(Comments::Table)
+          .if_not_exists()
+          .col(
+            ColumnDef::new(Comments::Id)
+              .uuid()
+              .not_null()
+              .primary_key()
+              .default(Expr::cust("uuid_generate_v4()")),
+          )
+          .col(ColumnDef::new(Comments::PostId).uuid().not_null())
+          .col(ColumnDef::new(Comments::UserId).uuid().not_null())
+          .col(ColumnDef::new(Comments::ParentId).uuid().null())
+          .col(
+            ColumnDef::new(Comments::Depth)
+              .integer()
+              .not_null()
+              .default(0),
+          )
+          .col(ColumnDef::new(Comments::Content).text().not_null())
+          .col(
+            ColumnDef::new(Comments::CreatedAt)
+              .timestamp_with_time_zone()
+              .not_null()
+              .default(Expr::current_timestamp()),
+          )
+          .col(
+            ColumnDef::new(Comments::UpdatedAt)
+              .timestamp_with_time_zone()
+              .not_null()
+              .default(Expr::current_timestamp()),
+          )
+          .foreign_key(
+            ForeignKey::create()
+              .name("fk_comments_post_id")
+              .from(Comments::Table, Comments::PostId)
+              .to(Posts::Table, Posts::Id)
+              .on_delete(ForeignKeyAction::Cascade)
+              .on_update(ForeignKeyAction::Cascade),
+          )
+          .foreign_key(
+            ForeignKey::create()
+              .name("fk_comments_user_id")
+              .from(Comments::Table, Comments::UserId)
+              .to(Users::Table, Users::Id)
+              .on_delete(ForeignKeyAction::Cascade)
+              .on_update(ForeignKeyAction::Cascade),
+          )
+          .foreign_key(
+            ForeignKey::create()
+              .name("fk_comments_parent_id")
+              .from(Comments::Table, Comments::ParentId)
+              .to(Comments::Table, Comments::Id)
+              .on_delete(ForeignKeyAction::Cascade)
+              .on_update(ForeignKeyAction::Cascade),
+          )
+          .to_owned(),
+      )
+      .await?;
+
+    manager
+      .create_index(
+        Index::create()
+          .name("idx_comments_post_id_created_at")
+          .table(Comments::Table)
+          .col(Comments::PostId)
+          .col(Comments::CreatedAt)
+          .if_not_exists()
+          .to_owned(),
+      )
+      .await
+  }
+
+  async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+    manager
+      .drop_table(Table::drop().table(Comments::Table).to_owned())
+      .await
+  }
+}
+
+#[derive(DeriveIden)]
+pub enum Comments {
+  Table,
+  Id,
+  PostId,
+  UserId,
+  ParentId,
+  Depth,
+  Content,
+  CreatedAt,
+  UpdatedAt,
+}
+
+#[derive(DeriveIden)]
+enum Posts {
+  Table,
+  Id,
+}
@@ -0,0 +1,116 @@
+use sea_orm_migration::prelude::*;
+
+use super::m20250101_000001_create_table_users::Users;
+use super::m20250101_000004_create_table_comments::Comments;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+  async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+    // A reaction targets exactly one post or one comment. Keeping a real
+    // foreign key per target lets reactions cascade with what they react to.
+    manager
+      .create_table(
+        Table::create()
+          .table(Reactions::Table)
+          .if_not_exists()
+          .col(
+            ColumnDef::new(Reactions::Id)
+              .uuid()
+              .not_null()
+              .primary_key()
+              .default(Expr::cust("uuid_generate_v4()")),
+          )
+          .col(ColumnDef::new(Reactions::UserId).uuid().not_null())
+          .col(ColumnDef::new(Reactions::PostId).uuid().null())
+          .col(ColumnDef::new(Reactions::CommentId).uuid().null())
+          .col(ColumnDef::new(Reactions::Kind).string().not_null())
+          .col(
+            ColumnDef::new(Reactions::CreatedAt)
+              .timestamp_with_time_zone()
+              .not_null()
+              .default(Expr::current_timestamp()),
+          )
+          .check(Expr::cust("num_nonnulls(post_id, comment_id) = 1"))
+          .foreign_key(
+            ForeignKey::create()
+              .name("fk_reactions_user_id")
+              .from(Reactions::Table, Reactions::UserId)
+              .to(Users::Table, Users::Id)
+              .on_delete(ForeignKeyAction::Cascade)
+              .on_update(ForeignKeyAction::Cascade),
+          )
+          .foreign_key(
+            ForeignKey::create()
+              .name("fk_reactions_post_id")
+              .from(Reactions::Table, Reactions::PostId)
+              .to(Posts::Table, Posts::Id)
+              .on_delete(ForeignKeyAction::Cascade)
+              .on_update(ForeignKeyAction::Cascade),
+          )
+          .foreign_key(
+            ForeignKey::create()
+              .name("fk_reactions_comment_id")
+              .from(Reactions::Table, Reactions::CommentId)
+              .to(Comments::Table, Comments::Id)
+              .on_delete(ForeignKeyAction::Cascade)
+              .on_update(ForeignKeyAction::Cascade),
+          )
+          .to_owned(),
+      )
+      .await?;
+
+    // NULL targets never collide, so these also serve as the lookup
+    // indexes for counting per post and per comment.
+    manager
+      .create_index(
+        Index::create()
+          .name("idx_reactions_post_id_user_id")
+          .table(Reactions::Table)
+          .col(Reactions::PostId)
+          .col(Reactions::UserId)
+          .unique()
+          .if_not_exists()
+          .to_owned(),
+      )
+      .await?;
+
+    manager
+      .create_index(
+        Index::create()
+          .name("idx_reactions_comment_id_user_id")
+          .table(Reactions::Table)
+          .col(Reactions::CommentId)
+          .col(Reactions::UserId)
+          .unique()
+          .if_not_exists()
+          .to_owned(),
+      )
+      .await
+  }
+
+  async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+    manager
+      .drop_table(Table::drop().table(Reactions::Table).to_owned())
+      .await
+  }
+}
+
+#[derive(DeriveIden)]
+enum Reactions {
+  Table,
+  Id,
+  UserId,
+  PostId,
+  CommentId,
+  Kind,
+  CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum Posts {
+  Table,
+  Id,
+}
@@ -0,0 +1,181 @@
+use sea_orm_migration::prelude::*;
+
+use super::m20250101_000001_create_table_users::Users;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+  async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+    manager
+      .create_table(
+        Table::create()
+          .table(BookmarkCollections::Table)
+          .if_not_exists()
+          .col(
+            ColumnDef::new(BookmarkCollections::Id)
+              .uuid()
+              .not_null()
+              .primary_key()
+              .default(Expr::cust("uuid_generate_v4()")),
+          )
+          .col(
+            ColumnDef::new(BookmarkCollections::UserId)
+              .uuid()
+              .not_null(),
+          )
+          .col(
+            ColumnDef::new(BookmarkCollections::Name)
+              .string()
+              .not_null(),
+          )
+          .col(
+            ColumnDef::new(BookmarkCollections::CreatedAt)
+              .timestamp_with_time_zone()
+              .not_null()
+              .default(Expr::current_timestamp()),
+          )
+          .col(
+            ColumnDef::new(BookmarkCollections::UpdatedAt)
+              .timestamp_with_time_zone()
+              .not_null()
+              .default(Expr::current_timestamp()),
+          )
+          .foreign_key(
+            ForeignKey::create()
+              .name("fk_bookmark_collections_user_id")
+              .from(BookmarkCollections::Table, BookmarkCollections::UserId)
+              .to(Users::Table, Users::Id)
+              .on_delete(ForeignKeyAction::Cascade)
+              .on_update(ForeignKeyAction::Cascade),
+          )
+          .to_owned(),
+      )
+      .await?;
+
+    manager
+      .create_index(
+        Index::create()
+          .name("idx_bookmark_collections_user_id_name")
+          .table(BookmarkCollections::Table)
+          .col(BookmarkCollections::UserId)
+          .col(BookmarkCollections::Name)
+          .unique()
+          .if_not_exists()
+          .to_owned(),
+      )
+      .await?;
+
+    manager
+      .create_table(
+        Table::create()
+          .table(Bookmarks::Table)
+          .if_not_exists()
+          .col(
+            ColumnDef::new(Bookmarks::Id)
+              .uuid()
+              .not_null()
+              .primary_key()
+              .default(Expr::cust("uuid_generate_v4()")),
+          )
+          .col(ColumnDef::new(Bookmarks::UserId).uuid().not_null())
+          .col(ColumnDef::new(Bookmarks::PostId).uuid().not_null())
+          .col(ColumnDef::new(Bookmarks::CollectionId).uuid().null())
+          .col(
+            ColumnDef::new(Bookmarks::CreatedAt)
+              .timestamp_with_time_zone()
+              .not_null()
+              .default(Expr::current_timestamp()),
+          )
+          .foreign_key(
+            ForeignKey::create()
+              .name("fk_bookmarks_user_id")
+              .from(Bookmarks::Table, Bookmarks::UserId)
+              .to(Users::Table, Users::Id)
+              .on_delete(ForeignKeyAction::Cascade)
+              .on_update(ForeignKeyAction::Cascade),
+          )
+          .foreign_key(
+            ForeignKey::create()
+              .name("fk_bookmarks_post_id")
+              .from(Bookmarks::Table, Bookmarks::PostId)
+              .to(Posts::Table, Posts::Id)
+              .on_delete(ForeignKeyAction::Cascade)
+              .on_update(ForeignKeyAction::Cascade),
+          )
+          // Deleting a collection keeps its bookmarks, they just become unfiled
+          .foreign_key(
+            ForeignKey::create()
+              .name("fk_bookmarks_collection_id")
+              .from(Bookmarks::Table, Bookmarks::CollectionId)
+              .to(BookmarkCollections::Table, BookmarkCollections::Id)
+              .on_delete(ForeignKeyAction::SetNull)
+              .on_update(ForeignKeyAction::Cascade),
+          )
+          .to_owned(),
+      )
+      .await?;
+
+    manager
+      .create_index(
+        Index::create()
+          .name("idx_bookmarks_user_id_post_id")
+          .table(Bookmarks::Table)
+          .col(Bookmarks::UserId)
+          .col(Bookmarks::PostId)
+          .unique()
+          .if_not_exists()
+          .to_owned(),
+      )
+      .await?;
+
+    manager
+      .create_index(
+        Index::create()
+          .name("idx_bookmarks_user_id_created_at")
+          .table(Bookmarks::Table)
+          .col(Bookmarks::UserId)
+          .col(Bookmarks::CreatedAt)
+          .if_not_exists()
+          .to_owned(),
+      )
+      .await
+  }
+
+  async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+    manager
+      .drop_table(Table::drop().table(Bookmarks::Table).to_owned())
+      .await?;
+
+    manager
+      .drop_table(Table::drop().table(BookmarkCollections::Table).to_owned())
+      .await
+  }
+}
+
+#[derive(DeriveIden)]
+enum BookmarkCollections {
+  Table,
+  Id,
+  UserId,
+  Name,
+  CreatedAt,
+  UpdatedAt,
+}
+
+#[derive(DeriveIden)]
+enum Bookmarks {
+  Table,
+  Id,
+  UserId,
+  PostId,
+  CollectionId,
+  CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum Posts {
+  Table,
+  Id,
+}
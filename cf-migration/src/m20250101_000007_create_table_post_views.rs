@@ -0,0 +1,172 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+  async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+    manager
+      .alter_table(
+        Table::alter()
+          .table(Posts::Table)
+          .add_column(
+            ColumnDef::new(Posts::ViewCount)
+              .big_integer()
+              .not_null()
+              .default(0),
+          )
+          .to_owned(),
+      )
+      .await?;
+
+    // Raw views, one row per visitor fingerprint per dedup window
+    manager
+      .create_table(
+        Table::create()
+          .table(PostViews::Table)
+          .if_not_exists()
+          .col(
+            ColumnDef::new(PostViews::Id)
+              .uuid()
+              .not_null()
+              .primary_key()
+              .default(Expr::cust("uuid_generate_v4()")),
+          )
+          .col(ColumnDef::new(PostViews::PostId).uuid().not_null())
+          .col(
+            ColumnDef::new(PostViews::Fingerprint)
+              .string_len(64)
+              .not_null(),
+          )
+          .col(
+            ColumnDef::new(PostViews::WindowStart)
+              .timestamp_with_time_zone()
+              .not_null(),
+          )
+          .col(
+            ColumnDef::new(PostViews::ViewedAt)
+              .timestamp_with_time_zone()
+              .not_null()
+              .default(Expr::current_timestamp()),
+          )
+          .foreign_key(
+            ForeignKey::create()
+              .name("fk_post_views_post_id")
+              .from(PostViews::Table, PostViews::PostId)
+              .to(Posts::Table, Posts::Id)
+              .on_delete(ForeignKeyAction::Cascade)
+              .on_update(ForeignKeyAction::Cascade),
+          )
+          .to_owned(),
+      )
+      .await?;
+
+    manager
+      .create_index(
+        Index::create()
+          .name("idx_post_views_dedup")
+          .table(PostViews::Table)
+          .col(PostViews::PostId)
+          .col(PostViews::Fingerprint)
+          .col(PostViews::WindowStart)
+          .unique()
+          .if_not_exists()
+          .to_owned(),
+      )
+      .await?;
+
+    manager
+      .create_index(
+        Index::create()
+          .name("idx_post_views_viewed_at")
+          .table(PostViews::Table)
+          .col(PostViews::ViewedAt)
+          .if_not_exists()
+          .to_owned(),
+      )
+      .await?;
+
+    // Daily rollup, rebuilt from post_views by the rollup job
+    manager
+      .create_table(
+        Table::create()
+          .table(PostViewDaily::Table)
+          .if_not_exists()
+          .col(ColumnDef::new(PostViewDaily::PostId).uuid().not_null())
+          .col(ColumnDef::new(PostViewDaily::Day).date().not_null())
+          .col(
+            ColumnDef::new(PostViewDaily::Views)
+              .big_integer()
+              .not_null()
+              .default(0),
+          )
+          .col(
+            ColumnDef::new(PostViewDaily::UniqueVisitors)
+              .big_integer()
+              .not_null()
+              .default(0),
+          )
+          .primary_key(
+            Index::create()
+              .col(PostViewDaily::PostId)
+              .col(PostViewDaily::Day),
+          )
+          .foreign_key(
+            ForeignKey::create()
+              .name("fk_post_view_daily_post_id")
+              .from(PostViewDaily::Table, PostViewDaily::PostId)
+              .to(Posts::Table, Posts::Id)
+              .on_delete(ForeignKeyAction::Cascade)
+              .on_update(ForeignKeyAction::Cascade),
+          )
+          .to_owned(),
+      )
+      .await
+  }
+
+  async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+    manager
+      .drop_table(Table::drop().table(PostViewDaily::Table).to_owned())
+      .await?;
+
+    manager
+      .drop_table(Table::drop().table(PostViews::Table).to_owned())
+      .await?;
+
+    manager
+      .alter_table(
+        Table::alter()
+          .table(Posts::Table)
+          .drop_column(Posts::ViewCount)
+          .to_owned(),
+      )
+      .await
+  }
+}
+
+#[derive(DeriveIden)]
+enum Posts {
+  Table,
+  Id,
+  ViewCount,
+}
+
+#[derive(DeriveIden)]
+enum PostViews {
+  Table,
+  Id,
+  PostId,
+  Fingerprint,
+  WindowStart,
+  ViewedAt,
+}
+
+#[derive(DeriveIden)]
+enum PostViewDaily {
+  Table,
+  PostId,
+  Day,
+  Views,
+  UniqueVisitors,
+}
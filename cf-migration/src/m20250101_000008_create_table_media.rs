@@ -0,0 +1,118 @@
+use sea_orm_migration::prelude::*;
+
+use super::m20250101_000001_create_table_users::Users;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+  async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+    manager
+      .create_table(
+        Table::create()
+          .table(Media::Table)
+          .if_not_exists()
+          .col(
+            ColumnDef::new(Media::Id)
+              .uuid()
+              .not_null()
+              .primary_key()
+              .default(Expr::cust("uuid_generate_v4()")),
+          )
+          .col(ColumnDef::new(Media::UserId).uuid().not_null())
+          .col(
+            ColumnDef::new(Media::StorageKey)
+              .string()
+              .not_null()
+              .unique_key(),
+          )
+          .col(ColumnDef::new(Media::MimeType).string().not_null())
+          .col(ColumnDef::new(Media::SizeBytes).big_integer().not_null())
+          .col(ColumnDef::new(Media::Width).integer().null())
+          .col(ColumnDef::new(Media::Height).integer().null())
+          .col(
+            ColumnDef::new(Media::CreatedAt)
+              .timestamp_with_time_zone()
+              .not_null()
+              .default(Expr::current_timestamp()),
+          )
+          .foreign_key(
+            ForeignKey::create()
+              .name("fk_media_user_id")
+              .from(Media::Table, Media::UserId)
+              .to(Users::Table, Users::Id)
+              .on_delete(ForeignKeyAction::Cascade)
+              .on_update(ForeignKeyAction::Cascade),
+          )
+          .to_owned(),
+      )
+      .await?;
+
+    manager
+      .create_index(
+        Index::create()
+          .name("idx_media_user_id_created_at")
+          .table(Media::Table)
+          .col(Media::UserId)
+          .col(Media::CreatedAt)
+          .if_not_exists()
+          .to_owned(),
+      )
+      .await?;
+
+    manager
+      .alter_table(
+        Table::alter()
+          .table(Posts::Table)
+          .add_column(ColumnDef::new(Posts::HeaderImageId).uuid().null())
+          .add_foreign_key(
+            TableForeignKey::new()
+              .name("fk_posts_header_image_id")
+              .from_tbl(Posts::Table)
+              .from_col(Posts::HeaderImageId)
+              .to_tbl(Media::Table)
+              .to_col(Media::Id)
+              .on_delete(ForeignKeyAction::SetNull)
+              .on_update(ForeignKeyAction::Cascade),
+          )
+          .to_owned(),
+      )
+      .await
+  }
+
+  async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+    manager
+      .alter_table(
+        Table::alter()
+          .table(Posts::Table)
+          .drop_foreign_key(Alias::new("fk_posts_header_image_id"))
+          .drop_column(Posts::HeaderImageId)
+          .to_owned(),
+      )
+      .await?;
+
+    manager
+      .drop_table(Table::drop().table(Media::Table).to_owned())
+      .await
+  }
+}
+
+#[derive(DeriveIden)]
+enum Posts {
+  Table,
+  HeaderImageId,
+}
+
+#[derive(DeriveIden)]
+pub enum Media {
+  Table,
+  Id,
+  UserId,
+  StorageKey,
+  MimeType,
+  SizeBytes,
+  Width,
+  Height,
+  CreatedAt,
+}
@@ -0,0 +1,89 @@
+use sea_orm_migration::prelude::*;
+
+use super::m20250101_000001_create_table_users::Users;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+  async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+    // Direct uploads handed out but not completed yet
+    manager
+      .create_table(
+        Table::create()
+          .table(MediaUploads::Table)
+          .if_not_exists()
+          .col(
+            ColumnDef::new(MediaUploads::Id)
+              .uuid()
+              .not_null()
+              .primary_key()
+              .default(Expr::cust("uuid_generate_v4()")),
+          )
+          .col(ColumnDef::new(MediaUploads::UserId).uuid().not_null())
+          .col(
+            ColumnDef::new(MediaUploads::StorageKey)
+              .string()
+              .not_null()
+              .unique_key(),
+          )
+          .col(ColumnDef::new(MediaUploads::MimeType).string().not_null())
+          .col(
+            ColumnDef::new(MediaUploads::SizeBytes)
+              .big_integer()
+              .not_null(),
+          )
+          .col(
+            ColumnDef::new(MediaUploads::ExpiresAt)
+              .timestamp_with_time_zone()
+              .not_null(),
+          )
+          .col(
+            ColumnDef::new(MediaUploads::CreatedAt)
+              .timestamp_with_time_zone()
+              .not_null()
+              .default(Expr::current_timestamp()),
+          )
+          .foreign_key(
+            ForeignKey::create()
+              .name("fk_media_uploads_user_id")
+              .from(MediaUploads::Table, MediaUploads::UserId)
+              .to(Users::Table, Users::Id)
+              .on_delete(ForeignKeyAction::Cascade)
+              .on_update(ForeignKeyAction::Cascade),
+          )
+          .to_owned(),
+      )
+      .await?;
+
+    manager
+      .create_index(
+        Index::create()
+          .name("idx_media_uploads_expires_at")
+          .table(MediaUploads::Table)
+          .col(MediaUploads::ExpiresAt)
+          .if_not_exists()
+          .to_owned(),
+      )
+      .await
+  }
+
+  async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+    manager
+      .drop_table(Table::drop().table(MediaUploads::Table).to_owned())
+      .await
+  }
+}
+
+#[derive(DeriveIden)]
+enum MediaUploads {
+  Table,
+  Id,
+  UserId,
+  StorageKey,
+  MimeType,
+  SizeBytes,
+  ExpiresAt,
+  CreatedAt,
+}
@@ -0,0 +1,122 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+  async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+    // Media uploaded before processing existed has nothing to wait for
+    manager
+      .alter_table(
+        Table::alter()
+          .table(Media::Table)
+          .add_column(
+            ColumnDef::new(Media::Status)
+              .string()
+              .not_null()
+              .default("READY"),
+          )
+          .to_owned(),
+      )
+      .await?;
+
+    manager
+      .create_table(
+        Table::create()
+          .table(MediaRenditions::Table)
+          .if_not_exists()
+          .col(
+            ColumnDef::new(MediaRenditions::Id)
+              .uuid()
+              .not_null()
+              .primary_key()
+              .default(Expr::cust("uuid_generate_v4()")),
+          )
+          .col(ColumnDef::new(MediaRenditions::MediaId).uuid().not_null())
+          .col(ColumnDef::new(MediaRenditions::Name).string().not_null())
+          .col(
+            ColumnDef::new(MediaRenditions::StorageKey)
+              .string()
+              .not_null(),
+          )
+          .col(
+            ColumnDef::new(MediaRenditions::MimeType)
+              .string()
+              .not_null(),
+          )
+          .col(
+            ColumnDef::new(MediaRenditions::SizeBytes)
+              .big_integer()
+              .not_null(),
+          )
+          .col(ColumnDef::new(MediaRenditions::Width).integer().not_null())
+          .col(ColumnDef::new(MediaRenditions::Height).integer().not_null())
+          .col(
+            ColumnDef::new(MediaRenditions::CreatedAt)
+              .timestamp_with_time_zone()
+              .not_null()
+              .default(Expr::current_timestamp()),
+          )
+          .foreign_key(
+            ForeignKey::create()
+              .name("fk_media_renditions_media_id")
+              .from(MediaRenditions::Table, MediaRenditions::MediaId)
+              .to(Media::Table, Media::Id)
+              .on_delete(ForeignKeyAction::Cascade)
+              .on_update(ForeignKeyAction::Cascade),
+          )
+          .to_owned(),
+      )
+      .await?;
+
+    manager
+      .create_index(
+        Index::create()
+          .name("idx_media_renditions_media_id_name")
+          .table(MediaRenditions::Table)
+          .col(MediaRenditions::MediaId)
+          .col(MediaRenditions::Name)
+          .unique()
+          .if_not_exists()
+          .to_owned(),
+      )
+      .await
+  }
+
+  async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+    manager
+      .drop_table(Table::drop().table(MediaRenditions::Table).to_owned())
+      .await?;
+
+    manager
+      .alter_table(
+        Table::alter()
+          .table(Media::Table)
+          .drop_column(Media::Status)
+          .to_owned(),
+      )
+      .await
+  }
+}
+
+#[derive(DeriveIden)]
+enum Media {
+  Table,
+  Id,
+  Status,
+}
+
+#[derive(DeriveIden)]
+enum MediaRenditions {
+  Table,
+  Id,
+  MediaId,
+  Name,
+  StorageKey,
+  MimeType,
+  SizeBytes,
+  Width,
+  Height,
+  CreatedAt,
+}
@@ -0,0 +1,108 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+  async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+    manager
+      .alter_table(
+        Table::alter()
+          .table(Media::Table)
+          .add_column(ColumnDef::new(Media::AltText).text().null())
+          .add_column(ColumnDef::new(Media::Caption).text().null())
+          .add_column(ColumnDef::new(Media::Credit).string().null())
+          .to_owned(),
+      )
+      .await?;
+
+    manager
+      .create_index(
+        Index::create()
+          .name("idx_media_created_at_id")
+          .table(Media::Table)
+          .col(Media::CreatedAt)
+          .col(Media::Id)
+          .if_not_exists()
+          .to_owned(),
+      )
+      .await?;
+
+    // Media still used as a header image must not disappear from under the
+    // post; NO ACTION still lets a user delete cascade through both tables
+    replace_header_image_fk(manager, ForeignKeyAction::NoAction).await
+  }
+
+  async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+    replace_header_image_fk(manager, ForeignKeyAction::SetNull).await?;
+
+    manager
+      .drop_index(
+        Index::drop()
+          .name("idx_media_created_at_id")
+          .table(Media::Table)
+          .to_owned(),
+      )
+      .await?;
+
+    manager
+      .alter_table(
+        Table::alter()
+          .table(Media::Table)
+          .drop_column(Media::AltText)
+          .drop_column(Media::Caption)
+          .drop_column(Media::Credit)
+          .to_owned(),
+      )
+      .await
+  }
+}
+
+async fn replace_header_image_fk(
+  manager: &SchemaManager<'_>,
+  on_delete: ForeignKeyAction,
+) -> Result<(), DbErr> {
+  manager
+    .alter_table(
+      Table::alter()
+        .table(Posts::Table)
+        .drop_foreign_key(Alias::new("fk_posts_header_image_id"))
+        .to_owned(),
+    )
+    .await?;
+
+  manager
+    .alter_table(
+      Table::alter()
+        .table(Posts::Table)
+        .add_foreign_key(
+          TableForeignKey::new()
+            .name("fk_posts_header_image_id")
+            .from_tbl(Posts::Table)
+            .from_col(Posts::HeaderImageId)
+            .to_tbl(Media::Table)
+            .to_col(Media::Id)
+            .on_delete(on_delete)
+            .on_update(ForeignKeyAction::Cascade),
+        )
+        .to_owned(),
+    )
+    .await
+}
+
+#[derive(DeriveIden)]
+enum Media {
+  Table,
+  Id,
+  AltText,
+  Caption,
+  Credit,
+  CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum Posts {
+  Table,
+  HeaderImageId,
+}
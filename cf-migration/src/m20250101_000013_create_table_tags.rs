@@ -0,0 +1,110 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+  async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+    manager
+      .create_table(
+        Table::create()
+          .table(Tags::Table)
+          .if_not_exists()
+          .col(
+            ColumnDef::new(Tags::Id)
+              .uuid()
+              .not_null()
+              .primary_key()
+              .default(Expr::cust("uuid_generate_v4()")),
+          )
+          .col(ColumnDef::new(Tags::Name).string().not_null())
+          .col(ColumnDef::new(Tags::Slug).string().not_null().unique_key())
+          .col(
+            ColumnDef::new(Tags::CreatedAt)
+              .timestamp_with_time_zone()
+              .not_null()
+              .default(Expr::current_timestamp()),
+          )
+          .to_owned(),
+      )
+      .await?;
+
+    manager
+      .create_table(
+        Table::create()
+          .table(PostTags::Table)
+          .if_not_exists()
+          .col(ColumnDef::new(PostTags::PostId).uuid().not_null())
+          .col(ColumnDef::new(PostTags::TagId).uuid().not_null())
+          .primary_key(
+            Index::create()
+              .name("pk_post_tags")
+              .col(PostTags::PostId)
+              .col(PostTags::TagId),
+          )
+          .foreign_key(
+            ForeignKey::create()
+              .name("fk_post_tags_post_id")
+              .from(PostTags::Table, PostTags::PostId)
+              .to(Posts::Table, Posts::Id)
+              .on_delete(ForeignKeyAction::Cascade)
+              .on_update(ForeignKeyAction::Cascade),
+          )
+          .foreign_key(
+            ForeignKey::create()
+              .name("fk_post_tags_tag_id")
+              .from(PostTags::Table, PostTags::TagId)
+              .to(Tags::Table, Tags::Id)
+              .on_delete(ForeignKeyAction::Cascade)
+              .on_update(ForeignKeyAction::Cascade),
+          )
+          .to_owned(),
+      )
+      .await?;
+
+    // Tag pages and feeds look posts up by tag
+    manager
+      .create_index(
+        Index::create()
+          .name("idx_post_tags_tag_id")
+          .table(PostTags::Table)
+          .col(PostTags::TagId)
+          .if_not_exists()
+          .to_owned(),
+      )
+      .await
+  }
+
+  async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+    manager
+      .drop_table(Table::drop().table(PostTags::Table).to_owned())
+      .await?;
+
+    manager
+      .drop_table(Table::drop().table(Tags::Table).to_owned())
+      .await
+  }
+}
+
+#[derive(DeriveIden)]
+enum Tags {
+  Table,
+  Id,
+  Name,
+  Slug,
+  CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum PostTags {
+  Table,
+  PostId,
+  TagId,
+}
+
+#[derive(DeriveIden)]
+enum Posts {
+  Table,
+  Id,
+}
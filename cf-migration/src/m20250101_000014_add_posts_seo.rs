@@ -0,0 +1,61 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+  async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+    manager
+      .alter_table(
+        Table::alter()
+          .table(Posts::Table)
+          .add_column(ColumnDef::new(Posts::MetaTitle).string().null())
+          .add_column(ColumnDef::new(Posts::MetaDescription).text().null())
+          .add_column(ColumnDef::new(Posts::CanonicalUrl).string().null())
+          .add_column(ColumnDef::new(Posts::SocialImageId).uuid().null())
+          .add_foreign_key(
+            TableForeignKey::new()
+              .name("fk_posts_social_image_id")
+              .from_tbl(Posts::Table)
+              .from_col(Posts::SocialImageId)
+              .to_tbl(Media::Table)
+              .to_col(Media::Id)
+              .on_delete(ForeignKeyAction::NoAction)
+              .on_update(ForeignKeyAction::Cascade),
+          )
+          .to_owned(),
+      )
+      .await
+  }
+
+  async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+    manager
+      .alter_table(
+        Table::alter()
+          .table(Posts::Table)
+          .drop_foreign_key(Alias::new("fk_posts_social_image_id"))
+          .drop_column(Posts::MetaTitle)
+          .drop_column(Posts::MetaDescription)
+          .drop_column(Posts::CanonicalUrl)
+          .drop_column(Posts::SocialImageId)
+          .to_owned(),
+      )
+      .await
+  }
+}
+
+#[derive(DeriveIden)]
+enum Posts {
+  Table,
+  MetaTitle,
+  MetaDescription,
+  CanonicalUrl,
+  SocialImageId,
+}
+
+#[derive(DeriveIden)]
+enum Media {
+  Table,
+  Id,
+}
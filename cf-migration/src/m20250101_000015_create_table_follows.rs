@@ -0,0 +1,76 @@
+use sea_orm_migration::prelude::*;
+
+use super::m20250101_000001_create_table_users::Users;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+  async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+    manager
+      .create_table(
+        Table::create()
+          .table(Follows::Table)
+          .if_not_exists()
+          .col(ColumnDef::new(Follows::FollowerId).uuid().not_null())
+          .col(ColumnDef::new(Follows::FolloweeId).uuid().not_null())
+          .col(
+            ColumnDef::new(Follows::CreatedAt)
+              .timestamp_with_time_zone()
+              .not_null()
+              .default(Expr::current_timestamp()),
+          )
+          .primary_key(
+            Index::create()
+              .name("pk_follows")
+              .col(Follows::FollowerId)
+              .col(Follows::FolloweeId),
+          )
+          .foreign_key(
+            ForeignKey::create()
+              .name("fk_follows_follower_id")
+              .from(Follows::Table, Follows::FollowerId)
+              .to(Users::Table, Users::Id)
+              .on_delete(ForeignKeyAction::Cascade)
+              .on_update(ForeignKeyAction::Cascade),
+          )
+          .foreign_key(
+            ForeignKey::create()
+              .name("fk_follows_followee_id")
+              .from(Follows::Table, Follows::FolloweeId)
+              .to(Users::Table, Users::Id)
+              .on_delete(ForeignKeyAction::Cascade)
+              .on_update(ForeignKeyAction::Cascade),
+          )
+          .to_owned(),
+      )
+      .await?;
+
+    // Follower lists and counts look rows up by the followed user
+    manager
+      .create_index(
+        Index::create()
+          .name("idx_follows_followee_id")
+          .table(Follows::Table)
+          .col(Follows::FolloweeId)
+          .if_not_exists()
+          .to_owned(),
+      )
+      .await
+  }
+
+  async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+    manager
+      .drop_table(Table::drop().table(Follows::Table).to_owned())
+      .await
+  }
+}
+
+#[derive(DeriveIden)]
+enum Follows {
+  Table,
+  FollowerId,
+  FolloweeId,
+  CreatedAt,
+}
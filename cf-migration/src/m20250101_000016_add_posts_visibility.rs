@@ -0,0 +1,63 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+  async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+    manager
+      .alter_table(
+        Table::alter()
+          .table(Posts::Table)
+          .add_column(
+            ColumnDef::new(Posts::Visibility)
+              .string()
+              .not_null()
+              .default("PUBLIC"),
+          )
+          .to_owned(),
+      )
+      .await?;
+
+    // Public listings filter on both before ordering
+    manager
+      .create_index(
+        Index::create()
+          .name("idx_posts_status_visibility")
+          .table(Posts::Table)
+          .col(Posts::Status)
+          .col(Posts::Visibility)
+          .if_not_exists()
+          .to_owned(),
+      )
+      .await
+  }
+
+  async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+    manager
+      .drop_index(
+        Index::drop()
+          .name("idx_posts_status_visibility")
+          .table(Posts::Table)
+          .to_owned(),
+      )
+      .await?;
+
+    manager
+      .alter_table(
+        Table::alter()
+          .table(Posts::Table)
+          .drop_column(Posts::Visibility)
+          .to_owned(),
+      )
+      .await
+  }
+}
+
+#[derive(DeriveIden)]
+enum Posts {
+  Table,
+  Status,
+  Visibility,
+}
@@ -0,0 +1,102 @@
+use sea_orm_migration::prelude::*;
+
+use super::m20250101_000001_create_table_users::Users;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+  async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+    // Shareable draft links, each holding the revision it was created for
+    manager
+      .create_table(
+        Table::create()
+          .table(PostPreviews::Table)
+          .if_not_exists()
+          .col(
+            ColumnDef::new(PostPreviews::Id)
+              .uuid()
+              .not_null()
+              .primary_key()
+              .default(Expr::cust("uuid_generate_v4()")),
+          )
+          .col(ColumnDef::new(PostPreviews::PostId).uuid().not_null())
+          .col(ColumnDef::new(PostPreviews::CreatedBy).uuid().not_null())
+          .col(ColumnDef::new(PostPreviews::Title).string().not_null())
+          .col(ColumnDef::new(PostPreviews::Content).text().not_null())
+          .col(
+            ColumnDef::new(PostPreviews::Revision)
+              .timestamp_with_time_zone()
+              .not_null(),
+          )
+          .col(
+            ColumnDef::new(PostPreviews::ExpiresAt)
+              .timestamp_with_time_zone()
+              .not_null(),
+          )
+          .col(ColumnDef::new(PostPreviews::RevokedAt).timestamp_with_time_zone())
+          .col(
+            ColumnDef::new(PostPreviews::CreatedAt)
+              .timestamp_with_time_zone()
+              .not_null()
+              .default(Expr::current_timestamp()),
+          )
+          .foreign_key(
+            ForeignKey::create()
+              .name("fk_post_previews_post_id")
+              .from(PostPreviews::Table, PostPreviews::PostId)
+              .to(Posts::Table, Posts::Id)
+              .on_delete(ForeignKeyAction::Cascade)
+              .on_update(ForeignKeyAction::Cascade),
+          )
+          .foreign_key(
+            ForeignKey::create()
+              .name("fk_post_previews_created_by")
+              .from(PostPreviews::Table, PostPreviews::CreatedBy)
+              .to(Users::Table, Users::Id)
+              .on_delete(ForeignKeyAction::Cascade)
+              .on_update(ForeignKeyAction::Cascade),
+          )
+          .to_owned(),
+      )
+      .await?;
+
+    manager
+      .create_index(
+        Index::create()
+          .name("idx_post_previews_post_id")
+          .table(PostPreviews::Table)
+          .col(PostPreviews::PostId)
+          .if_not_exists()
+          .to_owned(),
+      )
+      .await
+  }
+
+  async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+    manager
+      .drop_table(Table::drop().table(PostPreviews::Table).to_owned())
+      .await
+  }
+}
+
+#[derive(DeriveIden)]
+enum PostPreviews {
+  Table,
+  Id,
+  PostId,
+  CreatedBy,
+  Title,
+  Content,
+  Revision,
+  ExpiresAt,
+  RevokedAt,
+  CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum Posts {
+  Table,
+  Id,
+}
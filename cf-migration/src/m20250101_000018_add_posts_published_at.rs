@@ -0,0 +1,73 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+  async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+    manager
+      .alter_table(
+        Table::alter()
+          .table(Posts::Table)
+          .add_column(ColumnDef::new(Posts::PublishedAt).timestamp_with_time_zone())
+          .to_owned(),
+      )
+      .await?;
+
+    // Creation time is the best guess for posts published before the column
+    manager
+      .exec_stmt(
+        Query::update()
+          .table(Posts::Table)
+          .value(Posts::PublishedAt, Expr::col(Posts::CreatedAt))
+          .and_where(Expr::col(Posts::Status).eq("PUBLISHED"))
+          .to_owned(),
+      )
+      .await?;
+
+    // Author feeds read the newest posts of one author
+    manager
+      .create_index(
+        Index::create()
+          .name("idx_posts_user_id_published_at")
+          .table(Posts::Table)
+          .col(Posts::UserId)
+          .col(Posts::PublishedAt)
+          .col(Posts::Id)
+          .if_not_exists()
+          .to_owned(),
+      )
+      .await
+  }
+
+  async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+    manager
+      .drop_index(
+        Index::drop()
+          .name("idx_posts_user_id_published_at")
+          .table(Posts::Table)
+          .to_owned(),
+      )
+      .await?;
+
+    manager
+      .alter_table(
+        Table::alter()
+          .table(Posts::Table)
+          .drop_column(Posts::PublishedAt)
+          .to_owned(),
+      )
+      .await
+  }
+}
+
+#[derive(DeriveIden)]
+enum Posts {
+  Table,
+  Id,
+  UserId,
+  Status,
+  PublishedAt,
+  CreatedAt,
+}
@@ -0,0 +1,75 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+  async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+    // Precomputed rankings, one set of rows per period
+    manager
+      .create_table(
+        Table::create()
+          .table(PostTrending::Table)
+          .if_not_exists()
+          .col(ColumnDef::new(PostTrending::Period).string().not_null())
+          .col(ColumnDef::new(PostTrending::PostId).uuid().not_null())
+          .col(ColumnDef::new(PostTrending::Score).double().not_null())
+          .col(
+            ColumnDef::new(PostTrending::ComputedAt)
+              .timestamp_with_time_zone()
+              .not_null(),
+          )
+          .primary_key(
+            Index::create()
+              .name("pk_post_trending")
+              .col(PostTrending::Period)
+              .col(PostTrending::PostId),
+          )
+          .foreign_key(
+            ForeignKey::create()
+              .name("fk_post_trending_post_id")
+              .from(PostTrending::Table, PostTrending::PostId)
+              .to(Posts::Table, Posts::Id)
+              .on_delete(ForeignKeyAction::Cascade)
+              .on_update(ForeignKeyAction::Cascade),
+          )
+          .to_owned(),
+      )
+      .await?;
+
+    // The endpoint reads one period highest score first
+    manager
+      .create_index(
+        Index::create()
+          .name("idx_post_trending_period_score")
+          .table(PostTrending::Table)
+          .col(PostTrending::Period)
+          .col(PostTrending::Score)
+          .if_not_exists()
+          .to_owned(),
+      )
+      .await
+  }
+
+  async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+    manager
+      .drop_table(Table::drop().table(PostTrending::Table).to_owned())
+      .await
+  }
+}
+
+#[derive(DeriveIden)]
+enum PostTrending {
+  Table,
+  Period,
+  PostId,
+  Score,
+  ComputedAt,
+}
+
+#[derive(DeriveIden)]
+enum Posts {
+  Table,
+  Id,
+}
@@ -0,0 +1,28 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+  async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+    // Related posts match titles with `to_tsvector('english', title) @@ ...`,
+    // which only uses an index built on the same expression
+    manager
+      .get_connection()
+      .execute_unprepared(
+        "CREATE INDEX IF NOT EXISTS idx_posts_title_tsv \
+         ON posts USING GIN (to_tsvector('english', title))",
+      )
+      .await
+      .map(|_| ())
+  }
+
+  async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+    manager
+      .get_connection()
+      .execute_unprepared("DROP INDEX IF EXISTS idx_posts_title_tsv")
+      .await
+      .map(|_| ())
+  }
+}
@@ -0,0 +1,95 @@
+use sea_orm_migration::prelude::*;
+
+use super::m20250101_000001_create_table_users::Users;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+  async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+    // Editor curated slots: pinned to the top of lists or featured in the
+    // carousel, each ordered by position within its kind
+    manager
+      .create_table(
+        Table::create()
+          .table(PostPlacements::Table)
+          .if_not_exists()
+          .col(ColumnDef::new(PostPlacements::Kind).string().not_null())
+          .col(ColumnDef::new(PostPlacements::PostId).uuid().not_null())
+          .col(
+            ColumnDef::new(PostPlacements::Position)
+              .integer()
+              .not_null(),
+          )
+          .col(ColumnDef::new(PostPlacements::ExpiresAt).timestamp_with_time_zone())
+          .col(ColumnDef::new(PostPlacements::CreatedBy).uuid().not_null())
+          .col(
+            ColumnDef::new(PostPlacements::CreatedAt)
+              .timestamp_with_time_zone()
+              .not_null()
+              .default(Expr::current_timestamp()),
+          )
+          .primary_key(
+            Index::create()
+              .name("pk_post_placements")
+              .col(PostPlacements::Kind)
+              .col(PostPlacements::PostId),
+          )
+          .foreign_key(
+            ForeignKey::create()
+              .name("fk_post_placements_post_id")
+              .from(PostPlacements::Table, PostPlacements::PostId)
+              .to(Posts::Table, Posts::Id)
+              .on_delete(ForeignKeyAction::Cascade)
+              .on_update(ForeignKeyAction::Cascade),
+          )
+          .foreign_key(
+            ForeignKey::create()
+              .name("fk_post_placements_created_by")
+              .from(PostPlacements::Table, PostPlacements::CreatedBy)
+              .to(Users::Table, Users::Id)
+              .on_delete(ForeignKeyAction::Cascade)
+              .on_update(ForeignKeyAction::Cascade),
+          )
+          .to_owned(),
+      )
+      .await?;
+
+    // Lists read one kind in position order
+    manager
+      .create_index(
+        Index::create()
+          .name("idx_post_placements_kind_position")
+          .table(PostPlacements::Table)
+          .col(PostPlacements::Kind)
+          .col(PostPlacements::Position)
+          .if_not_exists()
+          .to_owned(),
+      )
+      .await
+  }
+
+  async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+    manager
+      .drop_table(Table::drop().table(PostPlacements::Table).to_owned())
+      .await
+  }
+}
+
+#[derive(DeriveIden)]
+enum PostPlacements {
+  Table,
+  Kind,
+  PostId,
+  Position,
+  ExpiresAt,
+  CreatedBy,
+  CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum Posts {
+  Table,
+  Id,
+}
@@ -0,0 +1,96 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+  async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+    // Media embedded in post content. Like header and social images it can't
+    // be deleted while a post still uses it
+    manager
+      .create_table(
+        Table::create()
+          .table(PostMedia::Table)
+          .if_not_exists()
+          .col(ColumnDef::new(PostMedia::PostId).uuid().not_null())
+          .col(ColumnDef::new(PostMedia::MediaId).uuid().not_null())
+          .primary_key(
+            Index::create()
+              .name("pk_post_media")
+              .col(PostMedia::PostId)
+              .col(PostMedia::MediaId),
+          )
+          .foreign_key(
+            ForeignKey::create()
+              .name("fk_post_media_post_id")
+              .from(PostMedia::Table, PostMedia::PostId)
+              .to(Posts::Table, Posts::Id)
+              .on_delete(ForeignKeyAction::Cascade)
+              .on_update(ForeignKeyAction::Cascade),
+          )
+          .foreign_key(
+            ForeignKey::create()
+              .name("fk_post_media_media_id")
+              .from(PostMedia::Table, PostMedia::MediaId)
+              .to(Media::Table, Media::Id)
+              .on_delete(ForeignKeyAction::Restrict)
+              .on_update(ForeignKeyAction::Cascade),
+          )
+          .to_owned(),
+      )
+      .await?;
+
+    // Reference counts look posts up by media
+    manager
+      .create_index(
+        Index::create()
+          .name("idx_post_media_media_id")
+          .table(PostMedia::Table)
+          .col(PostMedia::MediaId)
+          .if_not_exists()
+          .to_owned(),
+      )
+      .await?;
+
+    // Until they are saved again, existing posts keep any media of their
+    // author whose id appears anywhere in the content
+    manager
+      .get_connection()
+      .execute_unprepared(
+        "INSERT INTO post_media (post_id, media_id) \
+         SELECT DISTINCT p.id, m.id FROM posts p \
+         CROSS JOIN LATERAL regexp_matches(p.content, \
+           '[0-9a-f]{8}-[0-9a-f]{4}-[0-9a-f]{4}-[0-9a-f]{4}-[0-9a-f]{12}', 'gi') AS found(ids) \
+         JOIN media m ON m.id = found.ids[1]::uuid AND m.user_id = p.user_id \
+         ON CONFLICT DO NOTHING",
+      )
+      .await
+      .map(|_| ())
+  }
+
+  async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+    manager
+      .drop_table(Table::drop().table(PostMedia::Table).to_owned())
+      .await
+  }
+}
+
+#[derive(DeriveIden)]
+enum PostMedia {
+  Table,
+  PostId,
+  MediaId,
+}
+
+#[derive(DeriveIden)]
+enum Posts {
+  Table,
+  Id,
+}
+
+#[derive(DeriveIden)]
+enum Media {
+  Table,
+  Id,
+}
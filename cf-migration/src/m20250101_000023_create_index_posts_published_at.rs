@@ -0,0 +1,41 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+  async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+    // Timelines merge many authors newest first, which (user_id, published_at)
+    // cannot return in order; walking this one backwards needs no sort
+    manager
+      .create_index(
+        Index::create()
+          .name("idx_posts_published_at_id")
+          .table(Posts::Table)
+          .col(Posts::PublishedAt)
+          .col(Posts::Id)
+          .if_not_exists()
+          .to_owned(),
+      )
+      .await
+  }
+
+  async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+    manager
+      .drop_index(
+        Index::drop()
+          .name("idx_posts_published_at_id")
+          .table(Posts::Table)
+          .to_owned(),
+      )
+      .await
+  }
+}
+
+#[derive(DeriveIden)]
+enum Posts {
+  Table,
+  PublishedAt,
+  Id,
+}
@@ -1,2 +1,3 @@
 pub mod generator;
+pub mod pagination;
 pub mod parser_converter;
\ No newline at end of file
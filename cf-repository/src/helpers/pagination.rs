@@ -0,0 +1,128 @@
+use sea_orm::prelude::{DateTimeWithTimeZone, Expr, Uuid};
+use sea_orm::*;
+
+use crate::structs::pagination::{Cursor, CursorDirection, CursorPage, OffsetPage};
+
+/// Run an offset query for a 1-based `page`.
+///
+/// Out of range input is clamped rather than trusted: page 0 reads the first
+/// page and a zero page size reads one row, so callers cannot trigger the
+/// underflow or division by zero inside the paginator.
+pub async fn fetch_offset_page<'db, S>(
+  db: &'db DbConn,
+  query: S,
+  page: u64,
+  per_page: u64,
+) -> Result<OffsetPage<<S::Selector as SelectorTrait>::Item>, DbErr>
+where
+  S: PaginatorTrait<'db, DbConn>,
+{
+  let page = Ord::max(page, 1);
+  let per_page = Ord::max(per_page, 1);
+
+  let paginator = query.paginate(db, per_page);
+  let totals = paginator.num_items_and_pages().await?;
+  let items = paginator.fetch_page(page - 1).await?;
+
+  Ok(OffsetPage {
+    items,
+    page,
+    per_page,
+    total_items: totals.number_of_items,
+    total_pages: totals.number_of_pages,
+  })
+}
+
+/// The window query [`fetch_cursor_page`] runs: rows past `cursor` in
+/// `(created_at, id)` order, one more than `limit`.
+pub fn cursor_query<E: EntityTrait>(
+  query: Select<E>,
+  created_at: E::Column,
+  id: E::Column,
+  cursor: Option<&Cursor>,
+  limit: u64,
+) -> Select<E> {
+  let direction = cursor.map(|c| c.direction).unwrap_or(CursorDirection::Next);
+
+  let mut query = query;
+  if let Some(c) = cursor {
+    // A row comparison rather than `a < x OR (a = x AND b < y)`, so Postgres
+    // can start the index scan at the cursor instead of filtering up to it
+    let row = Expr::tuple([created_at.into_expr(), id.into_expr()]);
+    let at = Expr::tuple([Expr::val(c.created_at), Expr::val(c.id)]);
+    query = query.filter(match c.direction {
+      CursorDirection::Next => row.lt(at),
+      CursorDirection::Prev => row.gt(at),
+    });
+  }
+
+  // Walking backwards reads ascending from the cursor and flips the result,
+  // so both directions hand back rows newest first.
+  query = match direction {
+    CursorDirection::Next => query.order_by_desc(created_at).order_by_desc(id),
+    CursorDirection::Prev => query.order_by_asc(created_at).order_by_asc(id),
+  };
+
+  // One extra row tells whether another window exists
+  query.limit(limit + 1)
+}
+
+/// Run a keyset query ordered by `(created_at, id)` newest first.
+///
+/// `key` pulls the cursor columns back out of a row so the returned cursors
+/// point at the first and last items of the window.
+pub async fn fetch_cursor_page<E, F>(
+  db: &DbConn,
+  query: Select<E>,
+  created_at: E::Column,
+  id: E::Column,
+  cursor: Option<Cursor>,
+  limit: u64,
+  key: F,
+) -> Result<CursorPage<E::Model>, DbErr>
+where
+  E: EntityTrait,
+  F: Fn(&E::Model) -> (DateTimeWithTimeZone, Uuid),
+{
+  let direction = cursor
+    .as_ref()
+    .map(|c| c.direction)
+    .unwrap_or(CursorDirection::Next);
+
+  let mut items = cursor_query(query, created_at, id, cursor.as_ref(), limit)
+    .all(db)
+    .await?;
+  let has_more = items.len() as u64 > limit;
+  items.truncate(limit as usize);
+  if direction == CursorDirection::Prev {
+    items.reverse();
+  }
+
+  let (has_next, has_prev) = match (&cursor, direction) {
+    (None, _) => (has_more, false),
+    (Some(_), CursorDirection::Next) => (has_more, true),
+    (Some(_), CursorDirection::Prev) => (true, has_more),
+  };
+
+  let encode = |model: &E::Model, direction: CursorDirection| {
+    let (created_at, id) = key(model);
+    Cursor::new(created_at, id, direction).encode()
+  };
+
+  let next_cursor = if has_next {
+    items.last().map(|m| encode(m, CursorDirection::Next))
+  } else {
+    None
+  };
+  let prev_cursor = if has_prev {
+    items.first().map(|m| encode(m, CursorDirection::Prev))
+  } else {
+    None
+  };
+
+  Ok(CursorPage {
+    items,
+    next_cursor,
+    prev_cursor,
+  })
+}
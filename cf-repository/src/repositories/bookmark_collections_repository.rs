@@ -0,0 +1,70 @@
+use ::cf_entity::entity::bookmark_collections;
+use sea_orm::prelude::Uuid;
+use sea_orm::*;
+
+pub struct BookmarkCollectionsRepository;
+
+impl BookmarkCollectionsRepository {
+  pub async fn find_by_id(
+    db: &DbConn,
+    id: Uuid,
+  ) -> Result<Option<bookmark_collections::Model>, DbErr> {
+    bookmark_collections::Entity::find_by_id(id).one(db).await
+  }
+
+  pub async fn find_by_user(
+    db: &DbConn,
+    user_id: Uuid,
+  ) -> Result<Vec<bookmark_collections::Model>, DbErr> {
+    bookmark_collections::Entity::find()
+      .filter(bookmark_collections::Column::UserId.eq(user_id))
+      .order_by_asc(bookmark_collections::Column::Name)
+      .all(db)
+      .await
+  }
+
+  pub async fn find_by_user_and_name(
+    db: &DbConn,
+    user_id: Uuid,
+    name: &str,
+  ) -> Result<Option<bookmark_collections::Model>, DbErr> {
+    bookmark_collections::Entity::find()
+      .filter(bookmark_collections::Column::UserId.eq(user_id))
+      .filter(bookmark_collections::Column::Name.eq(name))
+      .one(db)
+      .await
+  }
+
+  pub async fn create(
+    db: &DbConn,
+    form_data: bookmark_collections::ActiveModel,
+  ) -> Result<bookmark_collections::Model, DbErr> {
+    form_data.insert(db).await
+  }
+
+  pub async fn update(
+    db: &DbConn,
+    id: Uuid,
+    form_data: bookmark_collections::ActiveModel,
+  ) -> Result<bookmark_collections::Model, DbErr> {
+    // Ensure exists
+    bookmark_collections::Entity::find_by_id(id)
+      .one(db)
+      .await?
+      .ok_or(DbErr::RecordNotFound(
+        "Bookmark collection not found".to_owned(),
+      ))?;
+
+    let mut form_data = form_data;
+    form_data.id = Set(id);
+
+    form_data.update(db).await
+  }
+
+  /// Bookmarks in the collection are kept and become unfiled.
+  pub async fn delete(db: &DbConn, id: Uuid) -> Result<DeleteResult, DbErr> {
+    bookmark_collections::Entity::delete_by_id(id)
+      .exec(db)
+      .await
+  }
+}
@@ -0,0 +1,108 @@
+use std::collections::HashSet;
+
+use ::cf_entity::entity::{bookmarks, posts};
+use sea_orm::prelude::Uuid;
+use sea_orm::*;
+
+use super::PostsRepository;
+use crate::helpers::pagination::fetch_offset_page;
+use crate::structs::pagination::OffsetPage;
+
+pub struct BookmarksRepository;
+
+impl BookmarksRepository {
+  pub async fn find_by_user_and_post(
+    db: &DbConn,
+    user_id: Uuid,
+    post_id: Uuid,
+  ) -> Result<Option<bookmarks::Model>, DbErr> {
+    bookmarks::Entity::find()
+      .filter(bookmarks::Column::UserId.eq(user_id))
+      .filter(bookmarks::Column::PostId.eq(post_id))
+      .one(db)
+      .await
+  }
+
+  /// Bookmarks of a user joined with their posts. `collection_id` narrows the
+  /// listing to one collection. Posts the user can no longer read are left
+  /// out.
+  pub async fn find_by_user(
+    db: &DbConn,
+    user_id: Uuid,
+    collection_id: Option<Uuid>,
+    oldest_first: bool,
+    page: u64,
+    per_page: u64,
+  ) -> Result<OffsetPage<(bookmarks::Model, posts::Model)>, DbErr> {
+    let mut query = bookmarks::Entity::find().filter(bookmarks::Column::UserId.eq(user_id));
+    if let Some(collection_id) = collection_id {
+      query = query.filter(bookmarks::Column::CollectionId.eq(collection_id));
+    }
+    query = if oldest_first {
+      query.order_by_asc(bookmarks::Column::CreatedAt)
+    } else {
+      query.order_by_desc(bookmarks::Column::CreatedAt)
+    };
+
+    let query = query
+      .inner_join(posts::Entity)
+      .select_also(posts::Entity)
+      .filter(PostsRepository::readable_by(Some(user_id)));
+    let mut result = fetch_offset_page(db, query, page, per_page).await?;
+
+    // The inner join counts and returns only rows that have a post
+    let items = std::mem::take(&mut result.items)
+      .into_iter()
+      .filter_map(|(bookmark, post)| post.map(|post| (bookmark, post)))
+      .collect();
+    Ok(result.with_items(items))
+  }
+
+  /// Which of `post_ids` the user has bookmarked, in one query.
+  pub async fn find_bookmarked_post_ids(
+    db: &DbConn,
+    user_id: Uuid,
+    post_ids: &[Uuid],
+  ) -> Result<HashSet<Uuid>, DbErr> {
+    if post_ids.is_empty() {
+      return Ok(HashSet::new());
+    }
+
+    let ids: Vec<Uuid> = bookmarks::Entity::find()
+      .select_only()
+      .column(bookmarks::Column::PostId)
+      .filter(bookmarks::Column::UserId.eq(user_id))
+      .filter(bookmarks::Column::PostId.is_in(post_ids.to_vec()))
+      .into_tuple()
+      .all(db)
+      .await?;
+
+    Ok(ids.into_iter().collect())
+  }
+
+  pub async fn create(
+    db: &DbConn,
+    form_data: bookmarks::ActiveModel,
+  ) -> Result<bookmarks::Model, DbErr> {
+    form_data.insert(db).await
+  }
+
+  pub async fn update(
+    db: &DbConn,
+    form_data: bookmarks::ActiveModel,
+  ) -> Result<bookmarks::Model, DbErr> {
+    form_data.update(db).await
+  }
+
+  pub async fn delete_by_user_and_post(
+    db: &DbConn,
+    user_id: Uuid,
+    post_id: Uuid,
+  ) -> Result<DeleteResult, DbErr> {
+    bookmarks::Entity::delete_many()
+      .filter(bookmarks::Column::UserId.eq(user_id))
+      .filter(bookmarks::Column::PostId.eq(post_id))
+      .exec(db)
+      .await
+  }
+}
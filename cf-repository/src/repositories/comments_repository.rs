@@ -0,0 +1,83 @@
+use std::collections::HashMap;
+
+use ::cf_entity::entity::comments;
+use sea_orm::prelude::Uuid;
+use sea_orm::*;
+
+pub struct CommentsRepository;
+
+impl CommentsRepository {
+  pub async fn find_by_id(db: &DbConn, id: Uuid) -> Result<Option<comments::Model>, DbErr> {
+    comments::Entity::find_by_id(id).one(db).await
+  }
+
+  /// All comments of a post down to `max_depth`, oldest first so parents
+  /// always come before their replies.
+  pub async fn find_by_post(
+    db: &DbConn,
+    post_id: Uuid,
+    max_depth: i32,
+  ) -> Result<Vec<comments::Model>, DbErr> {
+    comments::Entity::find()
+      .filter(comments::Column::PostId.eq(post_id))
+      .filter(comments::Column::Depth.lte(max_depth))
+      .order_by_asc(comments::Column::CreatedAt)
+      .order_by_asc(comments::Column::Id)
+      .all(db)
+      .await
+  }
+
+  /// Comment counts for many posts in one grouped query. Posts without
+  /// comments are left out of the map.
+  pub async fn count_by_posts(db: &DbConn, post_ids: &[Uuid]) -> Result<HashMap<Uuid, u64>, DbErr> {
+    if post_ids.is_empty() {
+      return Ok(HashMap::new());
+    }
+
+    let rows: Vec<(Uuid, i64)> = comments::Entity::find()
+      .select_only()
+      .column(comments::Column::PostId)
+      .column_as(comments::Column::Id.count(), "count")
+      .filter(comments::Column::PostId.is_in(post_ids.to_vec()))
+      .group_by(comments::Column::PostId)
+      .into_tuple()
+      .all(db)
+      .await?;
+
+    Ok(
+      rows
+        .into_iter()
+        .map(|(post_id, count)| (post_id, count as u64))
+        .collect(),
+    )
+  }
+
+  pub async fn create(
+    db: &DbConn,
+    form_data: comments::ActiveModel,
+  ) -> Result<comments::Model, DbErr> {
+    form_data.insert(db).await
+  }
+
+  pub async fn update(
+    db: &DbConn,
+    id: Uuid,
+    form_data: comments::ActiveModel,
+  ) -> Result<comments::Model, DbErr> {
+    // Ensure exists
+    comments::Entity::find_by_id(id)
+      .one(db)
+      .await?
+      .ok_or(DbErr::RecordNotFound("Comment not found".to_owned()))?;
+
+    let mut form_data = form_data;
+    form_data.id = Set(id);
+
+    form_data.update(db).await
+  }
+
+  /// Replies are removed with their parent through the foreign key cascade.
+  pub async fn delete(db: &DbConn, id: Uuid) -> Result<DeleteResult, DbErr> {
+    comments::Entity::delete_by_id(id).exec(db).await
+  }
+}
@@ -0,0 +1,47 @@
+use ::cf_entity::entity::follows;
+use sea_orm::prelude::Uuid;
+use sea_orm::sea_query::{OnConflict, Query, SelectStatement};
+use sea_orm::*;
+
+pub struct FollowsRepository;
+
+impl FollowsRepository {
+  /// Following twice is not an error.
+  pub async fn follow(db: &DbConn, follower_id: Uuid, followee_id: Uuid) -> Result<(), DbErr> {
+    let row = follows::ActiveModel {
+      follower_id: Set(follower_id),
+      followee_id: Set(followee_id),
+      ..Default::default()
+    };
+    follows::Entity::insert(row)
+      .on_conflict(
+        OnConflict::columns([follows::Column::FollowerId, follows::Column::FolloweeId])
+          .do_nothing()
+          .to_owned(),
+      )
+      .do_nothing()
+      .exec_without_returning(db)
+      .await
+      .map(|_| ())
+  }
+
+  pub async fn unfollow(
+    db: &DbConn,
+    follower_id: Uuid,
+    followee_id: Uuid,
+  ) -> Result<DeleteResult, DbErr> {
+    follows::Entity::delete_by_id((follower_id, followee_id))
+      .exec(db)
+      .await
+  }
+
+  /// `SELECT followee_id` of everyone `follower_id` follows, for use in an
+  /// `IN` condition.
+  pub fn followee_ids_query(follower_id: Uuid) -> SelectStatement {
+    Query::select()
+      .column(follows::Column::FolloweeId)
+      .from(follows::Entity)
+      .and_where(follows::Column::FollowerId.eq(follower_id))
+      .to_owned()
+  }
+}
@@ -0,0 +1,43 @@
+use ::cf_entity::entity::media_renditions;
+use sea_orm::prelude::Uuid;
+use sea_orm::*;
+
+pub struct MediaRenditionsRepository;
+
+impl MediaRenditionsRepository {
+  /// Renditions of many media in one query, ordered by media then name.
+  pub async fn find_by_media_ids(
+    db: &DbConn,
+    media_ids: &[Uuid],
+  ) -> Result<Vec<media_renditions::Model>, DbErr> {
+    if media_ids.is_empty() {
+      return Ok(Vec::new());
+    }
+
+    media_renditions::Entity::find()
+      .filter(media_renditions::Column::MediaId.is_in(media_ids.to_vec()))
+      .order_by_asc(media_renditions::Column::MediaId)
+      .order_by_asc(media_renditions::Column::Name)
+      .all(db)
+      .await
+  }
+
+  /// Swap every rendition of a media for `renditions`.
+  pub async fn replace_for_media<C: ConnectionTrait>(
+    db: &C,
+    media_id: Uuid,
+    renditions: Vec<media_renditions::ActiveModel>,
+  ) -> Result<(), DbErr> {
+    media_renditions::Entity::delete_many()
+      .filter(media_renditions::Column::MediaId.eq(media_id))
+      .exec(db)
+      .await?;
+
+    if !renditions.is_empty() {
+      media_renditions::Entity::insert_many(renditions)
+        .exec_without_returning(db)
+        .await?;
+    }
+    Ok(())
+  }
+}
@@ -0,0 +1,155 @@
+use std::collections::{HashMap, HashSet};
+
+use ::cf_entity::entity::sea_orm_active_enums::MediaStatus;
+use ::cf_entity::entity::{media, post_media, posts};
+use sea_orm::prelude::Uuid;
+use sea_orm::*;
+
+use crate::helpers::pagination::fetch_offset_page;
+use crate::structs::media::MediaFilter;
+use crate::structs::pagination::OffsetPage;
+
+pub struct MediaRepository;
+
+impl MediaRepository {
+  pub async fn find_by_id(db: &DbConn, id: Uuid) -> Result<Option<media::Model>, DbErr> {
+    media::Entity::find_by_id(id).one(db).await
+  }
+
+  pub async fn find_by_status(
+    db: &DbConn,
+    status: MediaStatus,
+  ) -> Result<Vec<media::Model>, DbErr> {
+    media::Entity::find()
+      .filter(media::Column::Status.eq(status))
+      .order_by_asc(media::Column::CreatedAt)
+      .all(db)
+      .await
+  }
+
+  /// Newest first.
+  pub async fn find_filtered(
+    db: &DbConn,
+    filter: MediaFilter,
+    page: u64,
+    per_page: u64,
+  ) -> Result<OffsetPage<media::Model>, DbErr> {
+    let mut query = media::Entity::find();
+    if let Some(user_id) = filter.user_id {
+      query = query.filter(media::Column::UserId.eq(user_id));
+    }
+    if let Some(mime_type) = filter.mime_type {
+      query = query.filter(media::Column::MimeType.eq(mime_type));
+    }
+    if let Some(from) = filter.from {
+      query = query.filter(media::Column::CreatedAt.gte(from));
+    }
+    if let Some(to) = filter.to {
+      query = query.filter(media::Column::CreatedAt.lt(to));
+    }
+
+    let query = query
+      .order_by_desc(media::Column::CreatedAt)
+      .order_by_desc(media::Column::Id);
+    fetch_offset_page(db, query, page, per_page).await
+  }
+
+  /// How many posts use each of `ids` as their header or social image or
+  /// embed it in their content. Unused media is left out of the map.
+  pub async fn count_post_references(
+    db: &DbConn,
+    ids: &[Uuid],
+  ) -> Result<HashMap<Uuid, u64>, DbErr> {
+    if ids.is_empty() {
+      return Ok(HashMap::new());
+    }
+
+    let mut posts_by_media: HashMap<Uuid, HashSet<Uuid>> = HashMap::new();
+    for column in [posts::Column::HeaderImageId, posts::Column::SocialImageId] {
+      let rows: Vec<(Uuid, Uuid)> = posts::Entity::find()
+        .select_only()
+        .column(column)
+        .column(posts::Column::Id)
+        .filter(column.is_in(ids.to_vec()))
+        .into_tuple()
+        .all(db)
+        .await?;
+      for (media_id, post_id) in rows {
+        posts_by_media.entry(media_id).or_default().insert(post_id);
+      }
+    }
+
+    let embedded = post_media::Entity::find()
+      .filter(post_media::Column::MediaId.is_in(ids.to_vec()))
+      .all(db)
+      .await?;
+    for row in embedded {
+      posts_by_media
+        .entry(row.media_id)
+        .or_default()
+        .insert(row.post_id);
+    }
+
+    Ok(
+      posts_by_media
+        .into_iter()
+        .map(|(media_id, posts)| (media_id, posts.len() as u64))
+        .collect(),
+    )
+  }
+
+  /// Replace the media embedded in a post's content with those of
+  /// `media_ids` that belong to `author_id`; unknown ids are ignored.
+  pub async fn set_embedded_in_post<C: ConnectionTrait>(
+    db: &C,
+    post_id: Uuid,
+    author_id: Uuid,
+    media_ids: &[Uuid],
+  ) -> Result<(), DbErr> {
+    post_media::Entity::delete_many()
+      .filter(post_media::Column::PostId.eq(post_id))
+      .exec(db)
+      .await?;
+
+    if media_ids.is_empty() {
+      return Ok(());
+    }
+    let owned: Vec<Uuid> = media::Entity::find()
+      .select_only()
+      .column(media::Column::Id)
+      .filter(media::Column::Id.is_in(media_ids.to_vec()))
+      .filter(media::Column::UserId.eq(author_id))
+      .into_tuple()
+      .all(db)
+      .await?;
+
+    if !owned.is_empty() {
+      let rows = owned.into_iter().map(|media_id| post_media::ActiveModel {
+        post_id: Set(post_id),
+        media_id: Set(media_id),
+      });
+      post_media::Entity::insert_many(rows)
+        .exec_without_returning(db)
+        .await?;
+    }
+    Ok(())
+  }
+
+  pub async fn create<C: ConnectionTrait>(
+    db: &C,
+    form_data: media::ActiveModel,
+  ) -> Result<media::Model, DbErr> {
+    form_data.insert(db).await
+  }
+
+  pub async fn update<C: ConnectionTrait>(
+    db: &C,
+    form_data: media::ActiveModel,
+  ) -> Result<media::Model, DbErr> {
+    form_data.update(db).await
+  }
+
+  pub async fn delete(db: &DbConn, id: Uuid) -> Result<DeleteResult, DbErr> {
+    media::Entity::delete_by_id(id).exec(db).await
+  }
+}
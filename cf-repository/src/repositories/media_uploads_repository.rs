@@ -0,0 +1,40 @@
+use ::cf_entity::entity::media_uploads;
+use sea_orm::prelude::{DateTimeWithTimeZone, Uuid};
+use sea_orm::*;
+
+pub struct MediaUploadsRepository;
+
+impl MediaUploadsRepository {
+  pub async fn find_by_id(db: &DbConn, id: Uuid) -> Result<Option<media_uploads::Model>, DbErr> {
+    media_uploads::Entity::find_by_id(id).one(db).await
+  }
+
+  /// Oldest uploads that expired before `before`, skipping the first `skip`
+  /// and returning at most `limit` of them.
+  pub async fn find_expired(
+    db: &DbConn,
+    before: DateTimeWithTimeZone,
+    skip: u64,
+    limit: u64,
+  ) -> Result<Vec<media_uploads::Model>, DbErr> {
+    media_uploads::Entity::find()
+      .filter(media_uploads::Column::ExpiresAt.lt(before))
+      .order_by_asc(media_uploads::Column::ExpiresAt)
+      .order_by_asc(media_uploads::Column::Id)
+      .offset(skip)
+      .limit(limit)
+      .all(db)
+      .await
+  }
+
+  pub async fn create(
+    db: &DbConn,
+    form_data: media_uploads::ActiveModel,
+  ) -> Result<media_uploads::Model, DbErr> {
+    form_data.insert(db).await
+  }
+
+  pub async fn delete<C: ConnectionTrait>(db: &C, id: Uuid) -> Result<DeleteResult, DbErr> {
+    media_uploads::Entity::delete_by_id(id).exec(db).await
+  }
+}
@@ -1,5 +1,35 @@
+pub mod bookmark_collections_repository;
+pub mod bookmarks_repository;
+pub mod comments_repository;
+pub mod follows_repository;
+pub mod media_renditions_repository;
+pub mod media_repository;
+pub mod media_uploads_repository;
+pub mod post_placements_repository;
+pub mod post_previews_repository;
+pub mod post_trending_repository;
+pub mod post_view_daily_repository;
+pub mod post_views_repository;
 pub mod posts_repository;
+pub mod public_posts_repository;
+pub mod reactions_repository;
+pub mod tags_repository;
 pub mod users_repository;
 
+pub use bookmark_collections_repository::BookmarkCollectionsRepository;
+pub use bookmarks_repository::BookmarksRepository;
+pub use comments_repository::CommentsRepository;
+pub use follows_repository::FollowsRepository;
+pub use media_renditions_repository::MediaRenditionsRepository;
+pub use media_repository::MediaRepository;
+pub use media_uploads_repository::MediaUploadsRepository;
+pub use post_placements_repository::PostPlacementsRepository;
+pub use post_previews_repository::PostPreviewsRepository;
+pub use post_trending_repository::{PostTrendingRepository, TrendingWeights};
+pub use post_view_daily_repository::PostViewDailyRepository;
+pub use post_views_repository::PostViewsRepository;
 pub use posts_repository::PostsRepository;
+pub use public_posts_repository::PublicPostsRepository;
+pub use reactions_repository::{ReactionTarget, ReactionTargetType, ReactionsRepository};
+pub use tags_repository::TagsRepository;
 pub use users_repository::UsersRepository;
@@ -0,0 +1,193 @@
+use ::cf_entity::entity::sea_orm_active_enums::PlacementKind;
+use ::cf_entity::entity::{post_placements, posts};
+use sea_orm::prelude::{DateTimeWithTimeZone, Expr, Uuid};
+use sea_orm::sea_query::{OnConflict, Query, SelectStatement};
+use sea_orm::*;
+
+pub struct PostPlacementsRepository;
+
+impl PostPlacementsRepository {
+  /// Placements of `kind` that have not expired.
+  pub fn active(kind: PlacementKind) -> Condition {
+    Condition::all()
+      .add(post_placements::Column::Kind.eq(kind))
+      .add(
+        Condition::any()
+          .add(post_placements::Column::ExpiresAt.is_null())
+          .add(Expr::col(post_placements::Column::ExpiresAt).gt(Expr::current_timestamp())),
+      )
+  }
+
+  /// `SELECT post_id` of the active placements of `kind`, for use in an
+  /// `IN` condition.
+  pub fn active_post_ids_query(kind: PlacementKind) -> SelectStatement {
+    Query::select()
+      .column(post_placements::Column::PostId)
+      .from(post_placements::Entity)
+      .cond_where(Self::active(kind))
+      .to_owned()
+  }
+
+  /// Active placements of `kind` in position order, whoever may read the
+  /// posts.
+  pub async fn find_active<C: ConnectionTrait>(
+    db: &C,
+    kind: PlacementKind,
+  ) -> Result<Vec<post_placements::Model>, DbErr> {
+    post_placements::Entity::find()
+      .filter(Self::active(kind))
+      .order_by_asc(post_placements::Column::Position)
+      .order_by_asc(post_placements::Column::CreatedAt)
+      .all(db)
+      .await
+  }
+
+  /// Posts of the active placements of `kind` that match `scope`, in
+  /// position order.
+  pub async fn find_posts(
+    db: &DbConn,
+    kind: PlacementKind,
+    scope: Condition,
+  ) -> Result<Vec<posts::Model>, DbErr> {
+    posts::Entity::find()
+      .join(
+        JoinType::InnerJoin,
+        post_placements::Relation::Posts.def().rev(),
+      )
+      .filter(Self::active(kind))
+      .filter(scope)
+      .order_by_asc(post_placements::Column::Position)
+      .order_by_asc(post_placements::Column::CreatedAt)
+      .all(db)
+      .await
+  }
+
+  /// Position after the last placement of `kind`, expired ones included.
+  pub async fn next_position<C: ConnectionTrait>(
+    db: &C,
+    kind: PlacementKind,
+  ) -> Result<i32, DbErr> {
+    let last: Option<Option<i32>> = post_placements::Entity::find()
+      .select_only()
+      .column_as(post_placements::Column::Position.max(), "position")
+      .filter(post_placements::Column::Kind.eq(kind))
+      .into_tuple()
+      .one(db)
+      .await?;
+    Ok(last.flatten().map_or(0, |position| position + 1))
+  }
+
+  /// Place `post_id` at `position`, by default its current one or after the
+  /// last. Placing a post again moves it and replaces its expiry. Returns
+  /// `None`, leaving everything as it was, when the post is not placed yet
+  /// and `max_active` placements of `kind` already are.
+  ///
+  /// Concurrent calls for the same kind are serialized on an advisory lock
+  /// so the cap cannot be overshot.
+  pub async fn place(
+    db: &DbConn,
+    kind: PlacementKind,
+    post_id: Uuid,
+    position: Option<i32>,
+    expires_at: Option<DateTimeWithTimeZone>,
+    created_by: Uuid,
+    max_active: usize,
+  ) -> Result<Option<post_placements::Model>, DbErr> {
+    let txn = db.begin().await?;
+    txn
+      .execute_raw(Statement::from_sql_and_values(
+        DbBackend::Postgres,
+        "SELECT pg_advisory_xact_lock(hashtext($1))",
+        [format!("post_placements:{}", kind.to_value()).into()],
+      ))
+      .await?;
+
+    let active = Self::find_active(&txn, kind).await?;
+    let current = active.iter().find(|placement| placement.post_id == post_id);
+    if current.is_none() && active.len() >= max_active {
+      return Ok(None);
+    }
+
+    let position = match (position, current) {
+      (Some(position), _) => position,
+      (None, Some(current)) => current.position,
+      (None, None) => Self::next_position(&txn, kind).await?,
+    };
+    let placement = Self::upsert(
+      &txn,
+      post_placements::ActiveModel {
+        kind: Set(kind),
+        post_id: Set(post_id),
+        position: Set(position),
+        expires_at: Set(expires_at),
+        created_by: Set(created_by),
+        ..Default::default()
+      },
+    )
+    .await?;
+
+    txn.commit().await?;
+    Ok(Some(placement))
+  }
+
+  async fn upsert<C: ConnectionTrait>(
+    db: &C,
+    form_data: post_placements::ActiveModel,
+  ) -> Result<post_placements::Model, DbErr> {
+    post_placements::Entity::insert(form_data)
+      .on_conflict(
+        OnConflict::columns([
+          post_placements::Column::Kind,
+          post_placements::Column::PostId,
+        ])
+        .update_columns([
+          post_placements::Column::Position,
+          post_placements::Column::ExpiresAt,
+        ])
+        .to_owned(),
+      )
+      .exec_with_returning(db)
+      .await
+  }
+
+  pub async fn delete(
+    db: &DbConn,
+    kind: PlacementKind,
+    post_id: Uuid,
+  ) -> Result<DeleteResult, DbErr> {
+    post_placements::Entity::delete_by_id((kind, post_id))
+      .exec(db)
+      .await
+  }
+
+  /// Number `post_ids` 0, 1, 2... in the order given and move every other
+  /// placement of `kind` after them, keeping their relative order.
+  pub async fn reorder(db: &DbConn, kind: PlacementKind, post_ids: &[Uuid]) -> Result<(), DbErr> {
+    let txn = db.begin().await?;
+
+    let rest: Vec<Uuid> = post_placements::Entity::find()
+      .select_only()
+      .column(post_placements::Column::PostId)
+      .filter(post_placements::Column::Kind.eq(kind))
+      .filter(post_placements::Column::PostId.is_not_in(post_ids.iter().copied()))
+      .order_by_asc(post_placements::Column::Position)
+      .order_by_asc(post_placements::Column::CreatedAt)
+      .into_tuple()
+      .all(&txn)
+      .await?;
+
+    for (position, post_id) in post_ids.iter().chain(rest.iter()).enumerate() {
+      post_placements::Entity::update_many()
+        .col_expr(
+          post_placements::Column::Position,
+          Expr::value(position as i32),
+        )
+        .filter(post_placements::Column::Kind.eq(kind))
+        .filter(post_placements::Column::PostId.eq(*post_id))
+        .exec(&txn)
+        .await?;
+    }
+
+    txn.commit().await
+  }
+}
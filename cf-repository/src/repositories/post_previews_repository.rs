@@ -0,0 +1,51 @@
+use ::cf_entity::entity::post_previews;
+use sea_orm::prelude::{DateTimeWithTimeZone, Expr, Uuid};
+use sea_orm::*;
+
+pub struct PostPreviewsRepository;
+
+impl PostPreviewsRepository {
+  pub async fn find_by_id(db: &DbConn, id: Uuid) -> Result<Option<post_previews::Model>, DbErr> {
+    post_previews::Entity::find_by_id(id).one(db).await
+  }
+
+  /// Links of a post that are neither revoked nor expired at `now`, newest
+  /// first.
+  pub async fn find_active_by_post(
+    db: &DbConn,
+    post_id: Uuid,
+    now: DateTimeWithTimeZone,
+  ) -> Result<Vec<post_previews::Model>, DbErr> {
+    post_previews::Entity::find()
+      .filter(post_previews::Column::PostId.eq(post_id))
+      .filter(post_previews::Column::RevokedAt.is_null())
+      .filter(post_previews::Column::ExpiresAt.gt(now))
+      .order_by_desc(post_previews::Column::CreatedAt)
+      .all(db)
+      .await
+  }
+
+  pub async fn create(
+    db: &DbConn,
+    form_data: post_previews::ActiveModel,
+  ) -> Result<post_previews::Model, DbErr> {
+    form_data.insert(db).await
+  }
+
+  /// Affects no rows when the link is already revoked or not one of
+  /// `post_id`'s.
+  pub async fn revoke(
+    db: &DbConn,
+    id: Uuid,
+    post_id: Uuid,
+    now: DateTimeWithTimeZone,
+  ) -> Result<UpdateResult, DbErr> {
+    post_previews::Entity::update_many()
+      .col_expr(post_previews::Column::RevokedAt, Expr::value(now))
+      .filter(post_previews::Column::Id.eq(id))
+      .filter(post_previews::Column::PostId.eq(post_id))
+      .filter(post_previews::Column::RevokedAt.is_null())
+      .exec(db)
+      .await
+  }
+}
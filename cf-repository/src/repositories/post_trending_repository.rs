@@ -0,0 +1,101 @@
+use ::cf_entity::entity::sea_orm_active_enums::TrendingPeriod;
+use ::cf_entity::entity::{post_trending, posts};
+use sea_orm::prelude::DateTimeWithTimeZone;
+use sea_orm::*;
+
+use super::PostsRepository;
+use crate::helpers::pagination::fetch_offset_page;
+use crate::structs::pagination::OffsetPage;
+
+/// What one event of each kind adds to a score before decay.
+#[derive(Debug, Clone, Copy)]
+pub struct TrendingWeights {
+  pub view: f64,
+  pub reaction: f64,
+  pub comment: f64,
+}
+
+pub struct PostTrendingRepository;
+
+impl PostTrendingRepository {
+  /// Posts ranked in `period`, highest score first. Posts that stopped
+  /// being public since the last refresh are left out.
+  pub async fn find_page(
+    db: &DbConn,
+    period: TrendingPeriod,
+    page: u64,
+    per_page: u64,
+  ) -> Result<OffsetPage<posts::Model>, DbErr> {
+    let query = posts::Entity::find()
+      .join(
+        JoinType::InnerJoin,
+        post_trending::Relation::Posts.def().rev(),
+      )
+      .filter(post_trending::Column::Period.eq(period))
+      .filter(PostsRepository::listed_for(None))
+      .order_by_desc(post_trending::Column::Score)
+      .order_by_asc(posts::Column::Id);
+    fetch_offset_page(db, query, page, per_page).await
+  }
+
+  /// Replace the ranking of `period` with the `limit` best public posts by
+  /// views, reactions and comments since `since`. Each event counts half as
+  /// much every `half_life_secs`. Returns how many posts were ranked.
+  pub async fn recompute(
+    db: &DbConn,
+    period: TrendingPeriod,
+    now: DateTimeWithTimeZone,
+    since: DateTimeWithTimeZone,
+    half_life_secs: f64,
+    weights: TrendingWeights,
+    limit: u64,
+  ) -> Result<u64, DbErr> {
+    let period = period.to_value();
+    let txn = db.begin().await?;
+
+    post_trending::Entity::delete_many()
+      .filter(post_trending::Column::Period.eq(period.clone()))
+      .exec(&txn)
+      .await?;
+
+    let result = txn
+      .execute_raw(Statement::from_sql_and_values(
+        DbBackend::Postgres,
+        r#"
+          INSERT INTO post_trending (period, post_id, score, computed_at)
+          SELECT $1, events.post_id,
+                 SUM(events.weight * EXP(-LN(2) * EXTRACT(EPOCH FROM ($2 - events.at)) / $4)),
+                 $2
+          FROM (
+            SELECT post_id, viewed_at AS at, $5::float8 AS weight
+            FROM post_views WHERE viewed_at >= $3
+            UNION ALL
+            SELECT post_id, created_at, $6::float8
+            FROM reactions WHERE post_id IS NOT NULL AND created_at >= $3
+            UNION ALL
+            SELECT post_id, created_at, $7::float8
+            FROM comments WHERE created_at >= $3
+          ) AS events
+          JOIN posts ON posts.id = events.post_id
+          WHERE posts.status = 'PUBLISHED' AND posts.visibility = 'PUBLIC'
+          GROUP BY events.post_id
+          ORDER BY 3 DESC
+          LIMIT $8
+        "#,
+        [
+          period.into(),
+          now.into(),
+          since.into(),
+          half_life_secs.into(),
+          weights.view.into(),
+          weights.reaction.into(),
+          weights.comment.into(),
+          (limit as i64).into(),
+        ],
+      ))
+      .await?;
+
+    txn.commit().await?;
+    Ok(result.rows_affected())
+  }
+}
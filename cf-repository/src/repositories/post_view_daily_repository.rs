@@ -0,0 +1,23 @@
+use ::cf_entity::entity::post_view_daily;
+use sea_orm::prelude::{Date, Uuid};
+use sea_orm::*;
+
+pub struct PostViewDailyRepository;
+
+impl PostViewDailyRepository {
+  /// Rolled up days of a post between `from` and `to` inclusive. Days without
+  /// views have no row.
+  pub async fn find_by_post_between(
+    db: &DbConn,
+    post_id: Uuid,
+    from: Date,
+    to: Date,
+  ) -> Result<Vec<post_view_daily::Model>, DbErr> {
+    post_view_daily::Entity::find()
+      .filter(post_view_daily::Column::PostId.eq(post_id))
+      .filter(post_view_daily::Column::Day.between(from, to))
+      .order_by_asc(post_view_daily::Column::Day)
+      .all(db)
+      .await
+  }
+}
@@ -0,0 +1,89 @@
+use ::cf_entity::entity::post_views;
+use sea_orm::prelude::DateTimeWithTimeZone;
+use sea_orm::sea_query::OnConflict;
+use sea_orm::*;
+
+pub struct PostViewsRepository;
+
+impl PostViewsRepository {
+  /// Insert a batch of views, silently skipping any visitor already counted
+  /// for the same post in the same dedup window.
+  pub async fn insert_many_deduplicated(
+    db: &DbConn,
+    views: Vec<post_views::ActiveModel>,
+  ) -> Result<u64, DbErr> {
+    if views.is_empty() {
+      return Ok(0);
+    }
+
+    let result = post_views::Entity::insert_many(views)
+      .on_conflict(
+        OnConflict::columns([
+          post_views::Column::PostId,
+          post_views::Column::Fingerprint,
+          post_views::Column::WindowStart,
+        ])
+        .do_nothing()
+        .to_owned(),
+      )
+      .do_nothing()
+      .exec_without_returning(db)
+      .await?;
+
+    Ok(match result {
+      TryInsertResult::Inserted(rows) => rows,
+      _ => 0,
+    })
+  }
+
+  /// Rebuild `post_view_daily` for every day from `since` onwards and refresh
+  /// `posts.view_count` for the posts viewed in that range.
+  pub async fn rollup_since(db: &DbConn, since: DateTimeWithTimeZone) -> Result<(), DbErr> {
+    db.execute_raw(Statement::from_sql_and_values(
+      DbBackend::Postgres,
+      r#"
+        INSERT INTO post_view_daily (post_id, day, views, unique_visitors)
+        SELECT post_id,
+               (viewed_at AT TIME ZONE 'UTC')::date AS day,
+               COUNT(*),
+               COUNT(DISTINCT fingerprint)
+        FROM post_views
+        WHERE viewed_at >= $1
+        GROUP BY post_id, day
+        ON CONFLICT (post_id, day) DO UPDATE
+          SET views = EXCLUDED.views,
+              unique_visitors = EXCLUDED.unique_visitors
+      "#,
+      [since.into()],
+    ))
+    .await?;
+
+    db.execute_raw(Statement::from_sql_and_values(
+      DbBackend::Postgres,
+      r#"
+        UPDATE posts
+        SET view_count = totals.views
+        FROM (
+          SELECT post_id, SUM(views)::bigint AS views
+          FROM post_view_daily
+          WHERE post_id IN (SELECT DISTINCT post_id FROM post_views WHERE viewed_at >= $1)
+          GROUP BY post_id
+        ) AS totals
+        WHERE posts.id = totals.post_id
+      "#,
+      [since.into()],
+    ))
+    .await?;
+
+    Ok(())
+  }
+
+  /// Drop raw views older than `before`; their totals live on in the rollup.
+  pub async fn delete_before(db: &DbConn, before: DateTimeWithTimeZone) -> Result<u64, DbErr> {
+    post_views::Entity::delete_many()
+      .filter(post_views::Column::ViewedAt.lt(before))
+      .exec(db)
+      .await
+      .map(|result| result.rows_affected)
+  }
+}
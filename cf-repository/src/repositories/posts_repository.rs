@@ -1,33 +1,271 @@
-use ::cf_entity::entity::posts;
-use sea_orm::prelude::Uuid;
+use ::cf_entity::entity::sea_orm_active_enums::{PlacementKind, PostStatus, PostVisibility};
+use ::cf_entity::entity::{post_placements, posts};
+use sea_orm::prelude::{Expr, Uuid};
+use sea_orm::sea_query::{Query, UnionType};
 use sea_orm::*;
 
+use super::{FollowsRepository, PostPlacementsRepository};
+use crate::helpers::pagination::{fetch_cursor_page, fetch_offset_page};
+use crate::structs::pagination::{Cursor, CursorDirection, CursorPage, OffsetPage, PinnedCursor};
+
 pub struct PostsRepository;
 
 impl PostsRepository {
+  /// Posts `viewer` may open: their own, and published posts that are
+  /// public, unlisted or by an author they follow. `None` is an anonymous
+  /// reader.
+  pub fn readable_by(viewer: Option<Uuid>) -> Condition {
+    Self::visible_to(viewer, true)
+  }
+
+  /// Like [`PostsRepository::readable_by`] without unlisted posts of other
+  /// authors, for anything that lists posts.
+  pub fn listed_for(viewer: Option<Uuid>) -> Condition {
+    Self::visible_to(viewer, false)
+  }
+
+  /// Every post of `owner` whatever its status or visibility, for the
+  /// management API.
+  pub fn owned_by(owner: Uuid) -> Condition {
+    Condition::all().add(posts::Column::UserId.eq(owner))
+  }
+
+  fn visible_to(viewer: Option<Uuid>, include_unlisted: bool) -> Condition {
+    let mut visibilities = vec![PostVisibility::Public];
+    if include_unlisted {
+      visibilities.push(PostVisibility::Unlisted);
+    }
+
+    let mut shared = Condition::any().add(posts::Column::Visibility.is_in(visibilities));
+    if let Some(viewer) = viewer {
+      shared = shared.add(
+        Condition::all()
+          .add(posts::Column::Visibility.eq(PostVisibility::FollowersOnly))
+          .add(posts::Column::UserId.in_subquery(FollowsRepository::followee_ids_query(viewer))),
+      );
+    }
+
+    let mut condition = Condition::any().add(
+      Condition::all()
+        .add(posts::Column::Status.eq(PostStatus::Published))
+        .add(shared),
+    );
+    if let Some(viewer) = viewer {
+      condition = condition.add(posts::Column::UserId.eq(viewer));
+    }
+    condition
+  }
+
+  /// Any post regardless of visibility, for ownership checks and writes.
   pub async fn find_by_id(db: &DbConn, id: Uuid) -> Result<Option<posts::Model>, DbErr> {
     posts::Entity::find_by_id(id).one(db).await
   }
 
+  /// A post if `viewer` may read it.
+  pub async fn find_readable(
+    db: &DbConn,
+    id: Uuid,
+    viewer: Option<Uuid>,
+  ) -> Result<Option<posts::Model>, DbErr> {
+    posts::Entity::find_by_id(id)
+      .filter(Self::readable_by(viewer))
+      .one(db)
+      .await
+  }
+
+  /// Posts matching `scope`, such as [`PostsRepository::listed_for`] or
+  /// [`PostsRepository::owned_by`], active pins first in position order and
+  /// the rest newest first. Pins are ordinary rows of the listing, so totals
+  /// and later pages stay consistent.
   pub async fn find_all(
     db: &DbConn,
+    scope: Condition,
     page: u64,
     per_page: u64,
-  ) -> Result<(Vec<posts::Model>, u64), DbErr> {
-    let paginator = posts::Entity::find()
+  ) -> Result<OffsetPage<posts::Model>, DbErr> {
+    let query = posts::Entity::find()
+      .join(
+        JoinType::LeftJoin,
+        post_placements::Relation::Posts
+          .def()
+          .rev()
+          .on_condition(|_, _| PostPlacementsRepository::active(PlacementKind::Pinned)),
+      )
+      .filter(scope)
+      .order_by(
+        Expr::col((post_placements::Entity, post_placements::Column::Position)).is_null(),
+        Order::Asc,
+      )
+      .order_by_asc(post_placements::Column::Position)
       .order_by_desc(posts::Column::CreatedAt)
-      .paginate(db, per_page);
-    let num_pages = paginator.num_pages().await?;
-    let items = paginator.fetch_page(page - 1).await?;
-    Ok((items, num_pages))
+      .order_by_desc(posts::Column::Id);
+    fetch_offset_page(db, query, page, per_page).await
   }
 
-  pub async fn create(db: &DbConn, form_data: posts::ActiveModel) -> Result<posts::Model, DbErr> {
-    form_data.insert(db).await
+  /// Keyset pages over the posts matching `scope`, led by the active pins in
+  /// position order and followed by the others newest first. Pins past the
+  /// first page carry on over the next ones, and walking back returns the
+  /// same windows, so every post shows up exactly once either way.
+  pub async fn find_all_by_cursor(
+    db: &DbConn,
+    scope: Condition,
+    cursor: Option<PinnedCursor>,
+    limit: u64,
+  ) -> Result<CursorPage<posts::Model>, DbErr> {
+    let rest =
+      posts::Entity::find()
+        .filter(scope.clone())
+        .filter(posts::Column::Id.not_in_subquery(
+          PostPlacementsRepository::active_post_ids_query(PlacementKind::Pinned),
+        ));
+    let cursor = match cursor {
+      Some(PinnedCursor::Row(cursor)) if cursor.direction == CursorDirection::Next => {
+        return Self::fetch_rest(db, rest, Some(cursor), limit).await;
+      }
+      cursor => cursor,
+    };
+
+    let pins = PostPlacementsRepository::find_posts(db, PlacementKind::Pinned, scope).await?;
+    let count = pins.len() as u64;
+    let pin_cursor = |at: u64, direction| PinnedCursor::Pin { at, direction }.encode();
+
+    match cursor {
+      Some(PinnedCursor::Row(cursor)) => {
+        // Back from the rest; once its start is reached the pins fill the
+        // front of the window
+        let mut page = Self::fetch_rest(db, rest, Some(cursor), limit).await?;
+        if page.prev_cursor.is_none() && count > 0 {
+          let start = count.saturating_sub(limit - page.items.len() as u64);
+          if page.items.is_empty() {
+            page.next_cursor = Some(pin_cursor(count, CursorDirection::Next));
+          }
+          page
+            .items
+            .splice(0..0, pins.into_iter().skip(start as usize));
+          page.prev_cursor = (start > 0).then(|| pin_cursor(start, CursorDirection::Prev));
+        }
+        Ok(page)
+      }
+      Some(PinnedCursor::Pin {
+        at,
+        direction: CursorDirection::Prev,
+      }) => {
+        let at = Ord::min(at, count);
+        let start = at.saturating_sub(limit);
+        let has_next = at < count || rest.one(db).await?.is_some();
+        Ok(CursorPage {
+          items: pins[start as usize..at as usize].to_vec(),
+          next_cursor: has_next.then(|| pin_cursor(at, CursorDirection::Next)),
+          prev_cursor: (start > 0).then(|| pin_cursor(start, CursorDirection::Prev)),
+        })
+      }
+      cursor => {
+        let at = match cursor {
+          Some(PinnedCursor::Pin { at, .. }) => Ord::min(at, count),
+          _ => 0,
+        };
+        let mut items: Vec<_> = pins
+          .into_iter()
+          .skip(at as usize)
+          .take(limit as usize)
+          .collect();
+        let end = at + items.len() as u64;
+        let remaining = limit - items.len() as u64;
+
+        let next_cursor = if remaining > 0 {
+          let mut page = Self::fetch_rest(db, rest, None, remaining).await?;
+          items.append(&mut page.items);
+          page.next_cursor
+        } else if end < count || rest.one(db).await?.is_some() {
+          Some(pin_cursor(end, CursorDirection::Next))
+        } else {
+          None
+        };
+        Ok(CursorPage {
+          items,
+          next_cursor,
+          prev_cursor: (at > 0).then(|| pin_cursor(at, CursorDirection::Prev)),
+        })
+      }
+    }
   }
 
-  pub async fn update(
+  /// Keyset page over the posts of a listing that are not pinned.
+  async fn fetch_rest(
     db: &DbConn,
+    rest: Select<posts::Entity>,
+    cursor: Option<Cursor>,
+    limit: u64,
+  ) -> Result<CursorPage<posts::Model>, DbErr> {
+    fetch_cursor_page(
+      db,
+      rest,
+      posts::Column::CreatedAt,
+      posts::Column::Id,
+      cursor,
+      limit,
+      |post| (post.created_at, post.id),
+    )
+    .await
+  }
+
+  /// Published posts of the authors `viewer` follows, newest first by
+  /// `published_at`. Unlisted posts stay out; `include_own` adds the
+  /// viewer's own published posts whatever their visibility.
+  pub async fn find_timeline_by_cursor(
+    db: &DbConn,
+    viewer: Uuid,
+    include_own: bool,
+    cursor: Option<Cursor>,
+    limit: u64,
+  ) -> Result<CursorPage<posts::Model>, DbErr> {
+    fetch_cursor_page(
+      db,
+      Self::timeline(viewer, include_own),
+      posts::Column::PublishedAt,
+      posts::Column::Id,
+      cursor,
+      limit,
+      |post| (post.published_at.unwrap_or(post.created_at), post.id),
+    )
+    .await
+  }
+
+  /// The posts [`PostsRepository::find_timeline_by_cursor`] pages through.
+  /// Paged by `(published_at, id)`, Postgres walks `idx_posts_published_at_id`
+  /// backwards from the cursor and checks each post's author against the
+  /// followed ones, stopping once the page is full, so pages are not sorted
+  /// however many authors are followed. Only near the end of a timeline may
+  /// it read the rest of the index range and sort the few posts left.
+  pub fn timeline(viewer: Uuid, include_own: bool) -> Select<posts::Entity> {
+    let mut authors = FollowsRepository::followee_ids_query(viewer);
+    let mut shared = Condition::any().add(
+      posts::Column::Visibility.is_in([PostVisibility::Public, PostVisibility::FollowersOnly]),
+    );
+    if include_own {
+      authors.union(
+        UnionType::All,
+        Query::select().expr(Expr::value(viewer)).to_owned(),
+      );
+      shared = shared.add(posts::Column::UserId.eq(viewer));
+    }
+
+    posts::Entity::find()
+      .filter(posts::Column::Status.eq(PostStatus::Published))
+      .filter(posts::Column::PublishedAt.is_not_null())
+      .filter(posts::Column::UserId.in_subquery(authors))
+      .filter(shared)
+  }
+
+  pub async fn create<C: ConnectionTrait>(
+    db: &C,
+    form_data: posts::ActiveModel,
+  ) -> Result<posts::Model, DbErr> {
+    form_data.insert(db).await
+  }
+
+  pub async fn update<C: ConnectionTrait>(
+    db: &C,
     id: Uuid,
     form_data: posts::ActiveModel,
   ) -> Result<posts::Model, DbErr> {
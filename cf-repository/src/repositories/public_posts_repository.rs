@@ -0,0 +1,160 @@
+use ::cf_entity::entity::{post_tags, posts};
+use sea_orm::prelude::{DateTimeWithTimeZone, Uuid};
+use sea_orm::sea_query::NullOrdering;
+use sea_orm::*;
+
+use super::PostsRepository;
+
+use crate::structs::pagination::{CursorPage, OffsetPage, PinnedCursor};
+
+/// Read-only access to what anonymous readers may see. Every query starts
+/// from [`PublicPostsRepository::visible`], so drafts, archived and
+/// non-public posts cannot leak through a forgotten filter. Deleted posts are
+/// removed from the table and need no filter.
+pub struct PublicPostsRepository;
+
+impl PublicPostsRepository {
+  /// Published, public posts
+  fn visible() -> Select<posts::Entity> {
+    posts::Entity::find().filter(PostsRepository::listed_for(None))
+  }
+
+  /// Pinned posts come first, see [`PostsRepository::find_all`].
+  pub async fn find_all(
+    db: &DbConn,
+    page: u64,
+    per_page: u64,
+  ) -> Result<OffsetPage<posts::Model>, DbErr> {
+    PostsRepository::find_all(db, PostsRepository::listed_for(None), page, per_page).await
+  }
+
+  /// Pinned posts lead the first page, see
+  /// [`PostsRepository::find_all_by_cursor`].
+  pub async fn find_all_by_cursor(
+    db: &DbConn,
+    cursor: Option<PinnedCursor>,
+    limit: u64,
+  ) -> Result<CursorPage<posts::Model>, DbErr> {
+    PostsRepository::find_all_by_cursor(db, PostsRepository::listed_for(None), cursor, limit).await
+  }
+
+  /// Newest posts, optionally only those of one author or with one tag.
+  pub async fn find_latest(
+    db: &DbConn,
+    user_id: Option<Uuid>,
+    tag_id: Option<Uuid>,
+    limit: u64,
+  ) -> Result<Vec<posts::Model>, DbErr> {
+    let mut query = Self::visible();
+    if let Some(user_id) = user_id {
+      query = query.filter(posts::Column::UserId.eq(user_id));
+    }
+    if let Some(tag_id) = tag_id {
+      query = query
+        .inner_join(post_tags::Entity)
+        .filter(post_tags::Column::TagId.eq(tag_id));
+    }
+
+    query
+      .order_by_with_nulls(posts::Column::PublishedAt, Order::Desc, NullOrdering::Last)
+      .order_by_desc(posts::Column::CreatedAt)
+      .order_by_desc(posts::Column::Id)
+      .limit(limit)
+      .all(db)
+      .await
+  }
+
+  /// Posts among `ids`, in the order given.
+  pub async fn find_by_ids(db: &DbConn, ids: &[Uuid]) -> Result<Vec<posts::Model>, DbErr> {
+    if ids.is_empty() {
+      return Ok(Vec::new());
+    }
+
+    let mut found = Self::visible()
+      .filter(posts::Column::Id.is_in(ids.iter().copied()))
+      .all(db)
+      .await?;
+    found.sort_by_key(|post| ids.iter().position(|id| *id == post.id));
+    Ok(found)
+  }
+
+  /// Up to `limit` posts most related to `post_id`, best first: each shared
+  /// tag counts 3, the same author 1, and title words in common up to 2 by
+  /// the share of the source title they cover. The post itself is never
+  /// included.
+  pub async fn find_related_ids(
+    db: &DbConn,
+    post_id: Uuid,
+    limit: u64,
+  ) -> Result<Vec<Uuid>, DbErr> {
+    // Candidates come from three indexed lookups rather than scoring every
+    // post. Title words are OR'ed so any overlap counts; the lexemes are
+    // already stemmed, so they are parsed back with the `simple` configuration
+    let rows = db
+      .query_all_raw(Statement::from_sql_and_values(
+        DbBackend::Postgres,
+        r#"
+          WITH source AS (
+            SELECT user_id,
+                   tsvector_to_array(to_tsvector('english', title)) AS lexemes,
+                   to_tsquery('simple', NULLIF(
+                     replace(plainto_tsquery('english', title)::text, ' & ', ' | '), ''
+                   )) AS words
+            FROM posts WHERE id = $1
+          ),
+          shared AS (
+            SELECT other.post_id, COUNT(*) AS tags
+            FROM post_tags AS mine
+            JOIN post_tags AS other ON other.tag_id = mine.tag_id AND other.post_id <> $1
+            WHERE mine.post_id = $1
+            GROUP BY other.post_id
+          ),
+          candidates AS (
+            SELECT post_id AS id FROM shared
+            UNION
+            SELECT posts.id FROM posts, source WHERE posts.user_id = source.user_id
+            UNION
+            SELECT posts.id FROM posts, source
+            WHERE to_tsvector('english', posts.title) @@ source.words
+          )
+          SELECT posts.id
+          FROM candidates
+          JOIN posts ON posts.id = candidates.id
+          CROSS JOIN source
+          LEFT JOIN shared ON shared.post_id = posts.id
+          WHERE posts.id <> $1
+            AND posts.status = 'PUBLISHED' AND posts.visibility = 'PUBLIC'
+          ORDER BY 3 * COALESCE(shared.tags, 0)
+                   + (posts.user_id = source.user_id)::int
+                   + 2 * COALESCE((
+                       SELECT COUNT(*)
+                       FROM unnest(tsvector_to_array(to_tsvector('english', posts.title))) AS lexeme
+                       WHERE lexeme = ANY(source.lexemes)
+                     )::float8 / NULLIF(cardinality(source.lexemes), 0), 0) DESC,
+                   posts.created_at DESC, posts.id
+          LIMIT $2
+        "#,
+        [post_id.into(), (limit as i64).into()],
+      ))
+      .await?;
+
+    rows
+      .iter()
+      .map(|row| row.try_get::<Uuid>("", "id"))
+      .collect()
+  }
+
+  /// Id and `updated_at` of every post, oldest first so earlier posts keep
+  /// their position as new ones are published.
+  pub async fn find_stamps(db: &DbConn) -> Result<Vec<(Uuid, DateTimeWithTimeZone)>, DbErr> {
+    Self::visible()
+      .select_only()
+      .column(posts::Column::Id)
+      .column(posts::Column::UpdatedAt)
+      .order_by_asc(posts::Column::CreatedAt)
+      .order_by_asc(posts::Column::Id)
+      .into_tuple()
+      .all(db)
+      .await
+  }
+}
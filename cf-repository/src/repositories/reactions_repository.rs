@@ -0,0 +1,168 @@
+use std::collections::HashMap;
+
+use ::cf_entity::entity::reactions;
+use ::cf_entity::entity::sea_orm_active_enums::ReactionKind;
+use sea_orm::prelude::{Expr, Uuid};
+use sea_orm::sea_query::OnConflict;
+use sea_orm::*;
+
+/// Kind of row a reaction is attached to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReactionTargetType {
+  Post,
+  Comment,
+}
+
+impl ReactionTargetType {
+  fn column(&self) -> reactions::Column {
+    match self {
+      ReactionTargetType::Post => reactions::Column::PostId,
+      ReactionTargetType::Comment => reactions::Column::CommentId,
+    }
+  }
+}
+
+/// A single post or comment a reaction is attached to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReactionTarget {
+  pub target_type: ReactionTargetType,
+  pub id: Uuid,
+}
+
+impl ReactionTarget {
+  pub fn post(id: Uuid) -> Self {
+    Self {
+      target_type: ReactionTargetType::Post,
+      id,
+    }
+  }
+
+  pub fn comment(id: Uuid) -> Self {
+    Self {
+      target_type: ReactionTargetType::Comment,
+      id,
+    }
+  }
+}
+
+pub struct ReactionsRepository;
+
+impl ReactionsRepository {
+  pub async fn find_by_user(
+    db: &DbConn,
+    user_id: Uuid,
+    target: ReactionTarget,
+  ) -> Result<Option<reactions::Model>, DbErr> {
+    reactions::Entity::find()
+      .filter(reactions::Column::UserId.eq(user_id))
+      .filter(target.target_type.column().eq(target.id))
+      .one(db)
+      .await
+  }
+
+  /// Keeps the reaction the user already has on the target, so a
+  /// concurrent toggle that got there first is not an error.
+  pub async fn create(
+    db: &DbConn,
+    user_id: Uuid,
+    target: ReactionTarget,
+    kind: ReactionKind,
+  ) -> Result<(), DbErr> {
+    let (post_id, comment_id) = match target.target_type {
+      ReactionTargetType::Post => (Some(target.id), None),
+      ReactionTargetType::Comment => (None, Some(target.id)),
+    };
+
+    let row = reactions::ActiveModel {
+      id: Set(Uuid::new_v4()),
+      user_id: Set(user_id),
+      post_id: Set(post_id),
+      comment_id: Set(comment_id),
+      kind: Set(kind),
+      ..Default::default()
+    };
+    reactions::Entity::insert(row)
+      .on_conflict(
+        OnConflict::columns([target.target_type.column(), reactions::Column::UserId])
+          .do_nothing()
+          .to_owned(),
+      )
+      .do_nothing()
+      .exec_without_returning(db)
+      .await
+      .map(|_| ())
+  }
+
+  /// Changing a reaction a concurrent toggle already removed is a no-op.
+  pub async fn update_kind(db: &DbConn, id: Uuid, kind: ReactionKind) -> Result<(), DbErr> {
+    reactions::Entity::update_many()
+      .col_expr(reactions::Column::Kind, Expr::value(kind))
+      .filter(reactions::Column::Id.eq(id))
+      .exec(db)
+      .await
+      .map(|_| ())
+  }
+
+  pub async fn delete(db: &DbConn, id: Uuid) -> Result<DeleteResult, DbErr> {
+    reactions::Entity::delete_by_id(id).exec(db).await
+  }
+
+  /// Reaction counts per kind for many targets in one grouped query.
+  /// Targets without reactions are left out of the map.
+  pub async fn count_by_targets(
+    db: &DbConn,
+    target_type: ReactionTargetType,
+    ids: &[Uuid],
+  ) -> Result<HashMap<Uuid, Vec<(ReactionKind, u64)>>, DbErr> {
+    if ids.is_empty() {
+      return Ok(HashMap::new());
+    }
+    let column = target_type.column();
+
+    let rows: Vec<(Uuid, ReactionKind, i64)> = reactions::Entity::find()
+      .select_only()
+      .column(column)
+      .column(reactions::Column::Kind)
+      .column_as(reactions::Column::Id.count(), "count")
+      .filter(column.is_in(ids.to_vec()))
+      .group_by(column)
+      .group_by(reactions::Column::Kind)
+      .into_tuple()
+      .all(db)
+      .await?;
+
+    let mut counts: HashMap<Uuid, Vec<(ReactionKind, u64)>> = HashMap::new();
+    for (target_id, kind, count) in rows {
+      counts
+        .entry(target_id)
+        .or_default()
+        .push((kind, count as u64));
+    }
+    Ok(counts)
+  }
+
+  /// The reaction `user_id` left on each of `ids`, keyed by target id.
+  pub async fn find_user_kinds(
+    db: &DbConn,
+    user_id: Uuid,
+    target_type: ReactionTargetType,
+    ids: &[Uuid],
+  ) -> Result<HashMap<Uuid, ReactionKind>, DbErr> {
+    if ids.is_empty() {
+      return Ok(HashMap::new());
+    }
+    let column = target_type.column();
+
+    let rows: Vec<(Uuid, ReactionKind)> = reactions::Entity::find()
+      .select_only()
+      .column(column)
+      .column(reactions::Column::Kind)
+      .filter(reactions::Column::UserId.eq(user_id))
+      .filter(column.is_in(ids.to_vec()))
+      .into_tuple()
+      .all(db)
+      .await?;
+
+    Ok(rows.into_iter().collect())
+  }
+}
@@ -0,0 +1,100 @@
+use std::collections::HashMap;
+
+use ::cf_entity::entity::{post_tags, tags};
+use sea_orm::prelude::Uuid;
+use sea_orm::sea_query::OnConflict;
+use sea_orm::*;
+
+pub struct TagsRepository;
+
+impl TagsRepository {
+  pub async fn find_by_slug(db: &DbConn, slug: &str) -> Result<Option<tags::Model>, DbErr> {
+    tags::Entity::find()
+      .filter(tags::Column::Slug.eq(slug))
+      .one(db)
+      .await
+  }
+
+  /// Tags for `(slug, name)` pairs, creating the missing ones. Existing tags
+  /// keep their original name.
+  pub async fn find_or_create<C: ConnectionTrait>(
+    db: &C,
+    tags: Vec<(String, String)>,
+  ) -> Result<Vec<tags::Model>, DbErr> {
+    if tags.is_empty() {
+      return Ok(Vec::new());
+    }
+    let slugs: Vec<String> = tags.iter().map(|(slug, _)| slug.clone()).collect();
+
+    let new_tags = tags.into_iter().map(|(slug, name)| tags::ActiveModel {
+      id: Set(Uuid::new_v4()),
+      name: Set(name),
+      slug: Set(slug),
+      ..Default::default()
+    });
+    tags::Entity::insert_many(new_tags)
+      .on_conflict(
+        OnConflict::column(tags::Column::Slug)
+          .do_nothing()
+          .to_owned(),
+      )
+      .do_nothing()
+      .exec_without_returning(db)
+      .await?;
+
+    tags::Entity::find()
+      .filter(tags::Column::Slug.is_in(slugs))
+      .order_by_asc(tags::Column::Slug)
+      .all(db)
+      .await
+  }
+
+  /// Replace the tags of a post.
+  pub async fn set_for_post<C: ConnectionTrait>(
+    db: &C,
+    post_id: Uuid,
+    tag_ids: &[Uuid],
+  ) -> Result<(), DbErr> {
+    post_tags::Entity::delete_many()
+      .filter(post_tags::Column::PostId.eq(post_id))
+      .exec(db)
+      .await?;
+
+    if !tag_ids.is_empty() {
+      let rows = tag_ids.iter().map(|tag_id| post_tags::ActiveModel {
+        post_id: Set(post_id),
+        tag_id: Set(*tag_id),
+      });
+      post_tags::Entity::insert_many(rows)
+        .exec_without_returning(db)
+        .await?;
+    }
+    Ok(())
+  }
+
+  /// Tags of many posts in one query, sorted by slug. Posts without tags are
+  /// left out of the map.
+  pub async fn find_by_post_ids(
+    db: &DbConn,
+    post_ids: &[Uuid],
+  ) -> Result<HashMap<Uuid, Vec<tags::Model>>, DbErr> {
+    if post_ids.is_empty() {
+      return Ok(HashMap::new());
+    }
+
+    let rows = post_tags::Entity::find()
+      .filter(post_tags::Column::PostId.is_in(post_ids.to_vec()))
+      .find_also_related(tags::Entity)
+      .order_by_asc(tags::Column::Slug)
+      .all(db)
+      .await?;
+
+    let mut by_post: HashMap<Uuid, Vec<tags::Model>> = HashMap::new();
+    for (post_tag, tag) in rows {
+      if let Some(tag) = tag {
+        by_post.entry(post_tag.post_id).or_default().push(tag);
+      }
+    }
+    Ok(by_post)
+  }
+}
@@ -1,7 +1,12 @@
-use ::cf_entity::entity::users;
-use sea_orm::prelude::Uuid;
+use ::cf_entity::entity::{posts, users};
+use sea_orm::prelude::{DateTimeWithTimeZone, Uuid};
+use sea_orm::sea_query::Query;
 use sea_orm::*;
 
+use super::PostsRepository;
+use crate::helpers::pagination::{fetch_cursor_page, fetch_offset_page};
+use crate::structs::pagination::{Cursor, CursorPage, OffsetPage};
+
 pub struct UsersRepository;
 
 impl UsersRepository {
@@ -9,6 +14,39 @@ impl UsersRepository {
     users::Entity::find_by_id(id).one(db).await
   }
 
+  pub async fn find_by_ids(db: &DbConn, ids: &[Uuid]) -> Result<Vec<users::Model>, DbErr> {
+    if ids.is_empty() {
+      return Ok(Vec::new());
+    }
+    users::Entity::find()
+      .filter(users::Column::Id.is_in(ids.to_vec()))
+      .all(db)
+      .await
+  }
+
+  /// Id and `updated_at` of users with at least one published, public post,
+  /// the profiles that are publicly listed. Oldest accounts first.
+  pub async fn find_public_stamps(db: &DbConn) -> Result<Vec<(Uuid, DateTimeWithTimeZone)>, DbErr> {
+    users::Entity::find()
+      .select_only()
+      .column(users::Column::Id)
+      .column(users::Column::UpdatedAt)
+      .filter(
+        users::Column::Id.in_subquery(
+          Query::select()
+            .column(posts::Column::UserId)
+            .from(posts::Entity)
+            .cond_where(PostsRepository::listed_for(None))
+            .to_owned(),
+        ),
+      )
+      .order_by_asc(users::Column::CreatedAt)
+      .order_by_asc(users::Column::Id)
+      .into_tuple()
+      .all(db)
+      .await
+  }
+
   pub async fn find_by_email(db: &DbConn, email: &str) -> Result<Option<users::Model>, DbErr> {
     users::Entity::find()
       .filter(users::Column::Email.eq(email))
@@ -20,11 +58,26 @@ impl UsersRepository {
     db: &DbConn,
     page: u64,
     per_page: u64,
-  ) -> Result<(Vec<users::Model>, u64), DbErr> {
-    let paginator = users::Entity::find().paginate(db, per_page);
-    let num_pages = paginator.num_pages().await?;
-    let items = paginator.fetch_page(page - 1).await?;
-    Ok((items, num_pages))
+  ) -> Result<OffsetPage<users::Model>, DbErr> {
+    let query = users::Entity::find().order_by_desc(users::Column::CreatedAt);
+    fetch_offset_page(db, query, page, per_page).await
+  }
+
+  pub async fn find_all_by_cursor(
+    db: &DbConn,
+    cursor: Option<Cursor>,
+    limit: u64,
+  ) -> Result<CursorPage<users::Model>, DbErr> {
+    fetch_cursor_page(
+      db,
+      users::Entity::find(),
+      users::Column::CreatedAt,
+      users::Column::Id,
+      cursor,
+      limit,
+      |user| (user.created_at, user.id),
+    )
+    .await
   }
 
   pub async fn create(db: &DbConn, form_data: users::ActiveModel) -> Result<users::Model, DbErr> {
@@ -52,6 +105,34 @@ impl UsersRepository {
     form_data.update(db).await
   }
 
+  /// Point `avatar_key` at a new avatar and return the updated user with the
+  /// key it replaced. The row is locked, so concurrent updates each see the
+  /// avatar the other one stored.
+  pub async fn replace_avatar(
+    db: &DbConn,
+    id: Uuid,
+    avatar_key: Option<String>,
+  ) -> Result<Option<(users::Model, Option<String>)>, DbErr> {
+    let txn = db.begin().await?;
+
+    let Some(user) = users::Entity::find_by_id(id)
+      .lock_exclusive()
+      .one(&txn)
+      .await?
+    else {
+      return Ok(None);
+    };
+    let previous = user.avatar_key.clone();
+
+    let mut user_model = user.into_active_model();
+    user_model.avatar_key = Set(avatar_key);
+    user_model.updated_at = Set(chrono::Utc::now().into());
+    let user = user_model.update(&txn).await?;
+
+    txn.commit().await?;
+    Ok(Some((user, previous)))
+  }
+
   pub async fn delete(db: &DbConn, id: Uuid) -> Result<DeleteResult, DbErr> {
     users::Entity::delete_by_id(id).exec(db).await
   }
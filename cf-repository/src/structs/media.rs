@@ -0,0 +1,12 @@
+use sea_orm::prelude::{DateTimeWithTimeZone, Uuid};
+
+/// Narrows a media listing; unset fields do not filter.
+#[derive(Debug, Clone, Default)]
+pub struct MediaFilter {
+  pub user_id: Option<Uuid>,
+  pub mime_type: Option<String>,
+  /// Uploaded at or after
+  pub from: Option<DateTimeWithTimeZone>,
+  /// Uploaded before
+  pub to: Option<DateTimeWithTimeZone>,
+}
@@ -1 +1,3 @@
+pub mod media;
+pub mod pagination;
 pub mod table_structs;
\ No newline at end of file
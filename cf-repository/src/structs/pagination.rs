@@ -0,0 +1,146 @@
+use base64::Engine;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use sea_orm::prelude::{DateTimeWithTimeZone, Uuid};
+
+/// One page of an offset listing along with the totals behind it.
+#[derive(Debug, Clone)]
+pub struct OffsetPage<T> {
+  pub items: Vec<T>,
+  pub page: u64,
+  pub per_page: u64,
+  pub total_items: u64,
+  pub total_pages: u64,
+}
+
+impl<T> OffsetPage<T> {
+  /// Swap the items for an already transformed list of the same length.
+  pub fn with_items<U>(self, items: Vec<U>) -> OffsetPage<U> {
+    OffsetPage {
+      items,
+      page: self.page,
+      per_page: self.per_page,
+      total_items: self.total_items,
+      total_pages: self.total_pages,
+    }
+  }
+}
+
+/// Which side of the cursor row a keyset query should read.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CursorDirection {
+  Next,
+  Prev,
+}
+
+/// Position in a `(created_at, id)` ordered listing.
+///
+/// Clients only ever see the encoded form, so the layout can change without
+/// breaking them as long as old cursors fail to decode cleanly.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cursor {
+  pub created_at: DateTimeWithTimeZone,
+  pub id: Uuid,
+  pub direction: CursorDirection,
+}
+
+impl Cursor {
+  pub fn new(created_at: DateTimeWithTimeZone, id: Uuid, direction: CursorDirection) -> Self {
+    Self {
+      created_at,
+      id,
+      direction,
+    }
+  }
+
+  pub fn encode(&self) -> String {
+    let direction = match self.direction {
+      CursorDirection::Next => "n",
+      CursorDirection::Prev => "p",
+    };
+    let raw = format!("{}|{}|{}", direction, self.created_at.to_rfc3339(), self.id);
+    URL_SAFE_NO_PAD.encode(raw)
+  }
+
+  pub fn decode(value: &str) -> Option<Self> {
+    let bytes = URL_SAFE_NO_PAD.decode(value).ok()?;
+    let raw = String::from_utf8(bytes).ok()?;
+    let mut parts = raw.splitn(3, '|');
+
+    let direction = match parts.next()? {
+      "n" => CursorDirection::Next,
+      "p" => CursorDirection::Prev,
+      _ => return None,
+    };
+    let created_at = DateTimeWithTimeZone::parse_from_rfc3339(parts.next()?).ok()?;
+    let id = Uuid::parse_str(parts.next()?).ok()?;
+
+    Some(Self {
+      created_at,
+      id,
+      direction,
+    })
+  }
+}
+
+/// Position in a listing that opens with its pinned rows, in their own order,
+/// before a `(created_at, id)` ordered rest.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PinnedCursor {
+  /// The pins from index `at` on, or the ones before it
+  Pin { at: u64, direction: CursorDirection },
+  /// Past a row of the rest
+  Row(Cursor),
+}
+
+impl PinnedCursor {
+  pub fn encode(&self) -> String {
+    match self {
+      Self::Pin { at, direction } => {
+        let direction = match direction {
+          CursorDirection::Next => "n",
+          CursorDirection::Prev => "p",
+        };
+        URL_SAFE_NO_PAD.encode(format!("pin|{}|{}", direction, at))
+      }
+      Self::Row(cursor) => cursor.encode(),
+    }
+  }
+
+  pub fn decode(value: &str) -> Option<Self> {
+    let bytes = URL_SAFE_NO_PAD.decode(value).ok()?;
+    let raw = String::from_utf8(bytes).ok()?;
+    let Some(pin) = raw.strip_prefix("pin|") else {
+      return Cursor::decode(value).map(Self::Row);
+    };
+
+    let (direction, at) = pin.split_once('|')?;
+    let direction = match direction {
+      "n" => CursorDirection::Next,
+      "p" => CursorDirection::Prev,
+      _ => return None,
+    };
+    Some(Self::Pin {
+      at: at.parse().ok()?,
+      direction,
+    })
+  }
+}
+
+/// One window of a keyset listing with the cursors to move either way.
+#[derive(Debug, Clone)]
+pub struct CursorPage<T> {
+  pub items: Vec<T>,
+  pub next_cursor: Option<String>,
+  pub prev_cursor: Option<String>,
+}
+
+impl<T> CursorPage<T> {
+  /// Swap the items for an already transformed list of the same length.
+  pub fn with_items<U>(self, items: Vec<U>) -> CursorPage<U> {
+    CursorPage {
+      items,
+      next_cursor: self.next_cursor,
+      prev_cursor: self.prev_cursor,
+    }
+  }
+}
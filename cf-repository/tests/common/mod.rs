@@ -0,0 +1,66 @@
+//! A scratch database for tests that need Postgres. `TEST_DATABASE_URL` must
+//! point at a connection allowed to create databases.
+
+use std::future::Future;
+
+use cf_migration::{Migrator, MigratorTrait};
+use sea_orm::prelude::Uuid;
+use sea_orm::*;
+
+/// Create a migrated database, run `check` against it and drop it
+/// afterwards, also when `check` panics.
+pub async fn with_database<F, Fut>(check: F)
+where
+  F: FnOnce(DatabaseConnection) -> Fut,
+  Fut: Future<Output = ()> + Send + 'static,
+{
+  let url = std::env::var("TEST_DATABASE_URL").expect("TEST_DATABASE_URL not set");
+  let admin = Database::connect(&url).await.unwrap();
+  let name = format!("cf_test_{}", Uuid::new_v4().simple());
+  admin
+    .execute_unprepared(&format!("CREATE DATABASE {}", name))
+    .await
+    .unwrap();
+
+  let db = Database::connect(&database_url(&url, &name)).await.unwrap();
+  db.execute_unprepared(r#"CREATE EXTENSION IF NOT EXISTS "uuid-ossp""#)
+    .await
+    .unwrap();
+  Migrator::up(&db, None).await.unwrap();
+  // Run on its own task so a panic comes back here instead of unwinding past
+  // the cleanup
+  let result = tokio::spawn(check(db)).await;
+
+  admin
+    .execute_unprepared(&format!("DROP DATABASE {} WITH (FORCE)", name))
+    .await
+    .unwrap();
+  if let Err(err) = result {
+    std::panic::resume_unwind(err.into_panic());
+  }
+}
+
+/// `url` pointing at database `name` instead.
+fn database_url(url: &str, name: &str) -> String {
+  let (base, query) = url.split_once('?').unwrap_or((url, ""));
+  let base = base.rsplit_once('/').map_or(base, |(server, _)| server);
+  if query.is_empty() {
+    format!("{}/{}", base, name)
+  } else {
+    format!("{}/{}?{}", base, name, query)
+  }
+}
+
+/// Ids of the rows `sql` returns in an `id` column.
+pub async fn query_ids(db: &DatabaseConnection, sql: &str, values: Vec<Value>) -> Vec<Uuid> {
+  db.query_all_raw(Statement::from_sql_and_values(
+    DbBackend::Postgres,
+    sql,
+    values,
+  ))
+  .await
+  .unwrap()
+  .iter()
+  .map(|row| row.try_get("", "id").unwrap())
+  .collect()
+}
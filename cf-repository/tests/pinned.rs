@@ -0,0 +1,122 @@
+//! Pinned posts across cursor pages, see [`common::with_database`].
+//!
+//! `TEST_DATABASE_URL=postgres://... cargo test -p cf-repository -- --ignored`
+
+mod common;
+
+use cf_entity::entity::posts;
+use cf_repository::repositories::PostsRepository;
+use cf_repository::structs::pagination::{CursorPage, PinnedCursor};
+use sea_orm::prelude::Uuid;
+use sea_orm::*;
+
+#[tokio::test]
+#[ignore = "needs TEST_DATABASE_URL"]
+async fn pins_carry_over_pages_both_ways() {
+  common::with_database(check_pins).await;
+}
+
+async fn check_pins(db: DatabaseConnection) {
+  let author = seed(&db).await;
+  let expected = common::query_ids(
+    &db,
+    r#"
+      SELECT posts.id FROM posts
+      LEFT JOIN post_placements AS pins
+        ON pins.post_id = posts.id AND pins.kind = 'PINNED'
+       AND (pins.expires_at IS NULL OR pins.expires_at > now())
+      WHERE posts.user_id = $1
+      ORDER BY pins.position IS NULL, pins.position, posts.created_at DESC, posts.id DESC
+    "#,
+    vec![author.into()],
+  )
+  .await;
+  assert_eq!(expected.len(), 12);
+
+  // Fewer, as many and more pins than fit a page
+  for limit in [2, 3, 5, 7, 20] {
+    let mut pages = vec![page(&db, author, None, limit).await];
+    assert_eq!(pages[0].prev_cursor, None);
+    while let Some(next) = pages.last().unwrap().next_cursor.clone() {
+      pages.push(page(&db, author, Some(&next), limit).await);
+    }
+    let seen: Vec<Uuid> = pages.iter().flat_map(ids).collect();
+    assert_eq!(seen, expected, "limit {}", limit);
+    assert!(pages.iter().all(|page| page.items.len() as u64 <= limit));
+
+    // Back from the last page through the same windows to the first
+    let mut back = vec![pages.last().unwrap().clone()];
+    while let Some(prev) = back.last().unwrap().prev_cursor.clone() {
+      back.push(page(&db, author, Some(&prev), limit).await);
+    }
+    back.reverse();
+    assert_eq!(
+      back.iter().map(ids).collect::<Vec<_>>(),
+      pages.iter().map(ids).collect::<Vec<_>>(),
+      "limit {}",
+      limit
+    );
+  }
+}
+
+/// Seeds an author with twelve posts, five of them pinned out of creation
+/// order and one more pinned with an expired placement, and another author
+/// whose pinned post must stay out of the listing. Returns the author.
+async fn seed(db: &DatabaseConnection) -> Uuid {
+  db.execute_unprepared(
+    r#"
+      INSERT INTO users (id, name, email)
+      SELECT uuid_generate_v4(), 'author ' || n, 'author' || n || '@example.com'
+      FROM generate_series(0, 1) AS n;
+
+      INSERT INTO posts (user_id, title, content, status, visibility, created_at)
+      SELECT users.id, 'post ' || g, '{}',
+             CASE WHEN g % 4 = 0 THEN 'DRAFT' ELSE 'PUBLISHED' END, 'PUBLIC',
+             TIMESTAMPTZ '2025-01-01' - g * INTERVAL '1 minute'
+      FROM generate_series(1, 12) AS g, users;
+
+      INSERT INTO post_placements (kind, post_id, position, expires_at, created_by)
+      SELECT 'PINNED', posts.id, n * 7 % 13,
+             CASE WHEN n = 12 THEN now() - INTERVAL '1 day' END, posts.user_id
+      FROM (
+        SELECT posts.*, row_number() OVER (PARTITION BY user_id ORDER BY created_at) AS n
+        FROM posts
+      ) AS posts
+      JOIN users ON users.id = posts.user_id
+      WHERE (users.email = 'author0@example.com' AND n IN (2, 5, 7, 8, 11, 12))
+         OR (users.email = 'author1@example.com' AND n = 1);
+    "#,
+  )
+  .await
+  .unwrap();
+
+  let row = db
+    .query_one_raw(Statement::from_string(
+      DbBackend::Postgres,
+      "SELECT id FROM users WHERE email = 'author0@example.com'",
+    ))
+    .await
+    .unwrap()
+    .unwrap();
+  row.try_get("", "id").unwrap()
+}
+
+async fn page(
+  db: &DatabaseConnection,
+  author: Uuid,
+  cursor: Option<&str>,
+  limit: u64,
+) -> CursorPage<posts::Model> {
+  PostsRepository::find_all_by_cursor(
+    db,
+    PostsRepository::owned_by(author),
+    cursor.map(|c| PinnedCursor::decode(c).unwrap()),
+    limit,
+  )
+  .await
+  .unwrap()
+}
+
+fn ids(page: &CursorPage<posts::Model>) -> Vec<Uuid> {
+  page.items.iter().map(|post| post.id).collect()
+}
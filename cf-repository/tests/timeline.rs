@@ -0,0 +1,170 @@
+//! Timeline paging against a real database, see [`common::with_database`].
+//!
+//! `TEST_DATABASE_URL=postgres://... cargo test -p cf-repository -- --ignored`
+
+mod common;
+
+use cf_entity::entity::posts;
+use cf_repository::helpers::pagination::cursor_query;
+use cf_repository::repositories::PostsRepository;
+use cf_repository::structs::pagination::Cursor;
+use sea_orm::prelude::Uuid;
+use sea_orm::*;
+
+const PAGE: u64 = 20;
+
+#[tokio::test]
+#[ignore = "needs TEST_DATABASE_URL"]
+async fn timeline_pages_follow_the_index_without_sorting() {
+  common::with_database(check_timeline).await;
+}
+
+async fn check_timeline(db: DatabaseConnection) {
+  let viewer = seed(&db).await;
+
+  for include_own in [false, true] {
+    let expected = expected_ids(&db, viewer, include_own).await;
+    assert!(expected.len() as u64 > 5 * PAGE);
+
+    // Forward through every page, then one step back
+    let mut seen = Vec::new();
+    let mut pages = Vec::new();
+    let mut cursor = None;
+    loop {
+      let page = PostsRepository::find_timeline_by_cursor(&db, viewer, include_own, cursor, PAGE)
+        .await
+        .unwrap();
+      assert!(page.items.len() as u64 <= PAGE);
+      seen.extend(page.items.iter().map(|post| post.id));
+      pages.push(page.clone());
+      match page.next_cursor {
+        Some(next) => cursor = Some(Cursor::decode(&next).unwrap()),
+        None => break,
+      }
+    }
+    assert_eq!(seen, expected);
+
+    let third = &pages[2];
+    let back = PostsRepository::find_timeline_by_cursor(
+      &db,
+      viewer,
+      include_own,
+      Cursor::decode(third.prev_cursor.as_ref().unwrap()),
+      PAGE,
+    )
+    .await
+    .unwrap();
+    let second: Vec<Uuid> = pages[1].items.iter().map(|post| post.id).collect();
+    assert_eq!(
+      back.items.iter().map(|post| post.id).collect::<Vec<_>>(),
+      second
+    );
+
+    // The first page and a later one. Near the end, with few posts left past
+    // the cursor, Postgres may rather read that range of the index and sort
+    // the handful of matches, which is cheaper there
+    let later = Cursor::decode(pages[2].next_cursor.as_ref().unwrap());
+    for cursor in [None, later.as_ref()] {
+      let plan = explain(&db, viewer, include_own, cursor).await;
+      assert!(
+        plan.contains("idx_posts_published_at_id"),
+        "timeline does not use idx_posts_published_at_id:\n{}",
+        plan
+      );
+      assert!(!plan.contains("Sort"), "timeline is sorted:\n{}", plan);
+    }
+  }
+}
+
+/// Seeds 200 authors with 5,000 posts in every state and visibility, pairs
+/// of them published at the same instant and all created a day before they
+/// were published, and a viewer following ten of the authors. Returns the
+/// viewer.
+async fn seed(db: &DatabaseConnection) -> Uuid {
+  db.execute_unprepared(
+    r#"
+      INSERT INTO users (id, name, email)
+      SELECT uuid_generate_v4(), 'author ' || n, 'author' || n || '@example.com'
+      FROM generate_series(0, 199) AS n;
+
+      CREATE TABLE authors AS
+      SELECT id, row_number() OVER (ORDER BY email) - 1 AS n FROM users;
+
+      INSERT INTO posts (user_id, title, content, status, visibility, published_at, created_at)
+      SELECT authors.id, 'post ' || g, '{}',
+             CASE WHEN g % 10 = 0 THEN 'DRAFT' ELSE 'PUBLISHED' END,
+             CASE WHEN g % 7 = 0 THEN 'UNLISTED'
+                  WHEN g % 11 = 0 THEN 'FOLLOWERS_ONLY'
+                  ELSE 'PUBLIC' END,
+             CASE WHEN g % 10 = 0 THEN NULL
+                  ELSE TIMESTAMPTZ '2025-01-01' - (g / 2) * INTERVAL '1 minute' END,
+             TIMESTAMPTZ '2025-01-01' - (g / 2) * INTERVAL '1 minute' - INTERVAL '1 day'
+      FROM generate_series(1, 5000) AS g
+      JOIN authors ON authors.n = g % 200;
+
+      INSERT INTO follows (follower_id, followee_id)
+      SELECT viewer.id, authors.id
+      FROM authors, authors AS viewer
+      WHERE viewer.n = 0 AND authors.n BETWEEN 1 AND 10;
+
+      ANALYZE;
+    "#,
+  )
+  .await
+  .unwrap();
+
+  let row = db
+    .query_one_raw(Statement::from_string(
+      DbBackend::Postgres,
+      "SELECT id FROM authors WHERE n = 0",
+    ))
+    .await
+    .unwrap()
+    .unwrap();
+  row.try_get("", "id").unwrap()
+}
+
+/// The whole timeline in one query written independently of the repository.
+async fn expected_ids(db: &DatabaseConnection, viewer: Uuid, include_own: bool) -> Vec<Uuid> {
+  common::query_ids(
+    db,
+    r#"
+      SELECT posts.id FROM posts
+      LEFT JOIN follows ON follows.followee_id = posts.user_id AND follows.follower_id = $1
+      WHERE posts.status = 'PUBLISHED'
+        AND ((follows.follower_id IS NOT NULL AND posts.visibility <> 'UNLISTED')
+             OR ($2 AND posts.user_id = $1))
+      ORDER BY posts.published_at DESC, posts.id DESC
+    "#,
+    vec![viewer.into(), include_own.into()],
+  )
+  .await
+}
+
+async fn explain(
+  db: &DatabaseConnection,
+  viewer: Uuid,
+  include_own: bool,
+  cursor: Option<&Cursor>,
+) -> String {
+  let query = cursor_query(
+    PostsRepository::timeline(viewer, include_own),
+    posts::Column::PublishedAt,
+    posts::Column::Id,
+    cursor,
+    PAGE,
+  )
+  .build(DbBackend::Postgres);
+
+  db.query_all_raw(Statement::from_sql_and_values(
+    DbBackend::Postgres,
+    format!("EXPLAIN {}", query.sql),
+    query.values.map(|values| values.0).unwrap_or_default(),
+  ))
+  .await
+  .unwrap()
+  .iter()
+  .map(|row| row.try_get::<String>("", "QUERY PLAN").unwrap())
+  .collect::<Vec<_>>()
+  .join("\n")
+}
@@ -0,0 +1,197 @@
+use std::fmt;
+use std::io::Cursor;
+
+use bytes::Bytes;
+use image::codecs::gif::{GifDecoder, GifEncoder, Repeat};
+use image::codecs::jpeg::JpegEncoder;
+use image::codecs::webp::WebPDecoder;
+use image::imageops::FilterType;
+use image::{AnimationDecoder, DynamicImage, ImageDecoder, ImageReader};
+
+pub use image::ImageFormat;
+
+/// Quality used whenever a JPEG is (re-)encoded.
+pub const JPEG_QUALITY: u8 = 85;
+
+#[derive(Debug)]
+pub enum ImagingError {
+  /// The bytes are not a JPEG, PNG, GIF or WebP image
+  Unsupported,
+  /// A crop rectangle is empty or reaches outside the image
+  OutOfBounds,
+  /// Animated WebP, which can't be re-encoded
+  Animated,
+  Decode(String),
+  Encode(String),
+}
+
+impl fmt::Display for ImagingError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      ImagingError::Unsupported => write!(f, "Unsupported image format"),
+      ImagingError::OutOfBounds => write!(f, "Crop rectangle is outside the image"),
+      ImagingError::Animated => write!(f, "Animated WebP images are not supported"),
+      ImagingError::Decode(msg) => write!(f, "Failed to decode image: {}", msg),
+      ImagingError::Encode(msg) => write!(f, "Failed to encode image: {}", msg),
+    }
+  }
+}
+
+impl std::error::Error for ImagingError {}
+
+/// Detect the format from the magic bytes, ignoring whatever type the
+/// client claimed. Only formats we accept for uploads are recognised.
+pub fn sniff_format(data: &[u8]) -> Option<ImageFormat> {
+  match image::guess_format(data).ok()? {
+    format @ (ImageFormat::Jpeg | ImageFormat::Png | ImageFormat::Gif | ImageFormat::WebP) => {
+      Some(format)
+    }
+    _ => None,
+  }
+}
+
+pub fn mime_type(format: ImageFormat) -> &'static str {
+  format.to_mime_type()
+}
+
+pub fn extension(format: ImageFormat) -> &'static str {
+  format.extensions_str().first().copied().unwrap_or("bin")
+}
+
+/// Decode an image by its magic bytes with the EXIF orientation applied, so
+/// pixels are upright even once the metadata is gone.
+pub fn decode(data: &[u8]) -> Result<(DynamicImage, ImageFormat), ImagingError> {
+  let format = sniff_format(data).ok_or(ImagingError::Unsupported)?;
+  let decode_err = |err: image::ImageError| ImagingError::Decode(err.to_string());
+
+  let mut decoder = ImageReader::with_format(Cursor::new(data), format)
+    .into_decoder()
+    .map_err(decode_err)?;
+  let orientation = decoder.orientation().map_err(decode_err)?;
+  let mut image = DynamicImage::from_decoder(decoder).map_err(decode_err)?;
+  image.apply_orientation(orientation);
+
+  Ok((image, format))
+}
+
+/// Re-encode the original so EXIF and other metadata (GPS, camera serials)
+/// are dropped. GIFs are re-encoded frame by frame to keep the animation and
+/// WebP becomes lossless. Animated WebP is rejected, the encoder can only
+/// write still images.
+pub fn strip_metadata(
+  original: Bytes,
+  image: &DynamicImage,
+  format: ImageFormat,
+) -> Result<Bytes, ImagingError> {
+  match format {
+    ImageFormat::Gif => reencode_gif(&original),
+    ImageFormat::WebP => {
+      let decoder = WebPDecoder::new(Cursor::new(&original[..]))
+        .map_err(|err| ImagingError::Decode(err.to_string()))?;
+      if decoder.has_animation() {
+        return Err(ImagingError::Animated);
+      }
+      encode(image, format)
+    }
+    _ => encode(image, format),
+  }
+}
+
+fn reencode_gif(original: &[u8]) -> Result<Bytes, ImagingError> {
+  let decoder =
+    GifDecoder::new(Cursor::new(original)).map_err(|err| ImagingError::Decode(err.to_string()))?;
+  let encode_err = |err: image::ImageError| ImagingError::Encode(err.to_string());
+
+  let mut buffer = Vec::new();
+  {
+    let mut encoder = GifEncoder::new(&mut buffer);
+    encoder.set_repeat(Repeat::Infinite).map_err(encode_err)?;
+    encoder
+      .try_encode_frames(decoder.into_frames())
+      .map_err(encode_err)?;
+    // The trailer is written when the encoder is dropped
+  }
+
+  Ok(Bytes::from(buffer))
+}
+
+/// Shrink to at most `max_width`, keeping the aspect ratio. Never upscales.
+pub fn resize_to_width(image: &DynamicImage, max_width: u32) -> DynamicImage {
+  if image.width() <= max_width {
+    return image.clone();
+  }
+  image.resize(max_width, u32::MAX, FilterType::Lanczos3)
+}
+
+/// Encode to JPEG, PNG or WebP. JPEG drops the alpha channel; WebP output is
+/// lossless, the only mode the encoder supports.
+pub fn encode(image: &DynamicImage, format: ImageFormat) -> Result<Bytes, ImagingError> {
+  let mut buffer = Cursor::new(Vec::new());
+  let encode_err = |err: image::ImageError| ImagingError::Encode(err.to_string());
+
+  match format {
+    ImageFormat::Jpeg => {
+      let encoder = JpegEncoder::new_with_quality(&mut buffer, JPEG_QUALITY);
+      DynamicImage::ImageRgb8(image.to_rgb8())
+        .write_with_encoder(encoder)
+        .map_err(encode_err)?;
+    }
+    ImageFormat::Png | ImageFormat::WebP => {
+      DynamicImage::ImageRgba8(image.to_rgba8())
+        .write_to(&mut buffer, format)
+        .map_err(encode_err)?;
+    }
+    _ => return Err(ImagingError::Unsupported),
+  }
+
+  Ok(Bytes::from(buffer.into_inner()))
+}
+
+/// How an image is fitted into a requested width and height.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Fit {
+  /// Fill the box exactly, cropping whatever overflows
+  Cover,
+  /// Fit inside the box, keeping the aspect ratio
+  Contain,
+  /// Stretch to the box, ignoring the aspect ratio
+  Fill,
+}
+
+/// Resize to the requested box. With only one side given the other follows
+/// the aspect ratio and `fit` does not matter.
+pub fn resize(
+  image: &DynamicImage,
+  width: Option<u32>,
+  height: Option<u32>,
+  fit: Fit,
+) -> DynamicImage {
+  match (width, height) {
+    (None, None) => image.clone(),
+    (Some(width), None) => image.resize(width, u32::MAX, FilterType::Lanczos3),
+    (None, Some(height)) => image.resize(u32::MAX, height, FilterType::Lanczos3),
+    (Some(width), Some(height)) => match fit {
+      Fit::Cover => image.resize_to_fill(width, height, FilterType::Lanczos3),
+      Fit::Contain => image.resize(width, height, FilterType::Lanczos3),
+      Fit::Fill => image.resize_exact(width, height, FilterType::Lanczos3),
+    },
+  }
+}
+
+/// Cut out the `width`×`height` rectangle at `x`, `y`. Fails when the
+/// rectangle is empty or not fully inside the image.
+pub fn crop(
+  image: &DynamicImage,
+  x: u32,
+  y: u32,
+  width: u32,
+  height: u32,
+) -> Result<DynamicImage, ImagingError> {
+  let fits = |offset: u32, size: u32, bound: u32| {
+    size > 0 && offset.checked_add(size).is_some_and(|end| end <= bound)
+  };
+  if !fits(x, width, image.width()) || !fits(y, height, image.height()) {
+    return Err(ImagingError::OutOfBounds);
+  }
+  Ok(image.crop_imm(x, y, width, height))
+}
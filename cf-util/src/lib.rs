@@ -1,6 +1,10 @@
+pub mod imaging;
 pub mod s3;
 pub mod sanitize;
+pub mod slug;
+pub mod storage;
 pub mod tiptap;
+pub mod urls;
 
 pub fn intro(info: &str) {
   let art = r#"
@@ -1,66 +1,201 @@
-use std::sync::Arc;
-use aws_config::Region;
+use std::time::{Duration, SystemTime};
+use async_trait::async_trait;
 use aws_sdk_s3::Client;
-use aws_sdk_s3::config::Credentials;
+use aws_sdk_s3::config::{BehaviorVersion, Credentials, Region};
+use aws_sdk_s3::presigning::PresigningConfig;
 use aws_sdk_s3::primitives::ByteStream;
+use bytes::Bytes;
 
+use crate::storage::{ObjectMeta, Storage, StorageError, StoredObject, validate_key};
+
+/// S3 (or S3 compatible) storage. The SDK client is built once and shared by
+/// clones, so requests reuse its connection pool.
 #[derive(Clone)]
 pub struct S3Client {
-    host: String,
-    access_key_id: String,
-    secret_access_key: String,
-    region: String,
+    client: Client,
     bucket: String
 }
 
 impl S3Client {
     pub fn new(host: &str, access_key_id: &str, secret_access_key: &str, region: &str, bucket: &str) -> S3Client {
-        Self {
-            host: host.to_owned(),
-            access_key_id: access_key_id.to_owned(),
-            secret_access_key: secret_access_key.to_owned(),
-            region: region.to_owned(),
-            bucket: bucket.to_owned(),
-        }
-    }
-
-    async fn get_client(&self) -> Arc<Client> {
-        let config = aws_config::from_env()
-            .endpoint_url(&self.host)
-            .region(Region::new(self.region.clone()))
+        let config = aws_sdk_s3::Config::builder()
+            .behavior_version(BehaviorVersion::latest())
+            .endpoint_url(host)
+            .region(Region::new(region.to_owned()))
             .credentials_provider(Credentials::new(
-                &self.access_key_id,
-                &self.secret_access_key,
+                access_key_id,
+                secret_access_key,
                 None,
                 None,
                 "static",
             ))
-            .load()
+            .build();
+
+        Self {
+            client: Client::from_conf(config),
+            bucket: bucket.to_owned(),
+        }
+    }
+}
+
+fn backend_error(err: impl Into<aws_sdk_s3::Error>) -> StorageError {
+    StorageError::Backend(err.into().to_string())
+}
+
+fn to_system_time(time: Option<&aws_sdk_s3::primitives::DateTime>) -> Option<SystemTime> {
+    time.and_then(|t| SystemTime::try_from(*t).ok())
+}
+
+fn presigning_config(expires_in: Duration) -> Result<PresigningConfig, StorageError> {
+    PresigningConfig::expires_in(expires_in).map_err(|err| StorageError::Backend(err.to_string()))
+}
+
+#[async_trait]
+impl Storage for S3Client {
+    async fn put(&self, key: &str, data: Bytes, content_type: &str) -> Result<(), StorageError> {
+        validate_key(key)?;
+        self.client
+            .put_object()
+            .bucket(&self.bucket)
+            .key(key)
+            .body(ByteStream::from(data))
+            .content_type(content_type)
+            .send()
+            .await
+            .map_err(backend_error)?;
+        Ok(())
+    }
+
+    async fn get(&self, key: &str) -> Result<StoredObject, StorageError> {
+        let output = self.client
+            .get_object()
+            .bucket(&self.bucket)
+            .key(key)
+            .send()
+            .await
+            .map_err(|err| match err.as_service_error() {
+                Some(service_err) if service_err.is_no_such_key() => StorageError::NotFound(key.to_string()),
+                _ => backend_error(err),
+            })?;
+
+        let content_type = output.content_type().map(str::to_owned);
+        let data = output.body
+            .collect()
+            .await
+            .map_err(|err| StorageError::Backend(err.to_string()))?
+            .into_bytes();
+
+        Ok(StoredObject { data, content_type })
+    }
+
+    async fn head(&self, key: &str) -> Result<Option<ObjectMeta>, StorageError> {
+        let result = self.client
+            .head_object()
+            .bucket(&self.bucket)
+            .key(key)
+            .send()
             .await;
 
-        Arc::new(Client::new(&config))
+        match result {
+            Ok(output) => Ok(Some(ObjectMeta {
+                key: key.to_owned(),
+                size: output.content_length().unwrap_or_default().max(0) as u64,
+                content_type: output.content_type().map(str::to_owned),
+                last_modified: to_system_time(output.last_modified()),
+            })),
+            Err(err) if err.as_service_error().is_some_and(|e| e.is_not_found()) => Ok(None),
+            Err(err) => Err(backend_error(err)),
+        }
+    }
+
+    async fn delete(&self, key: &str) -> Result<(), StorageError> {
+        self.client
+            .delete_object()
+            .bucket(&self.bucket)
+            .key(key)
+            .send()
+            .await
+            .map_err(backend_error)?;
+        Ok(())
     }
 
-    pub async fn upload_image(
+    async fn list(&self, prefix: &str) -> Result<Vec<ObjectMeta>, StorageError> {
+        let mut objects = Vec::new();
+        let mut continuation_token = None;
+
+        loop {
+            let output = self.client
+                .list_objects_v2()
+                .bucket(&self.bucket)
+                .prefix(prefix)
+                .set_continuation_token(continuation_token)
+                .send()
+                .await
+                .map_err(backend_error)?;
+
+            objects.extend(output.contents().iter().filter_map(|object| {
+                Some(ObjectMeta {
+                    key: object.key()?.to_owned(),
+                    size: object.size().unwrap_or_default().max(0) as u64,
+                    content_type: None,
+                    last_modified: to_system_time(object.last_modified()),
+                })
+            }));
+
+            match output.next_continuation_token() {
+                Some(token) if output.is_truncated().unwrap_or(false) => {
+                    continuation_token = Some(token.to_owned());
+                }
+                _ => break,
+            }
+        }
+
+        Ok(objects)
+    }
+
+    async fn copy(&self, from: &str, to: &str) -> Result<(), StorageError> {
+        validate_key(to)?;
+        self.client
+            .copy_object()
+            .bucket(&self.bucket)
+            .copy_source(format!("{}/{}", self.bucket, from))
+            .key(to)
+            .send()
+            .await
+            .map_err(backend_error)?;
+        Ok(())
+    }
+
+    async fn presign_get(&self, key: &str, expires_in: Duration) -> Result<String, StorageError> {
+        let request = self.client
+            .get_object()
+            .bucket(&self.bucket)
+            .key(key)
+            .presigned(presigning_config(expires_in)?)
+            .await
+            .map_err(backend_error)?;
+
+        Ok(request.uri().to_owned())
+    }
+
+    async fn presign_put(
         &self,
-        extension_name: &str,
+        key: &str,
         content_type: &str,
-        bytes: bytes::Bytes
-    ) -> Result<String, aws_sdk_s3::Error> {
-        let body = ByteStream::from(bytes);
-        let generate_file_name = uuid::Uuid::new_v4().to_string();
-        let client = self.get_client().await;
-        let key_name = String::from(generate_file_name.clone() + "." + &extension_name);
-
-        client
+        content_length: Option<u64>,
+        expires_in: Duration,
+    ) -> Result<String, StorageError> {
+        validate_key(key)?;
+        let request = self.client
             .put_object()
             .bucket(&self.bucket)
-            .key(&key_name)
-            .body(body)
+            .key(key)
             .content_type(content_type)
-            .send()
-            .await?;
+            .set_content_length(content_length.map(|len| len as i64))
+            .presigned(presigning_config(expires_in)?)
+            .await
+            .map_err(backend_error)?;
 
-        Ok(key_name)
+        Ok(request.uri().to_owned())
     }
-}
\ No newline at end of file
+}
@@ -1,21 +1,373 @@
+use std::borrow::Cow;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::sync::{Arc, Mutex};
+
 use ammonia::Builder;
-use maplit::{hashmap, hashset};
-
-pub fn sanitize_content_html_tiptap(html: &str) -> String {
-    Builder::default()
-        .add_tags(&["mark", "code", "pre", "figure", "figcaption"])
-
-        .add_tag_attributes("p", &["data-type"])
-        .add_tag_attributes("span", &["style", "class"])
-        .add_tag_attributes("img", &["title", "width", "height", "alt"])
-        .allowed_classes(hashmap![
-        "code" => hashset!["language-rust", "language-js", "language-python"],
-        "pre" => hashset!["language-rust", "language-js", "language-python"],
-        "p" => hashset!["text-left", "text-center", "text-right"]
-        ])
-
-        .set_tag_attribute_value("a", "target", "_blank")
-
-        .clean(html)
-        .to_string()
-}
\ No newline at end of file
+use serde::{Deserialize, Serialize};
+
+/// `rel` put on every link unless a policy says otherwise.
+pub const DEFAULT_LINK_REL: &str = "noopener noreferrer nofollow";
+
+/// Elements whose content is dropped along with them.
+const CLEAN_CONTENT_TAGS: [&str; 2] = ["script", "style"];
+
+const CODE_LANGUAGES: [&str; 18] = [
+    "bash", "c", "cpp", "css", "go", "html", "java", "javascript", "js", "json", "python", "rust",
+    "shell", "sql", "toml", "ts", "typescript", "yaml",
+];
+
+fn default_link_rel() -> Option<String> {
+    Some(DEFAULT_LINK_REL.to_string())
+}
+
+fn default_url_schemes() -> HashSet<String> {
+    set(&["http", "https", "mailto"])
+}
+
+/// What HTML survives sanitisation. Policies are plain data so they can be
+/// loaded from configuration; see [`SanitizePolicies`].
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct SanitizePolicy {
+    /// Elements kept; anything else is unwrapped, keeping its text
+    pub tags: HashSet<String>,
+    /// Attributes kept per element
+    pub tag_attributes: HashMap<String, HashSet<String>>,
+    /// Classes kept per element, all others are dropped
+    pub classes: HashMap<String, HashSet<String>>,
+    /// Schemes allowed in `href` and `src`, `http`, `https` and `mailto`
+    /// unless configured
+    #[serde(default = "default_url_schemes")]
+    pub url_schemes: HashSet<String>,
+    /// Replaces any `rel` on links; `None` keeps no `rel` at all
+    #[serde(default = "default_link_rel")]
+    pub link_rel: Option<String>,
+    /// Add `target="_blank"` to every link
+    pub links_new_tab: bool,
+    /// Hosts `<iframe src>` may point at, subdomains included. Only used
+    /// when `iframe` is in `tags`
+    pub iframe_hosts: HashSet<String>,
+}
+
+/// What a policy removed from a document.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct SanitizeReport {
+    /// Removed elements and how often, by tag name
+    pub removed_tags: BTreeMap<String, usize>,
+    /// Removed attributes and how often, as `tag[attribute]`
+    pub removed_attributes: BTreeMap<String, usize>,
+    /// Embed URLs dropped because their host is not allowed
+    pub blocked_embeds: Vec<String>,
+}
+
+impl SanitizeReport {
+    pub fn is_empty(&self) -> bool {
+        self.removed_tags.is_empty()
+            && self.removed_attributes.is_empty()
+            && self.blocked_embeds.is_empty()
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Sanitized {
+    pub html: String,
+    pub report: SanitizeReport,
+}
+
+fn set(items: &[&str]) -> HashSet<String> {
+    items.iter().map(|item| item.to_string()).collect()
+}
+
+impl SanitizePolicy {
+    /// Basic formatting and links, for comments.
+    pub fn strict() -> Self {
+        Self {
+            tags: set(&[
+                "a", "b", "blockquote", "br", "code", "em", "i", "li", "ol", "p", "pre", "s",
+                "strong", "u", "ul",
+            ]),
+            tag_attributes: HashMap::from([("a".to_string(), set(&["href"]))]),
+            url_schemes: default_url_schemes(),
+            link_rel: default_link_rel(),
+            links_new_tab: true,
+            ..Self::default()
+        }
+    }
+
+    /// Everything the article editor produces: headings, images, tables,
+    /// highlighted code and text alignment.
+    pub fn rich() -> Self {
+        let languages: HashSet<String> = CODE_LANGUAGES
+            .iter()
+            .map(|language| format!("language-{}", language))
+            .collect();
+
+        let mut tags = Self::strict().tags;
+        tags.extend(set(&[
+            "figcaption", "figure", "h1", "h2", "h3", "h4", "h5", "h6", "hr", "img", "mark", "span",
+            "sub", "sup", "table", "tbody", "td", "th", "thead", "tr",
+        ]));
+
+        Self {
+            tags,
+            tag_attributes: HashMap::from([
+                ("a".to_string(), set(&["href", "title"])),
+                ("img".to_string(), set(&["src", "alt", "title", "width", "height"])),
+                ("p".to_string(), set(&["data-type"])),
+                ("span".to_string(), set(&["style"])),
+                ("td".to_string(), set(&["colspan", "rowspan"])),
+                ("th".to_string(), set(&["colspan", "rowspan"])),
+            ]),
+            classes: HashMap::from([
+                ("code".to_string(), languages.clone()),
+                ("pre".to_string(), languages),
+                ("p".to_string(), set(&["text-left", "text-center", "text-right"])),
+            ]),
+            ..Self::strict()
+        }
+    }
+
+    /// Rich content plus video embeds from YouTube and Vimeo.
+    pub fn embed() -> Self {
+        let mut policy = Self::rich();
+        policy.tags.insert("iframe".to_string());
+        policy.tag_attributes.insert(
+            "iframe".to_string(),
+            set(&["src", "width", "height", "title", "allow", "allowfullscreen", "frameborder"]),
+        );
+        policy.iframe_hosts = set(&[
+            "youtube.com",
+            "youtube-nocookie.com",
+            "player.vimeo.com",
+        ]);
+        policy
+    }
+
+    fn iframe_host_allowed(hosts: &HashSet<String>, src: &str) -> bool {
+        let Ok(url) = url::Url::parse(src) else {
+            return false;
+        };
+        let Some(host) = url.host_str() else {
+            return false;
+        };
+        url.scheme() == "https"
+            && hosts
+                .iter()
+                .any(|allowed| host == allowed || host.ends_with(&format!(".{}", allowed)))
+    }
+
+    /// Clean `html` and report what was removed.
+    pub fn clean(&self, html: &str) -> Sanitized {
+        // Drop combinations ammonia refuses: managed `rel` and `class`
+        // attributes, and attributes on elements removed with their content
+        let mut tag_attributes = self.tag_attributes.clone();
+        tag_attributes.retain(|tag, _| !CLEAN_CONTENT_TAGS.contains(&tag.as_str()));
+        for (tag, attributes) in tag_attributes.iter_mut() {
+            if self.classes.contains_key(tag) {
+                attributes.remove("class");
+            }
+            if tag == "a" {
+                if self.link_rel.is_some() {
+                    attributes.remove("rel");
+                }
+                if self.links_new_tab {
+                    attributes.remove("target");
+                }
+            }
+        }
+
+        let mut builder = Builder::empty();
+        builder
+            .tags(
+                self.tags
+                    .iter()
+                    .map(String::as_str)
+                    .filter(|tag| !CLEAN_CONTENT_TAGS.contains(tag))
+                    .collect(),
+            )
+            .clean_content_tags(CLEAN_CONTENT_TAGS.into_iter().collect())
+            .tag_attributes(
+                tag_attributes
+                    .iter()
+                    .map(|(tag, attributes)| {
+                        (tag.as_str(), attributes.iter().map(String::as_str).collect())
+                    })
+                    .collect(),
+            )
+            .allowed_classes(
+                self.classes
+                    .iter()
+                    .map(|(tag, classes)| {
+                        (tag.as_str(), classes.iter().map(String::as_str).collect())
+                    })
+                    .collect(),
+            )
+            .url_schemes(self.url_schemes.iter().map(String::as_str).collect())
+            .link_rel(self.link_rel.as_deref());
+
+        if self.links_new_tab {
+            builder.set_tag_attribute_value("a", "target", "_blank");
+        }
+
+        let blocked = Arc::new(Mutex::new(Vec::new()));
+        if self.tags.contains("iframe") {
+            let hosts = self.iframe_hosts.clone();
+            let blocked = blocked.clone();
+            builder.attribute_filter(move |element, attribute, value| {
+                if element == "iframe"
+                    && attribute == "src"
+                    && !Self::iframe_host_allowed(&hosts, value)
+                {
+                    blocked.lock().unwrap().push(value.to_string());
+                    return None;
+                }
+                Some(Cow::Borrowed(value))
+            });
+        }
+
+        let cleaned = builder.clean(html).to_string();
+
+        let (tags_before, attributes_before) = count_markup(html);
+        let (tags_after, attributes_after) = count_markup(&cleaned);
+        let removed = |before: HashMap<String, usize>, after: &HashMap<String, usize>| {
+            before
+                .into_iter()
+                .filter_map(|(name, count)| {
+                    let kept = after.get(&name).copied().unwrap_or(0);
+                    (count > kept).then(|| (name, count - kept))
+                })
+                .collect::<BTreeMap<_, _>>()
+        };
+
+        let blocked_embeds = std::mem::take(&mut *blocked.lock().unwrap());
+        Sanitized {
+            report: SanitizeReport {
+                removed_tags: removed(tags_before, &tags_after),
+                removed_attributes: removed(attributes_before, &attributes_after),
+                blocked_embeds,
+            },
+            html: cleaned,
+        }
+    }
+}
+
+/// Named policies. Starts with `comments` (strict), `articles` (rich) and
+/// `embeds` (rich with iframes); configuration can replace those or add more.
+#[derive(Clone, Debug)]
+pub struct SanitizePolicies {
+    policies: HashMap<String, Arc<SanitizePolicy>>,
+}
+
+impl Default for SanitizePolicies {
+    fn default() -> Self {
+        Self {
+            policies: HashMap::from([
+                ("comments".to_string(), Arc::new(SanitizePolicy::strict())),
+                ("articles".to_string(), Arc::new(SanitizePolicy::rich())),
+                ("embeds".to_string(), Arc::new(SanitizePolicy::embed())),
+            ]),
+        }
+    }
+}
+
+impl SanitizePolicies {
+    /// Built-in policies overridden by a JSON object of name to policy.
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        let configured: HashMap<String, SanitizePolicy> = serde_json::from_str(json)?;
+        let mut policies = Self::default();
+        for (name, policy) in configured {
+            policies.policies.insert(name, Arc::new(policy));
+        }
+        Ok(policies)
+    }
+
+    pub fn get(&self, name: &str) -> Option<Arc<SanitizePolicy>> {
+        self.policies.get(name).cloned()
+    }
+
+    pub fn names(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = self.policies.keys().map(String::as_str).collect();
+        names.sort_unstable();
+        names
+    }
+}
+
+/// Count start tags by name and attributes as `tag[attribute]`. A loose
+/// scanner, only good enough to compare markup before and after cleaning.
+fn count_markup(html: &str) -> (HashMap<String, usize>, HashMap<String, usize>) {
+    let mut tags = HashMap::new();
+    let mut attributes = HashMap::new();
+    let bytes = html.as_bytes();
+    let mut i = 0;
+
+    let name_end = |start: usize| {
+        let mut end = start;
+        while end < bytes.len()
+            && !bytes[end].is_ascii_whitespace()
+            && !matches!(bytes[end], b'>' | b'/' | b'=')
+        {
+            end += 1;
+        }
+        end
+    };
+
+    while i < bytes.len() {
+        if bytes[i] != b'<' {
+            i += 1;
+            continue;
+        }
+        if html[i..].starts_with("<!--") {
+            i = html[i..].find("-->").map(|end| i + end + 3).unwrap_or(bytes.len());
+            continue;
+        }
+        if !bytes.get(i + 1).is_some_and(u8::is_ascii_alphabetic) {
+            i += 1;
+            continue;
+        }
+
+        let end = name_end(i + 1);
+        let tag = html[i + 1..end].to_ascii_lowercase();
+        *tags.entry(tag.clone()).or_insert(0) += 1;
+        i = end;
+
+        while i < bytes.len() && bytes[i] != b'>' {
+            if bytes[i].is_ascii_whitespace() || bytes[i] == b'/' {
+                i += 1;
+                continue;
+            }
+            let end = name_end(i).max(i + 1);
+            let attribute = html[i..end].to_ascii_lowercase();
+            *attributes.entry(format!("{}[{}]", tag, attribute)).or_insert(0) += 1;
+            i = end;
+
+            if bytes.get(i) == Some(&b'=') {
+                i += 1;
+                match bytes.get(i) {
+                    Some(&quote @ (b'"' | b'\'')) => {
+                        i += 1;
+                        while i < bytes.len() && bytes[i] != quote {
+                            i += 1;
+                        }
+                        i += 1;
+                    }
+                    _ => {
+                        while i < bytes.len() && !bytes[i].is_ascii_whitespace() && bytes[i] != b'>' {
+                            i += 1;
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    (tags, attributes)
+}
+
+/// Clean editor HTML with the `articles` policy, as configured in
+/// `policies`.
+pub fn sanitize_content_html_tiptap(html: &str, policies: &SanitizePolicies) -> String {
+    match policies.get("articles") {
+        Some(policy) => policy.clean(html).html,
+        None => SanitizePolicy::rich().clean(html).html,
+    }
+}
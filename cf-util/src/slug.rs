@@ -0,0 +1,17 @@
+/// Lowercase ASCII letters and digits with single dashes in between, e.g.
+/// `"Rust & WebAssembly"` becomes `"rust-webassembly"`. Other characters act
+/// as separators, so the result may be empty.
+pub fn slugify(value: &str) -> String {
+  let mut slug = String::with_capacity(value.len());
+  for c in value.chars() {
+    if c.is_ascii_alphanumeric() {
+      slug.push(c.to_ascii_lowercase());
+    } else if !slug.is_empty() && !slug.ends_with('-') {
+      slug.push('-');
+    }
+  }
+  while slug.ends_with('-') {
+    slug.pop();
+  }
+  slug
+}
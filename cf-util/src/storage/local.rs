@@ -0,0 +1,152 @@
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use tokio::fs;
+
+use super::{ObjectMeta, Storage, StorageError, StoredObject, validate_key};
+
+/// Stores objects as plain files under `root`, for local development.
+/// Content types are not persisted and are guessed from the extension.
+#[derive(Clone, Debug)]
+pub struct LocalStorage {
+  root: PathBuf,
+}
+
+impl LocalStorage {
+  pub fn new(root: impl Into<PathBuf>) -> Self {
+    Self { root: root.into() }
+  }
+
+  fn path(&self, key: &str) -> Result<PathBuf, StorageError> {
+    validate_key(key)?;
+    Ok(self.root.join(key))
+  }
+
+  async fn meta(&self, key: String, path: &Path) -> Result<ObjectMeta, StorageError> {
+    let metadata = fs::metadata(path).await?;
+    Ok(ObjectMeta {
+      content_type: guess_content_type(&key),
+      key,
+      size: metadata.len(),
+      last_modified: metadata.modified().ok(),
+    })
+  }
+}
+
+fn guess_content_type(key: &str) -> Option<String> {
+  let extension = key.rsplit_once('.')?.1.to_ascii_lowercase();
+  let content_type = match extension.as_str() {
+    "jpg" | "jpeg" => "image/jpeg",
+    "png" => "image/png",
+    "gif" => "image/gif",
+    "webp" => "image/webp",
+    "xml" => "application/xml",
+    "json" => "application/json",
+    "txt" => "text/plain",
+    _ => return None,
+  };
+  Some(content_type.to_string())
+}
+
+#[async_trait]
+impl Storage for LocalStorage {
+  async fn put(&self, key: &str, data: Bytes, _content_type: &str) -> Result<(), StorageError> {
+    let path = self.path(key)?;
+    if let Some(parent) = path.parent() {
+      fs::create_dir_all(parent).await?;
+    }
+    fs::write(path, data).await?;
+    Ok(())
+  }
+
+  async fn get(&self, key: &str) -> Result<StoredObject, StorageError> {
+    let data = fs::read(self.path(key)?)
+      .await
+      .map_err(|err| match err.kind() {
+        std::io::ErrorKind::NotFound => StorageError::NotFound(key.to_string()),
+        _ => StorageError::Io(err),
+      })?;
+    Ok(StoredObject {
+      data: Bytes::from(data),
+      content_type: guess_content_type(key),
+    })
+  }
+
+  async fn head(&self, key: &str) -> Result<Option<ObjectMeta>, StorageError> {
+    let path = self.path(key)?;
+    match self.meta(key.to_string(), &path).await {
+      Ok(meta) => Ok(Some(meta)),
+      Err(StorageError::NotFound(_)) => Ok(None),
+      Err(err) => Err(err),
+    }
+  }
+
+  async fn delete(&self, key: &str) -> Result<(), StorageError> {
+    match fs::remove_file(self.path(key)?).await {
+      Err(err) if err.kind() != std::io::ErrorKind::NotFound => Err(StorageError::Io(err)),
+      _ => Ok(()),
+    }
+  }
+
+  async fn list(&self, prefix: &str) -> Result<Vec<ObjectMeta>, StorageError> {
+    let mut objects = Vec::new();
+    let mut pending = vec![(self.root.clone(), String::new())];
+
+    while let Some((dir, dir_key)) = pending.pop() {
+      let mut entries = match fs::read_dir(&dir).await {
+        Ok(entries) => entries,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => continue,
+        Err(err) => return Err(StorageError::Io(err)),
+      };
+
+      while let Some(entry) = entries.next_entry().await? {
+        let name = entry.file_name().to_string_lossy().into_owned();
+        let key = format!("{}{}", dir_key, name);
+
+        if entry.file_type().await?.is_dir() {
+          // Only descend into directories that can still match the prefix
+          let dir_prefix = format!("{}/", key);
+          if dir_prefix.starts_with(prefix) || prefix.starts_with(&dir_prefix) {
+            pending.push((entry.path(), dir_prefix));
+          }
+        } else if key.starts_with(prefix) {
+          objects.push(self.meta(key, &entry.path()).await?);
+        }
+      }
+    }
+
+    objects.sort_by(|a, b| a.key.cmp(&b.key));
+    Ok(objects)
+  }
+
+  async fn copy(&self, from: &str, to: &str) -> Result<(), StorageError> {
+    let source = self.path(from)?;
+    let target = self.path(to)?;
+    if let Some(parent) = target.parent() {
+      fs::create_dir_all(parent).await?;
+    }
+    fs::copy(source, target)
+      .await
+      .map_err(|err| match err.kind() {
+        std::io::ErrorKind::NotFound => StorageError::NotFound(from.to_string()),
+        _ => StorageError::Io(err),
+      })?;
+    Ok(())
+  }
+
+  async fn presign_get(&self, _key: &str, _expires_in: Duration) -> Result<String, StorageError> {
+    Err(StorageError::Unsupported("presigned URLs"))
+  }
+
+  async fn presign_put(
+    &self,
+    _key: &str,
+    _content_type: &str,
+    _content_length: Option<u64>,
+    _expires_in: Duration,
+  ) -> Result<String, StorageError> {
+    Err(StorageError::Unsupported("presigned URLs"))
+  }
+}
@@ -0,0 +1,124 @@
+use std::collections::BTreeMap;
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, SystemTime};
+
+use async_trait::async_trait;
+use bytes::Bytes;
+
+use super::{ObjectMeta, Storage, StorageError, StoredObject, validate_key};
+
+struct Entry {
+  data: Bytes,
+  content_type: String,
+  last_modified: SystemTime,
+}
+
+impl Entry {
+  fn meta(&self, key: &str) -> ObjectMeta {
+    ObjectMeta {
+      key: key.to_string(),
+      size: self.data.len() as u64,
+      content_type: Some(self.content_type.clone()),
+      last_modified: Some(self.last_modified),
+    }
+  }
+}
+
+/// Keeps objects in process memory. Meant for tests; everything is lost on
+/// restart and presigned URLs use a `memory://` scheme nothing can fetch.
+#[derive(Clone, Default)]
+pub struct MemoryStorage {
+  objects: Arc<RwLock<BTreeMap<String, Entry>>>,
+}
+
+impl MemoryStorage {
+  pub fn new() -> Self {
+    Self::default()
+  }
+}
+
+#[async_trait]
+impl Storage for MemoryStorage {
+  async fn put(&self, key: &str, data: Bytes, content_type: &str) -> Result<(), StorageError> {
+    validate_key(key)?;
+    let entry = Entry {
+      data,
+      content_type: content_type.to_string(),
+      last_modified: SystemTime::now(),
+    };
+    self.objects.write().unwrap().insert(key.to_string(), entry);
+    Ok(())
+  }
+
+  async fn get(&self, key: &str) -> Result<StoredObject, StorageError> {
+    let objects = self.objects.read().unwrap();
+    let entry = objects
+      .get(key)
+      .ok_or_else(|| StorageError::NotFound(key.to_string()))?;
+    Ok(StoredObject {
+      data: entry.data.clone(),
+      content_type: Some(entry.content_type.clone()),
+    })
+  }
+
+  async fn head(&self, key: &str) -> Result<Option<ObjectMeta>, StorageError> {
+    Ok(self.objects.read().unwrap().get(key).map(|e| e.meta(key)))
+  }
+
+  async fn delete(&self, key: &str) -> Result<(), StorageError> {
+    self.objects.write().unwrap().remove(key);
+    Ok(())
+  }
+
+  async fn list(&self, prefix: &str) -> Result<Vec<ObjectMeta>, StorageError> {
+    Ok(
+      self
+        .objects
+        .read()
+        .unwrap()
+        .range(prefix.to_string()..)
+        .take_while(|(key, _)| key.starts_with(prefix))
+        .map(|(key, entry)| entry.meta(key))
+        .collect(),
+    )
+  }
+
+  async fn copy(&self, from: &str, to: &str) -> Result<(), StorageError> {
+    validate_key(to)?;
+    let mut objects = self.objects.write().unwrap();
+    let entry = objects
+      .get(from)
+      .ok_or_else(|| StorageError::NotFound(from.to_string()))?;
+    let copied = Entry {
+      data: entry.data.clone(),
+      content_type: entry.content_type.clone(),
+      last_modified: SystemTime::now(),
+    };
+    objects.insert(to.to_string(), copied);
+    Ok(())
+  }
+
+  async fn presign_get(&self, key: &str, expires_in: Duration) -> Result<String, StorageError> {
+    validate_key(key)?;
+    Ok(format!(
+      "memory://{}?method=GET&expires_in={}",
+      key,
+      expires_in.as_secs()
+    ))
+  }
+
+  async fn presign_put(
+    &self,
+    key: &str,
+    _content_type: &str,
+    _content_length: Option<u64>,
+    expires_in: Duration,
+  ) -> Result<String, StorageError> {
+    validate_key(key)?;
+    Ok(format!(
+      "memory://{}?method=PUT&expires_in={}",
+      key,
+      expires_in.as_secs()
+    ))
+  }
+}
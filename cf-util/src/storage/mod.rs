@@ -0,0 +1,106 @@
+use std::fmt;
+use std::time::{Duration, SystemTime};
+
+use async_trait::async_trait;
+use bytes::Bytes;
+
+pub mod local;
+pub mod memory;
+
+pub use local::LocalStorage;
+pub use memory::MemoryStorage;
+
+/// Size and type of a stored object, without its body.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ObjectMeta {
+  pub key: String,
+  pub size: u64,
+  pub content_type: Option<String>,
+  pub last_modified: Option<SystemTime>,
+}
+
+#[derive(Clone, Debug)]
+pub struct StoredObject {
+  pub data: Bytes,
+  pub content_type: Option<String>,
+}
+
+#[derive(Debug)]
+pub enum StorageError {
+  NotFound(String),
+  InvalidKey(String),
+  /// The backend cannot do this, e.g. presigning on the local filesystem
+  Unsupported(&'static str),
+  Io(std::io::Error),
+  Backend(String),
+}
+
+impl fmt::Display for StorageError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      StorageError::NotFound(key) => write!(f, "Object not found: {}", key),
+      StorageError::InvalidKey(key) => write!(f, "Invalid object key: {}", key),
+      StorageError::Unsupported(op) => write!(f, "Storage backend does not support {}", op),
+      StorageError::Io(err) => write!(f, "Storage I/O error: {}", err),
+      StorageError::Backend(msg) => write!(f, "Storage error: {}", msg),
+    }
+  }
+}
+
+impl std::error::Error for StorageError {}
+
+impl From<std::io::Error> for StorageError {
+  fn from(err: std::io::Error) -> Self {
+    match err.kind() {
+      std::io::ErrorKind::NotFound => StorageError::NotFound(err.to_string()),
+      _ => StorageError::Io(err),
+    }
+  }
+}
+
+/// Object storage used for uploaded media and generated files. Keys are
+/// `/` separated relative paths such as `media/<id>.jpg`.
+#[async_trait]
+pub trait Storage: Send + Sync {
+  async fn put(&self, key: &str, data: Bytes, content_type: &str) -> Result<(), StorageError>;
+
+  async fn get(&self, key: &str) -> Result<StoredObject, StorageError>;
+
+  /// `None` when the object does not exist.
+  async fn head(&self, key: &str) -> Result<Option<ObjectMeta>, StorageError>;
+
+  /// Deleting a missing object is not an error.
+  async fn delete(&self, key: &str) -> Result<(), StorageError>;
+
+  /// Every object whose key starts with `prefix`.
+  async fn list(&self, prefix: &str) -> Result<Vec<ObjectMeta>, StorageError>;
+
+  async fn copy(&self, from: &str, to: &str) -> Result<(), StorageError>;
+
+  async fn presign_get(&self, key: &str, expires_in: Duration) -> Result<String, StorageError>;
+
+  /// URL a client can upload to directly. When `content_length` is given
+  /// the upload must be exactly that size.
+  async fn presign_put(
+    &self,
+    key: &str,
+    content_type: &str,
+    content_length: Option<u64>,
+    expires_in: Duration,
+  ) -> Result<String, StorageError>;
+}
+
+/// Reject keys that could escape a prefix or the local storage root.
+pub fn validate_key(key: &str) -> Result<(), StorageError> {
+  let invalid = key.is_empty()
+    || key.starts_with('/')
+    || key.contains('\\')
+    || key
+      .split('/')
+      .any(|segment| segment.is_empty() || segment == "." || segment == "..");
+
+  if invalid {
+    return Err(StorageError::InvalidKey(key.to_string()));
+  }
+  Ok(())
+}
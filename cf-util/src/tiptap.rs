@@ -31,4 +31,181 @@ pub fn create_excerpt(json: &Value, word_limit: usize) -> String {
 
     let excerpt = words[..word_limit].join(" ");
     format!("{}...", excerpt)
-}
\ No newline at end of file
+}
+
+/// Parse stored post content. Anything that is not a Tiptap document, such
+/// as legacy plain text, becomes a document with a single paragraph.
+pub fn parse_document(content: &str) -> Value {
+    serde_json::from_str::<Value>(content)
+        .ok()
+        .filter(|value| value.is_object())
+        .unwrap_or_else(|| {
+            serde_json::json!({
+                "type": "doc",
+                "content": [{
+                    "type": "paragraph",
+                    "content": [{ "type": "text", "text": content }]
+                }]
+            })
+        })
+}
+
+fn escape_html(text: &str, out: &mut String) {
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            _ => out.push(c),
+        }
+    }
+}
+
+fn attr<'a>(node: &'a Value, name: &str) -> Option<&'a Value> {
+    node.get("attrs").and_then(|attrs| attrs.get(name)).filter(|v| !v.is_null())
+}
+
+fn attr_str(node: &Value, name: &str) -> Option<String> {
+    attr(node, name).map(|v| match v.as_str() {
+        Some(s) => s.to_string(),
+        None => v.to_string(),
+    })
+}
+
+fn render_text(node: &Value, out: &mut String) {
+    let text = node.get("text").and_then(|v| v.as_str()).unwrap_or_default();
+    let marks = node
+        .get("marks")
+        .and_then(|v| v.as_array())
+        .map(Vec::as_slice)
+        .unwrap_or_default();
+
+    let mut closing = Vec::new();
+    for mark in marks {
+        let (open, close) = match mark.get("type").and_then(|v| v.as_str()) {
+            Some("bold") => ("<strong>".to_string(), "</strong>"),
+            Some("italic") => ("<em>".to_string(), "</em>"),
+            Some("underline") => ("<u>".to_string(), "</u>"),
+            Some("strike") => ("<s>".to_string(), "</s>"),
+            Some("code") => ("<code>".to_string(), "</code>"),
+            Some("highlight") => ("<mark>".to_string(), "</mark>"),
+            Some("subscript") => ("<sub>".to_string(), "</sub>"),
+            Some("superscript") => ("<sup>".to_string(), "</sup>"),
+            Some("link") => {
+                let mut open = String::from("<a href=\"");
+                escape_html(&attr_str(mark, "href").unwrap_or_default(), &mut open);
+                open.push_str("\">");
+                (open, "</a>")
+            }
+            _ => continue,
+        };
+        out.push_str(&open);
+        closing.push(close);
+    }
+
+    escape_html(text, out);
+    for close in closing.into_iter().rev() {
+        out.push_str(close);
+    }
+}
+
+fn render_children(node: &Value, out: &mut String) {
+    if let Some(content) = node.get("content").and_then(|v| v.as_array()) {
+        for child in content {
+            render_node(child, out);
+        }
+    }
+}
+
+fn render_wrapped(tag: &str, attrs: &str, node: &Value, out: &mut String) {
+    out.push('<');
+    out.push_str(tag);
+    out.push_str(attrs);
+    out.push('>');
+    render_children(node, out);
+    out.push_str("</");
+    out.push_str(tag);
+    out.push('>');
+}
+
+fn render_node(node: &Value, out: &mut String) {
+    let align = attr_str(node, "textAlign")
+        .filter(|align| matches!(align.as_str(), "left" | "center" | "right"))
+        .map(|align| format!(" class=\"text-{}\"", align))
+        .unwrap_or_default();
+
+    match node.get("type").and_then(|v| v.as_str()) {
+        Some("text") => render_text(node, out),
+        Some("paragraph") => render_wrapped("p", &align, node, out),
+        Some("heading") => {
+            let level = attr(node, "level").and_then(|v| v.as_u64()).unwrap_or(1).clamp(1, 6);
+            render_wrapped(&format!("h{}", level), "", node, out);
+        }
+        Some("bulletList") => render_wrapped("ul", "", node, out),
+        Some("orderedList") => render_wrapped("ol", "", node, out),
+        Some("listItem") => render_wrapped("li", "", node, out),
+        Some("blockquote") => render_wrapped("blockquote", "", node, out),
+        Some("codeBlock") => {
+            let class = attr_str(node, "language")
+                .map(|language| {
+                    let mut class = String::from(" class=\"language-");
+                    escape_html(&language, &mut class);
+                    class.push('"');
+                    class
+                })
+                .unwrap_or_default();
+            out.push_str("<pre><code");
+            out.push_str(&class);
+            out.push('>');
+            render_children(node, out);
+            out.push_str("</code></pre>");
+        }
+        Some("hardBreak") => out.push_str("<br>"),
+        Some("horizontalRule") => out.push_str("<hr>"),
+        Some("image") => {
+            out.push_str("<img");
+            for name in ["src", "alt", "title"] {
+                if let Some(value) = attr_str(node, name) {
+                    out.push(' ');
+                    out.push_str(name);
+                    out.push_str("=\"");
+                    escape_html(&value, out);
+                    out.push('"');
+                }
+            }
+            out.push('>');
+        }
+        // `doc` and anything unknown: keep the content, drop the wrapper
+        _ => render_children(node, out),
+    }
+}
+
+/// Render a Tiptap document to HTML. Only the markup is produced; run the
+/// result through a sanitisation policy before serving it.
+pub fn render_html(json: &Value) -> String {
+    let mut out = String::new();
+    render_node(json, &mut out);
+    out
+}
+
+/// `src` of every image node in a Tiptap document, in document order.
+pub fn image_sources(json: &Value) -> Vec<String> {
+    fn traverse(node: &Value, sources: &mut Vec<String>) {
+        if node.get("type").and_then(|v| v.as_str()) == Some("image")
+            && let Some(src) = attr_str(node, "src")
+        {
+            sources.push(src);
+        }
+        if let Some(content) = node.get("content").and_then(|v| v.as_array()) {
+            for child in content {
+                traverse(child, sources);
+            }
+        }
+    }
+
+    let mut sources = Vec::new();
+    traverse(json, &mut sources);
+    sources
+}
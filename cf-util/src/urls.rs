@@ -0,0 +1,86 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::storage::{Storage, StorageError};
+
+/// Whether objects may be linked to directly or need a signed URL.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Visibility {
+  /// Served from the public base URL, usually a CDN in front of the bucket
+  Public,
+  /// Served through a presigned GET URL that expires
+  Private,
+}
+
+impl std::str::FromStr for Visibility {
+  type Err = String;
+
+  fn from_str(value: &str) -> Result<Self, Self::Err> {
+    match value.trim().to_lowercase().as_str() {
+      "public" => Ok(Visibility::Public),
+      "private" => Ok(Visibility::Private),
+      other => Err(format!("Unknown visibility '{}'", other)),
+    }
+  }
+}
+
+/// Turns storage keys into URLs clients can fetch. The bucket has a default
+/// visibility and prefixes can override it; the longest matching prefix wins.
+#[derive(Clone)]
+pub struct UrlResolver {
+  storage: Arc<dyn Storage>,
+  public_base_url: String,
+  default_visibility: Visibility,
+  rules: Vec<(String, Visibility)>,
+  signed_expiry: Duration,
+}
+
+impl UrlResolver {
+  pub fn new(
+    storage: Arc<dyn Storage>,
+    public_base_url: &str,
+    default_visibility: Visibility,
+    signed_expiry: Duration,
+  ) -> Self {
+    Self {
+      storage,
+      public_base_url: public_base_url.trim_end_matches('/').to_string(),
+      default_visibility,
+      rules: Vec::new(),
+      signed_expiry,
+    }
+  }
+
+  /// Give every key starting with `prefix` its own visibility.
+  pub fn with_rule(mut self, prefix: &str, visibility: Visibility) -> Self {
+    self.rules.push((prefix.to_string(), visibility));
+    // Longest first, so the first match is the most specific one
+    self
+      .rules
+      .sort_by_key(|(prefix, _)| std::cmp::Reverse(prefix.len()));
+    self
+  }
+
+  pub fn visibility(&self, key: &str) -> Visibility {
+    self
+      .rules
+      .iter()
+      .find(|(prefix, _)| key.starts_with(prefix.as_str()))
+      .map(|(_, visibility)| *visibility)
+      .unwrap_or(self.default_visibility)
+  }
+
+  /// URL under the public base, whatever the visibility of the key. Only
+  /// for URLs that are stored and must not expire.
+  pub fn public_url(&self, key: &str) -> String {
+    format!("{}/{}", self.public_base_url, key)
+  }
+
+  /// Public URL or presigned GET URL, depending on the key's visibility.
+  pub async fn resolve(&self, key: &str) -> Result<String, StorageError> {
+    match self.visibility(key) {
+      Visibility::Public => Ok(self.public_url(key)),
+      Visibility::Private => self.storage.presign_get(key, self.signed_expiry).await,
+    }
+  }
+}